@@ -0,0 +1,84 @@
+use crate::image::RgbaImage;
+use runner_core::graphics::data::TileSheet;
+use runner_core::rect::Rect;
+
+/// Only tiles from these sheets run into each other
+pub const SHEETS: [TileSheet; 2] = [TileSheet::Character, TileSheet::Enemy];
+
+// Pixels that are at least this opaque are solid
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Tiles are cut into these many horizontal bands & each band gets a hitbox
+// that hugs the solid pixels in it. This follows the outline closely enough
+// (think of the wings of a bat vs its body) while keeping collisions cheap
+const BANDS: u32 = 3;
+
+/// Hitboxes (relative to the tile) of the tile at `bounds` in `sheet`.
+/// Empty if the tile is fully transparent
+pub fn derive(sheet: &RgbaImage, bounds: Rect) -> Vec<Rect> {
+    let bands = BANDS.min(bounds.height());
+    (0..bands)
+        .filter_map(|band| {
+            let top = bounds.height() * band / bands;
+            let bottom = bounds.height() * (band + 1) / bands;
+            solid_bounds(sheet, bounds, top, bottom)
+        })
+        .collect()
+}
+
+/// Smallest rect (relative to the tile) holding all the solid pixels in the
+/// rows from `top` till `bottom`
+fn solid_bounds(sheet: &RgbaImage, bounds: Rect, top: u32, bottom: u32) -> Option<Rect> {
+    let mut solid: Option<(u32, u32, u32, u32)> = None;
+    for y in top..bottom {
+        for x in 0..bounds.width() {
+            let alpha = sheet.alpha(bounds.x() as u32 + x, bounds.y() as u32 + y);
+            if alpha >= ALPHA_THRESHOLD {
+                solid = Some(match solid {
+                    None => (x, y, x, y),
+                    Some((left, top, right, bottom)) => {
+                        (left.min(x), top.min(y), right.max(x), bottom.max(y))
+                    }
+                })
+            }
+        }
+    }
+
+    solid.map(|(left, top, right, bottom)| {
+        Rect::new(left as i32, top as i32, right - left + 1, bottom - top + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLID: [u8; 4] = [0, 0, 0, 255];
+
+    #[test]
+    fn transparent_tile_has_no_hitboxes() {
+        // Faint pixels don't count either
+        let mut sheet = RgbaImage::new(8, 8);
+        sheet.set_pixel(3, 3, [0, 0, 0, ALPHA_THRESHOLD - 1]);
+        assert!(derive(&sheet, Rect::new(0, 0, 8, 8)).is_empty());
+    }
+
+    #[test]
+    fn hitboxes_hug_the_solid_pixels_of_each_band() {
+        // Wide wings on top of a thin body, in a tile that doesn't start at
+        // the top left of the sheet. Last band is left empty
+        let mut sheet = RgbaImage::new(12, 11);
+        for x in 2..12 {
+            sheet.set_pixel(x, 3, SOLID);
+        }
+
+        for y in 4..8 {
+            sheet.set_pixel(6, y, SOLID);
+        }
+
+        assert_eq!(
+            derive(&sheet, Rect::new(2, 2, 10, 9)),
+            vec![Rect::new(0, 1, 10, 2), Rect::new(4, 3, 1, 3)]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use png::{BitDepth, ColorType, Compression, Decoder, Encoder, FilterType, Transformations};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+const BYTES_PER_PIXEL: usize = 4;
+
+/// 8 bits per channel RGBA image. Whatever the source images are in (like
+/// paletted or without alpha), they are converted into this to keep copying
+/// pixels around simple
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Fully transparent image
+    pub fn new(width: u32, height: u32) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * BYTES_PER_PIXEL],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn load(path: &Path) -> RgbaImage {
+        let file = File::open(path).unwrap_or_else(|_| panic!("Couldn't open file: {:?}", path));
+
+        // Expand palettes, low bit depths & transparency chunks to get
+        // a plain 8 bits per channel image
+        let mut decoder = Decoder::new(file);
+        decoder.set_transformations(Transformations::EXPAND | Transformations::STRIP_16);
+        let (info, mut reader) = decoder
+            .read_info()
+            .unwrap_or_else(|_| panic!("Couldn't decode file: {:?}", path));
+
+        let mut buffer = vec![0; info.buffer_size()];
+        reader
+            .next_frame(&mut buffer)
+            .unwrap_or_else(|_| panic!("Couldn't decode file: {:?}", path));
+
+        let pixels = match info.color_type {
+            ColorType::RGBA => buffer,
+            ColorType::RGB => buffer
+                .chunks(3)
+                .flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            ColorType::GrayscaleAlpha => buffer
+                .chunks(2)
+                .flat_map(|ga| vec![ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            ColorType::Grayscale => buffer.iter().flat_map(|&g| vec![g, g, g, 255]).collect(),
+            ColorType::Indexed => panic!("Palette wasn't expanded for file: {:?}", path),
+        };
+
+        RgbaImage {
+            width: info.width,
+            height: info.height,
+            pixels,
+        }
+    }
+
+    /// Sheets are bundled with the game. So, this tries to keep them small:
+    /// pixels are written with a palette (or as greyscale) when they allow
+    /// for it & every filter is tried to keep whichever compresses best
+    pub fn save(&self, path: &Path) {
+        let encoding = self
+            .palette_encoding()
+            .or_else(|| self.grayscale_encoding())
+            .unwrap_or_else(|| Encoding {
+                color_type: ColorType::RGBA,
+                bit_depth: BitDepth::Eight,
+                palette: None,
+                trns: None,
+                data: self.pixels.clone(),
+            });
+
+        let smallest = [
+            FilterType::NoFilter,
+            FilterType::Sub,
+            FilterType::Up,
+            FilterType::Avg,
+            FilterType::Paeth,
+        ]
+        .iter()
+        .map(|&filter| self.encode(&encoding, filter))
+        .min_by_key(|bytes| bytes.len())
+        .expect("There is always a filter to try");
+
+        fs::write(path, smallest).unwrap_or_else(|_| panic!("Couldn't write file: {:?}", path));
+    }
+
+    fn encode(&self, encoding: &Encoding, filter: FilterType) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = Encoder::new(&mut bytes, self.width, self.height);
+            encoder.set_color(encoding.color_type);
+            encoder.set_depth(encoding.bit_depth);
+            encoder.set_compression(Compression::Best);
+            encoder.set_filter(filter);
+            if let Some(palette) = &encoding.palette {
+                encoder.set_palette(palette.clone());
+            }
+
+            if let Some(trns) = &encoding.trns {
+                encoder.set_trns(trns.clone());
+            }
+
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&encoding.data))
+                .expect("Couldn't encode image");
+        }
+
+        bytes
+    }
+
+    /// [None] if there are more colors than a palette can hold
+    fn palette_encoding(&self) -> Option<Encoding> {
+        let mut colors: Vec<&[u8]> = Vec::new();
+        let mut seen: HashSet<&[u8]> = HashSet::new();
+        for pixel in self.pixels.chunks(BYTES_PER_PIXEL) {
+            if seen.insert(pixel) {
+                if colors.len() == 256 {
+                    return None;
+                }
+
+                colors.push(pixel);
+            }
+        }
+
+        // Colors that are fully opaque needn't be listed in the transparency
+        // chunk as long as they come after all the others
+        colors.sort_by_key(|color| color[3] == 255);
+        let indices: HashMap<&[u8], u8> = colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (*color, index as u8))
+            .collect();
+
+        // Use as few bits as the palette allows for. Rows start on a new byte
+        let bits_per_pixel: usize = match colors.len() {
+            0..=2 => 1,
+            3..=4 => 2,
+            5..=16 => 4,
+            _ => 8,
+        };
+
+        let row_length = (self.width as usize * bits_per_pixel + 8 - 1) / 8;
+        let mut data = vec![0; row_length * self.height as usize];
+        for (i, pixel) in self.pixels.chunks(BYTES_PER_PIXEL).enumerate() {
+            let index = indices[pixel];
+            let (x, y) = (i % self.width as usize, i / self.width as usize);
+            let bit_offset = x * bits_per_pixel;
+            let shift = 8 - bits_per_pixel - bit_offset % 8;
+            data[y * row_length + bit_offset / 8] |= index << shift;
+        }
+
+        let mut trns: Vec<u8> = colors.iter().map(|color| color[3]).collect();
+        while trns.last() == Some(&255) {
+            trns.pop();
+        }
+
+        Some(Encoding {
+            color_type: ColorType::Indexed,
+            bit_depth: match bits_per_pixel {
+                1 => BitDepth::One,
+                2 => BitDepth::Two,
+                4 => BitDepth::Four,
+                _ => BitDepth::Eight,
+            },
+            palette: Some(
+                colors
+                    .iter()
+                    .flat_map(|color| color[..3].to_vec())
+                    .collect(),
+            ),
+            trns: if trns.is_empty() { None } else { Some(trns) },
+            data,
+        })
+    }
+
+    /// [None] if there is any color in the image
+    fn grayscale_encoding(&self) -> Option<Encoding> {
+        let is_grayscale = self
+            .pixels
+            .chunks(BYTES_PER_PIXEL)
+            .all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]);
+
+        if is_grayscale {
+            Some(Encoding {
+                color_type: ColorType::GrayscaleAlpha,
+                bit_depth: BitDepth::Eight,
+                palette: None,
+                trns: None,
+                data: self
+                    .pixels
+                    .chunks(BYTES_PER_PIXEL)
+                    .flat_map(|pixel| vec![pixel[0], pixel[3]])
+                    .collect(),
+            })
+        } else {
+            None
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_pixel(&mut self, x: u32, y: u32, rgba: [u8; BYTES_PER_PIXEL]) {
+        let offset = self.offset(x, y);
+        self.pixels[offset..offset + BYTES_PER_PIXEL].copy_from_slice(&rgba)
+    }
+
+    pub fn alpha(&self, x: u32, y: u32) -> u8 {
+        self.pixels[self.offset(x, y) + BYTES_PER_PIXEL - 1]
+    }
+
+    /// Copies a `width` x `height` area at (`from_x`, `from_y`) in `source`
+    /// to (`to_x`, `to_y`) in this image
+    pub fn copy_from(
+        &mut self,
+        source: &RgbaImage,
+        from_x: u32,
+        from_y: u32,
+        width: u32,
+        height: u32,
+        to_x: u32,
+        to_y: u32,
+    ) {
+        let row_length = width as usize * BYTES_PER_PIXEL;
+        for row in 0..height {
+            let from = source.offset(from_x, from_y + row);
+            let to = self.offset(to_x, to_y + row);
+            self.pixels[to..to + row_length]
+                .copy_from_slice(&source.pixels[from..from + row_length]);
+        }
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * BYTES_PER_PIXEL
+    }
+}
+
+/// How pixels are laid out in a PNG
+struct Encoding {
+    color_type: ColorType,
+    bit_depth: BitDepth,
+    palette: Option<Vec<u8>>,
+    trns: Option<Vec<u8>>,
+    data: Vec<u8>,
+}
@@ -0,0 +1,162 @@
+use crate::image::RgbaImage;
+use runner_core::graphics::data::{Tile, TileSheet};
+use runner_core::graphics::tile_atlas::{SheetManifest, TileAtlas, TileAtlasManifest};
+use runner_core::rect::Rect;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+mod hitboxes;
+mod image;
+mod packer;
+
+const DEFAULT_INPUT_DIR: &str = "assets_raw";
+const DEFAULT_OUTPUT_DIR: &str = "assets_processed";
+const TILE_ATLAS_FILENAME: &str = "tile_atlas.ron";
+
+struct PackedSheet {
+    image_file: String,
+    image: RgbaImage,
+    source_width: u32,
+    source_height: u32,
+}
+
+/// Usage: `runner_atlas_packer [input-dir] [output-dir]` (defaults to
+/// `assets_raw` & `assets_processed`).
+///
+/// Reads the tile atlas manifest & sheets from the input directory, copies
+/// out only the tiles the game knows about & packs them into trimmed sheets.
+/// The sheets & the matching manifest (along with hitboxes for tiles that
+/// don't have any yet) are written to the output directory
+pub fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let input_dir = PathBuf::from(args.get(0).map_or(DEFAULT_INPUT_DIR, String::as_str));
+    let output_dir = PathBuf::from(args.get(1).map_or(DEFAULT_OUTPUT_DIR, String::as_str));
+
+    let manifest_path = input_dir.join(TILE_ATLAS_FILENAME);
+    let source_manifest = TileAtlasManifest::from_ron(
+        &fs::read_to_string(&manifest_path)
+            .unwrap_or_else(|_| panic!("Couldn't read file: {:?}", manifest_path)),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
+
+    let tile_atlas =
+        TileAtlas::from_manifest(&source_manifest).unwrap_or_else(|error| panic!("{}", error));
+
+    // Pack everything before writing anything. This way, nothing is left
+    // half done if something goes wrong
+    let mut manifest = TileAtlasManifest::default();
+    let packed_sheets: Vec<PackedSheet> = TileSheet::ALL
+        .iter()
+        .map(|&tile_sheet| {
+            pack_sheet(
+                &input_dir,
+                &source_manifest,
+                &tile_atlas,
+                tile_sheet,
+                &mut manifest,
+            )
+        })
+        .collect();
+
+    // Shouldn't happen. But, better to find out now than when the game starts
+    TileAtlas::from_manifest(&manifest)
+        .unwrap_or_else(|error| panic!("Packed atlas is broken: {}", error));
+
+    fs::create_dir_all(&output_dir)
+        .unwrap_or_else(|_| panic!("Couldn't create directory: {:?}", output_dir));
+
+    for packed_sheet in packed_sheets.iter() {
+        packed_sheet
+            .image
+            .save(&output_dir.join(&packed_sheet.image_file));
+
+        println!(
+            "{}: {}x{} => {}x{}",
+            packed_sheet.image_file,
+            packed_sheet.source_width,
+            packed_sheet.source_height,
+            packed_sheet.image.width(),
+            packed_sheet.image.height()
+        );
+    }
+
+    let manifest_path = output_dir.join(TILE_ATLAS_FILENAME);
+    fs::write(&manifest_path, manifest.to_ron())
+        .unwrap_or_else(|_| panic!("Couldn't write file: {:?}", manifest_path));
+}
+
+/// Adds the packed sheet, its tiles & their hitboxes to the `manifest`
+fn pack_sheet(
+    input_dir: &PathBuf,
+    source_manifest: &TileAtlasManifest,
+    tile_atlas: &TileAtlas,
+    tile_sheet: TileSheet,
+    manifest: &mut TileAtlasManifest,
+) -> PackedSheet {
+    let image_file = tile_atlas.image_file(tile_sheet).to_string();
+    let source = RgbaImage::load(&input_dir.join(&image_file));
+
+    let tiles: Vec<Tile> = Tile::all()
+        .into_iter()
+        .filter(|tile| tile.tile_sheet() == tile_sheet)
+        .collect();
+
+    let sources: Vec<Rect> = tiles
+        .iter()
+        .map(|&tile| tile_atlas.tile_data(tile).bounds_in_tile_sheet)
+        .collect();
+
+    let sizes: Vec<(u32, u32)> = sources
+        .iter()
+        .map(|bounds| (bounds.width(), bounds.height()))
+        .collect();
+
+    let packing = packer::pack(&sizes);
+    let mut image = RgbaImage::new(packing.width, packing.height);
+    for ((tile, bounds), &(x, y)) in tiles.iter().zip(sources.iter()).zip(&packing.positions) {
+        image.copy_from(
+            &source,
+            bounds.x() as u32,
+            bounds.y() as u32,
+            bounds.width(),
+            bounds.height(),
+            x,
+            y,
+        );
+
+        manifest.tiles.insert(
+            tile.name(),
+            Rect::new(x as i32, y as i32, bounds.width(), bounds.height()),
+        );
+
+        // Hitboxes are relative to the tile. So, hand made ones can be kept
+        // as they are. Others are worked out from the image (only for tiles
+        // that can run into each other)
+        let tile_hitboxes = match source_manifest.hitboxes.get(&tile.name()) {
+            Some(tile_hitboxes) => tile_hitboxes.clone(),
+            None if hitboxes::SHEETS.contains(&tile_sheet) => hitboxes::derive(&source, *bounds),
+            None => Vec::new(),
+        };
+
+        if !tile_hitboxes.is_empty() {
+            manifest.hitboxes.insert(tile.name(), tile_hitboxes);
+        }
+    }
+
+    manifest.sheets.insert(
+        tile_sheet.name(),
+        SheetManifest {
+            image: image_file.clone(),
+            width: packing.width,
+            height: packing.height,
+        },
+    );
+
+    PackedSheet {
+        image_file,
+        image,
+        source_width: source.width(),
+        source_height: source.height(),
+    }
+}
@@ -0,0 +1,156 @@
+/// Empty pixels around each sprite. Without these, scaled sprites could pick
+/// up a few pixels from their neighbours when drawn
+const PADDING: u32 = 2;
+
+/// Sheets don't get any wider than this
+const MAX_SHEET_WIDTH: u32 = 8192;
+
+pub struct Packing {
+    pub width: u32,
+    pub height: u32,
+
+    /// Top left of every sprite (in the same order as they were given in)
+    pub positions: Vec<(u32, u32)>,
+}
+
+impl Packing {
+    fn longer_side(&self) -> u32 {
+        self.width.max(self.height)
+    }
+
+    fn area(&self) -> u32 {
+        self.width * self.height
+    }
+}
+
+/// Packs sprites of the given sizes (width, height) into shelves => rows
+/// of sprites, tallest first. Shelves aren't the tightest packing there
+/// is, but for sprites of similar heights (like ours), they come close.
+///
+/// A few widths are tried & the squarest packing is picked. Sheets far
+/// taller than wide (or vice versa) can run into texture size limits
+pub fn pack(sizes: &[(u32, u32)]) -> Packing {
+    let widest = sizes
+        .iter()
+        .map(|(width, _)| width + 2 * PADDING)
+        .max()
+        .unwrap_or(1);
+
+    let mut sheet_width = widest.next_power_of_two();
+    let mut best_packing = pack_in_shelves(sizes, sheet_width);
+    while sheet_width < MAX_SHEET_WIDTH {
+        sheet_width *= 2;
+        let packing = pack_in_shelves(sizes, sheet_width);
+        if (packing.longer_side(), packing.area())
+            < (best_packing.longer_side(), best_packing.area())
+        {
+            best_packing = packing
+        }
+    }
+
+    best_packing
+}
+
+fn pack_in_shelves(sizes: &[(u32, u32)], max_width: u32) -> Packing {
+    // Tallest first so that every shelf is as tall as its first sprite
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (width_a, height_a) = sizes[a];
+        let (width_b, height_b) = sizes[b];
+        height_b.cmp(&height_a).then(width_b.cmp(&width_a))
+    });
+
+    let mut positions = vec![(0, 0); sizes.len()];
+    let mut shelf_x = 0;
+    let mut shelf_y = 0;
+    let mut shelf_height = 0;
+    let mut used_width = 0;
+
+    for index in order {
+        let (width, height) = sizes[index];
+        let padded_width = width + 2 * PADDING;
+        let padded_height = height + 2 * PADDING;
+
+        // Start a new shelf if this sprite doesn't fit in the current one
+        if shelf_x > 0 && shelf_x + padded_width > max_width {
+            shelf_y += shelf_height;
+            shelf_x = 0;
+            shelf_height = 0;
+        }
+
+        positions[index] = (shelf_x + PADDING, shelf_y + PADDING);
+        shelf_x += padded_width;
+        shelf_height = shelf_height.max(padded_height);
+        used_width = used_width.max(shelf_x);
+    }
+
+    Packing {
+        width: used_width.max(1),
+        height: (shelf_y + shelf_height).max(1),
+        positions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bounds of the sprite at `index` along with its padding
+    fn padded_bounds(
+        packing: &Packing,
+        sizes: &[(u32, u32)],
+        index: usize,
+    ) -> (u32, u32, u32, u32) {
+        let (x, y) = packing.positions[index];
+        let (width, height) = sizes[index];
+        (
+            x - PADDING,
+            y - PADDING,
+            x + width + PADDING,
+            y + height + PADDING,
+        )
+    }
+
+    #[test]
+    fn sprites_are_padded_apart_within_the_sheet() {
+        let sizes = [
+            (30, 20),
+            (12, 40),
+            (64, 8),
+            (5, 5),
+            (20, 20),
+            (33, 17),
+            (1, 1),
+        ];
+        let packing = pack(&sizes);
+        for index in 0..sizes.len() {
+            let (left, top, right, bottom) = padded_bounds(&packing, &sizes, index);
+            assert!(right <= packing.width && bottom <= packing.height);
+
+            for other in index + 1..sizes.len() {
+                let (other_left, other_top, other_right, other_bottom) =
+                    padded_bounds(&packing, &sizes, other);
+                let is_apart = right <= other_left
+                    || other_right <= left
+                    || bottom <= other_top
+                    || other_bottom <= top;
+                assert!(is_apart, "sprites {} & {} overlap", index, other);
+            }
+        }
+    }
+
+    #[test]
+    fn squarest_packing_is_picked() {
+        // Padded, these are 14x14. 4 to a shelf makes for a square sheet
+        let sizes = vec![(10, 10); 16];
+        let packing = pack(&sizes);
+        assert_eq!((packing.width, packing.height), (56, 56));
+    }
+
+    #[test]
+    fn nothing_to_pack_still_makes_a_sheet() {
+        let packing = pack(&[]);
+        assert_eq!((packing.width, packing.height), (1, 1));
+        assert!(packing.positions.is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::resources::game_play::MILLISECONDS_IN_A_TICK;
+use instant::Instant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Where game play gets its notion of "now" from. Only the difference
+/// between two readings matters. So, the starting point of a clock can
+/// be anything as long as it never goes back in time
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+}
+
+/// Keeps up with the wall-clock. This is what a player expects
+pub struct RealTimeClock {
+    started_at: Instant,
+}
+
+impl RealTimeClock {
+    pub fn new() -> RealTimeClock {
+        RealTimeClock {
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl Default for RealTimeClock {
+    fn default() -> Self {
+        RealTimeClock::new()
+    }
+}
+
+impl Clock for RealTimeClock {
+    fn now(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// Time stands still until the owner explicitly moves it forward. Useful
+/// for tests, replays or to fast-forward a simulation.
+///
+/// Clones share the same time. Hand one over to the game & hold on to
+/// another to advance it
+#[derive(Clone)]
+pub struct ManualClock {
+    elapsed_in_ms: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock {
+            elapsed_in_ms: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed_in_ms
+            .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    pub fn advance_by_ticks(&self, ticks: u64) {
+        self.advance(Duration::from_millis(
+            ticks * u64::from(MILLISECONDS_IN_A_TICK),
+        ))
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.elapsed_in_ms.load(Ordering::SeqCst))
+    }
+}
@@ -1,18 +1,30 @@
-mod animatable;
+pub mod animatable;
 mod cloud;
 mod drawable;
 pub mod enemy;
-mod ground;
+pub mod ground;
+mod hidden;
 mod icon;
 pub mod input;
 mod letter;
+mod life;
+pub mod pickup;
 pub mod player;
 pub mod score;
+mod shield_bubble;
+mod transform;
+mod velocity;
 
 pub use animatable::Animatable;
 pub use cloud::Cloud;
 pub use drawable::Drawable;
 pub use enemy::Enemy;
 pub use ground::Ground;
+pub use hidden::Hidden;
 pub use icon::Icon;
 pub use letter::Letter;
+pub use life::Life;
+pub use pickup::Pickup;
+pub use shield_bubble::ShieldBubble;
+pub use transform::Transform;
+pub use velocity::Velocity;
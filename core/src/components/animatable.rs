@@ -1,8 +1,121 @@
+use crate::components::animatable::data::Clip;
+use crate::graphics::data::Tile;
 use specs::Component;
 use specs::VecStorage;
 
-#[derive(Component)]
+/// Plays a [Clip] (check out [crate::systems::AnimationSystem]). Systems
+/// only need to switch clips as things change
+#[derive(Copy, Clone, Component, Debug)]
 #[storage(VecStorage)]
 pub struct Animatable {
-    pub current_step_started_at_tick: u64,
+    pub clip: Clip,
+    pub clip_started_at_tick: u64,
+    pub frame_index: usize,
+    pub frame_started_at_tick: u64,
+
+    // Frames last these many times as long as they usually would. For eg.,
+    // legs move faster when running faster
+    pub ticks_multiplier: f32,
+}
+
+impl Animatable {
+    pub fn new(clip: Clip) -> Animatable {
+        Animatable {
+            clip,
+            clip_started_at_tick: 0,
+            frame_index: 0,
+            frame_started_at_tick: 0,
+            ticks_multiplier: 1.0,
+        }
+    }
+
+    /// Starts `clip` over from its first frame
+    pub fn play(&mut self, clip: Clip, current_tick: u64) {
+        *self = Animatable {
+            clip_started_at_tick: current_tick,
+            frame_started_at_tick: current_tick,
+            ..Animatable::new(clip)
+        }
+    }
+
+    pub fn current_tile(&self) -> Tile {
+        self.clip.frame(self.frame_index).tile
+    }
+}
+
+pub mod data {
+    use crate::graphics::data::Tile;
+
+    // Enough for any clip we have. Keeping the frames inline (instead of in
+    // a `Vec`) lets clips be copied around cheaply
+    const MAX_FRAMES_IN_CLIP: usize = 8;
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub struct Frame {
+        pub tile: Tile,
+        pub ticks: u16,
+    }
+
+    impl Frame {
+        pub fn new(tile: Tile, ticks: u16) -> Frame {
+            Frame { tile, ticks }
+        }
+    }
+
+    /// Frames that are shown one after the other. Looping clips go back to
+    /// the first frame after the last one & the others stay on the last one
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub struct Clip {
+        frames: [Frame; MAX_FRAMES_IN_CLIP],
+        frame_count: usize,
+        is_looping: bool,
+    }
+
+    impl Clip {
+        pub fn looping(frames: &[Frame]) -> Clip {
+            Clip::new(frames, true)
+        }
+
+        pub fn one_shot(frames: &[Frame]) -> Clip {
+            Clip::new(frames, false)
+        }
+
+        /// Just the one tile for as long as the clip is played
+        pub fn still(tile: Tile) -> Clip {
+            Clip::one_shot(&[Frame::new(tile, 0)])
+        }
+
+        pub fn frame(&self, index: usize) -> Frame {
+            self.frames[..self.frame_count][index]
+        }
+
+        /// [None] once a clip that doesn't loop is over
+        pub fn next_frame_index(&self, index: usize) -> Option<usize> {
+            if index + 1 < self.frame_count {
+                Some(index + 1)
+            } else if self.is_looping {
+                Some(0)
+            } else {
+                None
+            }
+        }
+
+        fn new(frames: &[Frame], is_looping: bool) -> Clip {
+            if frames.is_empty() || frames.len() > MAX_FRAMES_IN_CLIP {
+                panic!(
+                    "Clips need 1 to {} frames. Got {}",
+                    MAX_FRAMES_IN_CLIP,
+                    frames.len()
+                )
+            }
+
+            let mut all_frames = [frames[0]; MAX_FRAMES_IN_CLIP];
+            all_frames[..frames.len()].copy_from_slice(frames);
+            Clip {
+                frames: all_frames,
+                frame_count: frames.len(),
+                is_looping,
+            }
+        }
+    }
 }
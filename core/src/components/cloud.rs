@@ -1,16 +1,7 @@
 use specs::Component;
-use specs::VecStorage;
+use specs::NullStorage;
 
-#[derive(Component)]
-#[storage(VecStorage)]
-pub struct Cloud {
-    pub speed_in_wc_per_tick: u16,
-}
-
-impl Cloud {
-    pub fn new(speed_in_wc_per_tick: u16) -> Self {
-        Self {
-            speed_in_wc_per_tick,
-        }
-    }
-}
+/// Clouds just drift along (check out [crate::components::Velocity])
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Cloud;
@@ -1,11 +1,55 @@
+use crate::components::Transform;
 use crate::graphics::data::TileData;
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::rect::Rect;
+use crate::render::Layer;
 use specs::Component;
 use specs::VecStorage;
 
-#[derive(Component)]
+/// What to draw & how big. Where it goes is up to its [Transform]
+#[derive(Copy, Clone, Component)]
 #[storage(VecStorage)]
 pub struct Drawable {
     pub tile_data: TileData,
-    pub world_bounds: Rect,
+    pub width_in_wc: u32,
+    pub height_in_wc: u32,
+    pub layer: Layer,
+}
+
+impl Drawable {
+    /// Where the tile is drawn in the world when it is at `transform`
+    pub fn world_bounds(&self, transform: &Transform) -> Rect {
+        Rect::new(
+            transform.left(),
+            transform.top(),
+            self.width_in_wc,
+            self.height_in_wc,
+        )
+    }
+
+    /// Hitboxes of the tile scaled & moved to wherever the tile is drawn
+    /// in the world. Worked out as they are asked for since this is done
+    /// for every enemy, every tick
+    pub fn world_hitboxes<'a>(
+        &self,
+        transform: &Transform,
+        tile_atlas: &'a TileAtlas,
+    ) -> impl Iterator<Item = Rect> + 'a {
+        let world_bounds = self.world_bounds(transform);
+        let tile_bounds = self.tile_data.bounds_in_tile_sheet;
+        let x_scale = world_bounds.width() as f32 / tile_bounds.width() as f32;
+        let y_scale = world_bounds.height() as f32 / tile_bounds.height() as f32;
+
+        tile_atlas
+            .hitboxes(self.tile_data.tile)
+            .iter()
+            .map(move |hitbox| {
+                Rect::new(
+                    world_bounds.left() + (hitbox.x() as f32 * x_scale) as i32,
+                    world_bounds.top() + (hitbox.y() as f32 * y_scale) as i32,
+                    ((hitbox.width() as f32 * x_scale) as u32).max(1),
+                    ((hitbox.height() as f32 * y_scale) as u32).max(1),
+                )
+            })
+    }
 }
@@ -1,21 +1,31 @@
 use crate::components::enemy::data::Position;
+use crate::data::enemy_data::Behaviour;
 use specs::Component;
 use specs::VecStorage;
 
-#[derive(Component)]
+#[derive(Copy, Clone, Component)]
 #[storage(VecStorage)]
 pub struct Enemy {
-    pub speed_in_wc_per_tick: u16,
-    pub ticks_in_movement: u8,
+    pub behaviour: Behaviour,
     pub position: Position,
+    pub spawned_at_tick: u64,
+
+    // Where the enemy was spawned. Behaviours move it around from here
+    pub base_world_bottom: i32,
 }
 
 impl Enemy {
-    pub fn new(speed_in_wc_per_tick: u16, ticks_in_movement: u8, position: Position) -> Self {
+    pub fn new(
+        behaviour: Behaviour,
+        position: Position,
+        spawned_at_tick: u64,
+        base_world_bottom: i32,
+    ) -> Self {
         Self {
-            speed_in_wc_per_tick,
-            ticks_in_movement,
+            behaviour,
             position,
+            spawned_at_tick,
+            base_world_bottom,
         }
     }
 }
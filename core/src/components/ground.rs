@@ -4,3 +4,18 @@ use specs::NullStorage;
 #[derive(Component, Default)]
 #[storage(NullStorage)]
 pub struct Ground;
+
+pub mod data {
+    /// What a column of ground looks like
+    #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+    pub enum Terrain {
+        Flat,
+
+        // Nothing to stand on. Has to be jumped across
+        Pit,
+
+        // Flat with a platform floating above it. Platforms can be jumped
+        // on to from below
+        Raised,
+    }
+}
@@ -0,0 +1,8 @@
+use specs::Component;
+use specs::NullStorage;
+
+/// Drawables of entities with this aren't drawn (but are still there
+/// for everything else). For eg., to make the player blink
+#[derive(Component, Clone, Copy, Default)]
+#[storage(NullStorage)]
+pub struct Hidden;
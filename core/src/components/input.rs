@@ -2,7 +2,7 @@ use crate::components::input::data::Direction;
 use specs::Component;
 use specs::VecStorage;
 
-#[derive(Component, Default)]
+#[derive(Clone, Component, Default)]
 #[storage(VecStorage)]
 pub struct InputControlled {
     up: bool,
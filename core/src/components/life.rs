@@ -0,0 +1,10 @@
+use specs::Component;
+use specs::VecStorage;
+
+/// One of the icons showing how many lives the player has left. The
+/// left most one is at index `0`
+#[derive(Component)]
+#[storage(VecStorage)]
+pub struct Life {
+    pub index: u8,
+}
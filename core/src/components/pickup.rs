@@ -0,0 +1,29 @@
+use crate::components::pickup::data::Kind;
+use specs::Component;
+use specs::VecStorage;
+
+#[derive(Component)]
+#[storage(VecStorage)]
+pub struct Pickup {
+    pub kind: Kind,
+}
+
+impl Pickup {
+    pub fn new(kind: Kind) -> Self {
+        Self { kind }
+    }
+}
+
+pub mod data {
+    #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+    pub enum Kind {
+        // Adds to the score
+        Coin,
+
+        // Takes the next hit instead of the player
+        Shield,
+
+        // Pulls nearby coins in for a while
+        Magnet,
+    }
+}
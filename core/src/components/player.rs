@@ -3,26 +3,59 @@ use crate::jump_physics::JumpPhysics;
 use specs::Component;
 use specs::VecStorage;
 
-#[derive(Component)]
+#[derive(Clone, Component)]
 #[storage(VecStorage)]
 pub struct Player {
     pub jump_physics: Option<JumpPhysics>,
     pub most_recent_max_jump_end_at: u64,
     pub most_recent_max_slide_end_at: u64,
+
+    // Tick at which `up` was most recently pressed (as opposed to being
+    // held) & whether it was engaged in the last tick to find that out
+    pub jump_pressed_at: Option<u64>,
+    pub was_up_engaged: bool,
+
+    // Set when the player drops down without jumping. Jumping is allowed
+    // for a little while after this (coyote time)
+    pub fall_started_at: Option<u64>,
+
     pub current_action: Action,
     pub is_hit: bool,
+    pub lives: u8,
+
+    // Hits don't count until this tick
+    pub invulnerable_until: u64,
+
+    // Power ups picked up along the way
+    pub has_shield: bool,
+    pub magnet_until: u64,
 }
 
 impl Player {
-    pub fn new() -> Player {
+    pub fn new(lives: u8) -> Player {
         Player {
             jump_physics: None,
             most_recent_max_jump_end_at: 0,
             most_recent_max_slide_end_at: 0,
+            jump_pressed_at: None,
+            was_up_engaged: false,
+            fall_started_at: None,
             current_action: Action::Still,
             is_hit: false,
+            lives,
+            invulnerable_until: 0,
+            has_shield: false,
+            magnet_until: 0,
         }
     }
+
+    pub fn is_invulnerable(&self, current_tick: u64) -> bool {
+        current_tick < self.invulnerable_until
+    }
+
+    pub fn has_magnet(&self, current_tick: u64) -> bool {
+        current_tick < self.magnet_until
+    }
 }
 
 pub mod data {
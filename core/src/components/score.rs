@@ -1,3 +1,4 @@
+use crate::graphics::data::NumberTile;
 use specs::Component;
 use specs::VecStorage;
 
@@ -7,6 +8,7 @@ pub struct Score {
     pub position: Position,
 }
 
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Position {
     One,
     Ten,
@@ -15,3 +17,52 @@ pub enum Position {
     TenThousand,
     HundredThousand,
 }
+
+impl Position {
+    pub const ALL: [Position; 6] = [
+        Position::One,
+        Position::Ten,
+        Position::Hundred,
+        Position::Thousand,
+        Position::TenThousand,
+        Position::HundredThousand,
+    ];
+
+    /// Tile to show for the digit of `score` at this position
+    pub fn digit_tile(&self, score: u64) -> NumberTile {
+        // Math here is to first find the remainder by diving with the
+        // next immediate order of magnitude & then divide again by the
+        // current order of magnitude to get a single positional digit.
+        //
+        // Eg., to get the hundredth position for 1234, find the remainder
+        // with 1000 & then the quotient with 100
+        //      1234 % 1000 = 234
+        //      234 / 100 = 2
+        let remainder_finding_divisor = match self {
+            Position::One => 10,
+            Position::Ten => 100,
+            Position::Hundred => 1000,
+            Position::Thousand => 10000,
+            Position::TenThousand => 100000,
+            Position::HundredThousand => 1000000,
+        };
+
+        let remainder = score % remainder_finding_divisor;
+        let quotient_finding_divisor = remainder_finding_divisor / 10;
+
+        let single_digit = remainder / quotient_finding_divisor;
+        match single_digit {
+            0 => NumberTile::Zero,
+            1 => NumberTile::One,
+            2 => NumberTile::Two,
+            3 => NumberTile::Three,
+            4 => NumberTile::Four,
+            5 => NumberTile::Five,
+            6 => NumberTile::Six,
+            7 => NumberTile::Seven,
+            8 => NumberTile::Eight,
+            9 => NumberTile::Nine,
+            _ => panic!("Score math is off!"),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use specs::Component;
+use specs::NullStorage;
+
+/// Goes around the player for as long as the player has a shield
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct ShieldBubble;
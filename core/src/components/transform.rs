@@ -0,0 +1,75 @@
+use specs::Component;
+use specs::VecStorage;
+
+// Positions are kept in these many parts of a world coordinate. Fixed point
+// instead of floats so that things moving at the same speed (for eg., ground
+// tiles) stay in step no matter where they are in the world
+const PARTS_IN_A_WC: i64 = 256;
+
+/// Where the top left of anything that is in the world is. This is the only
+/// place that says where something is. Speeds needn't be whole world
+/// coordinates per tick. So, this is kept in fractions of a world coordinate
+/// & is only rounded (down) when it is drawn or run into (check out
+/// [crate::components::Drawable]). This way, a speed of 2.2 moves things by
+/// 2 for four ticks & by 3 for the fifth instead of always by 2
+#[derive(Copy, Clone, Component, Debug, Default, Eq, Hash, PartialEq)]
+#[storage(VecStorage)]
+pub struct Transform {
+    left_in_parts: i64,
+    top_in_parts: i64,
+}
+
+impl Transform {
+    pub fn new(left: i32, top: i32) -> Transform {
+        Transform {
+            left_in_parts: i64::from(left) * PARTS_IN_A_WC,
+            top_in_parts: i64::from(top) * PARTS_IN_A_WC,
+        }
+    }
+
+    pub fn left(&self) -> i32 {
+        self.left_in_parts.div_euclid(PARTS_IN_A_WC) as i32
+    }
+
+    pub fn top(&self) -> i32 {
+        self.top_in_parts.div_euclid(PARTS_IN_A_WC) as i32
+    }
+
+    /// Where (0, 0) would be if it had been moved around along with this.
+    /// Anything that moves along with this is as far from the origin as it
+    /// was from (0, 0) when it started out in the same spot. For eg., the
+    /// surfaces of the ground (check out [crate::surfaces::Surfaces])
+    pub fn origin(&self) -> Transform {
+        Transform {
+            left_in_parts: self.left_in_parts - i64::from(self.left()) * PARTS_IN_A_WC,
+            top_in_parts: self.top_in_parts - i64::from(self.top()) * PARTS_IN_A_WC,
+        }
+    }
+
+    /// Moves by `speed_in_wc_per_tick` for `ticks`
+    pub fn move_left(&mut self, speed_in_wc_per_tick: f32, ticks: u64) {
+        self.left_in_parts -= Transform::parts_in(speed_in_wc_per_tick, ticks)
+    }
+
+    /// Takes back [Transform::move_left]
+    pub fn move_right(&mut self, speed_in_wc_per_tick: f32, ticks: u64) {
+        self.left_in_parts += Transform::parts_in(speed_in_wc_per_tick, ticks)
+    }
+
+    /// By whole world coordinates. Whatever fraction there is stays as is
+    pub fn offset(&mut self, x: i32, y: i32) {
+        self.left_in_parts += i64::from(x) * PARTS_IN_A_WC;
+        self.top_in_parts += i64::from(y) * PARTS_IN_A_WC;
+    }
+
+    /// By fractions of a world coordinate too. For eg., when something is
+    /// headed somewhere at an angle
+    pub fn move_by(&mut self, x_in_wc: f32, y_in_wc: f32) {
+        self.left_in_parts += (x_in_wc * PARTS_IN_A_WC as f32).round() as i64;
+        self.top_in_parts += (y_in_wc * PARTS_IN_A_WC as f32).round() as i64;
+    }
+
+    fn parts_in(speed_in_wc_per_tick: f32, ticks: u64) -> i64 {
+        (speed_in_wc_per_tick * PARTS_IN_A_WC as f32).round() as i64 * ticks as i64
+    }
+}
@@ -0,0 +1,52 @@
+use crate::data::GroundData;
+use specs::Component;
+use specs::VecStorage;
+
+/// How fast something moves to the left (check out [crate::systems::MovementSystem])
+#[derive(Copy, Clone, Component, Debug)]
+#[storage(VecStorage)]
+pub enum Velocity {
+    // Keeps up with the ground however fast it is going. For eg., pickups
+    // that are just lying around on it
+    Ground,
+
+    // At its own pace. Doesn't change when the game speeds up
+    Left {
+        speed_in_wc_per_tick: f32,
+    },
+
+    // Same as `Left` till the player is within range (& still ahead). Makes
+    // a dash for it after that. Worked out afresh every tick
+    Dash {
+        speed_in_wc_per_tick: f32,
+        range_in_wc: u16,
+        extra_speed_in_wc_per_tick: f32,
+    },
+}
+
+impl Velocity {
+    /// `distance_to_player` is how far ahead the player is. [None] if there
+    /// isn't a player to chase or if the player has already been passed
+    pub fn speed_in_wc_per_tick(
+        &self,
+        ground_data: &GroundData,
+        distance_to_player: Option<i32>,
+    ) -> f32 {
+        match *self {
+            Velocity::Ground => ground_data.speed_in_wc_per_tick,
+            Velocity::Left {
+                speed_in_wc_per_tick,
+            } => speed_in_wc_per_tick,
+            Velocity::Dash {
+                speed_in_wc_per_tick,
+                range_in_wc,
+                extra_speed_in_wc_per_tick,
+            } => match distance_to_player {
+                Some(distance) if distance <= i32::from(range_in_wc) => {
+                    speed_in_wc_per_tick + extra_speed_in_wc_per_tick
+                }
+                _ => speed_in_wc_per_tick,
+            },
+        }
+    }
+}
@@ -1,10 +1,13 @@
 mod cloud_data;
+pub mod difficulty;
 pub mod enemy_data;
 mod ground_data;
+mod pickup_data;
 mod player_data;
 mod world_data;
 
 pub use cloud_data::CloudData;
-pub use ground_data::GroundData;
+pub use ground_data::{GroundData, GroundSpeeds};
+pub use pickup_data::PickupData;
 pub use player_data::PlayerData;
 pub use world_data::WorldData;
@@ -11,7 +11,7 @@ impl CloudData {
         // Let's say that one enemy wave is one world length & we want
         // a particular number of enemies in a wave
         let wave_length_in_wc = world_data.bounds().width();
-        let wave_ticks_count = (wave_length_in_wc / ground_data.speed_in_wc_per_tick as u32) as u16;
+        let wave_ticks_count = ground_data.ticks_to_cover(wave_length_in_wc);
         Self {
             cloud_wave_ticks_count: wave_ticks_count,
             cloud_count_in_wave: 1,
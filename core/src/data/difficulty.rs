@@ -0,0 +1,360 @@
+use crate::data::enemy_data::EnemyKind;
+use crate::resources::game_play;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// How the game gets harder as it goes on => how fast the ground moves &
+/// which enemies (& how many of them) show up. Can be read from RON (check
+/// out [Difficulty::from_ron]) to play with custom curves. For eg.,
+///
+/// ```text
+/// (
+///     name: "bats only",
+///     starting_speed_multiplier: 1.0,
+///     max_speed_multiplier: 3.0,
+///     speed_up: (multiplier: 1.1, every: Seconds(10)),
+///     stages: [
+///         (starts_at: Seconds(0), enemy_count_in_wave: 2, enemies: [Bat]),
+///         (starts_at: Distance(5000), enemy_count_in_wave: 5, enemies: [Bat]),
+///     ],
+/// )
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Difficulty {
+    pub name: String,
+
+    // Multipliers are on top of the default speed of the ground. There is
+    // no cap if the max is `inf`
+    pub starting_speed_multiplier: f32,
+    pub max_speed_multiplier: f32,
+    pub speed_up: SpeedUp,
+
+    // In order. A stage lasts till the next one starts. The first one is
+    // used from the beginning even if it is set to start later
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SpeedUp {
+    pub multiplier: f32,
+    pub every: Progress,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stage {
+    pub starts_at: Progress,
+    pub enemy_count_in_wave: u8,
+    pub enemies: Vec<EnemyKind>,
+}
+
+/// How far into a game => time spent playing or the distance covered (in
+/// world coordinates)
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Progress {
+    Seconds(u32),
+    Distance(u64),
+}
+
+impl Progress {
+    pub fn is_reached(&self, ticks: u64, distance_in_wc: u64) -> bool {
+        match *self {
+            Progress::Seconds(seconds) => {
+                ticks * u64::from(game_play::MILLISECONDS_IN_A_TICK) >= u64::from(seconds) * 1000
+            }
+            Progress::Distance(distance) => distance_in_wc >= distance,
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty::normal()
+    }
+}
+
+impl Difficulty {
+    pub fn from_ron(text: &str) -> Result<Difficulty, DifficultyError> {
+        let difficulty: Difficulty = ron::from_str(text).map_err(DifficultyError::Parse)?;
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
+
+    /// A list of difficulties. For eg., `[(name: "custom", ...), ...]`
+    pub fn list_from_ron(text: &str) -> Result<Vec<Difficulty>, DifficultyError> {
+        let difficulties: Vec<Difficulty> = ron::from_str(text).map_err(DifficultyError::Parse)?;
+
+        let problems: Vec<DifficultyProblem> =
+            difficulties.iter().flat_map(Difficulty::problems).collect();
+
+        if problems.is_empty() {
+            Ok(difficulties)
+        } else {
+            Err(DifficultyError::Invalid(problems))
+        }
+    }
+
+    /// Custom difficulties could ask for anything. The game should never
+    /// stop moving & there should always be a next speed up to wait for
+    pub fn validate(&self) -> Result<(), DifficultyError> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(DifficultyError::Invalid(problems))
+        }
+    }
+
+    fn problems(&self) -> Vec<DifficultyProblem> {
+        let mut problems = Vec::new();
+        let multipliers = [
+            ("starting_speed_multiplier", self.starting_speed_multiplier),
+            ("max_speed_multiplier", self.max_speed_multiplier),
+            ("speed_up.multiplier", self.speed_up.multiplier),
+        ];
+
+        for &(field, multiplier) in multipliers.iter() {
+            if multiplier.is_nan() || multiplier <= 0.0 {
+                problems.push(DifficultyProblem::NotPositive {
+                    difficulty: self.name.clone(),
+                    field,
+                })
+            }
+        }
+
+        if self.max_speed_multiplier < self.starting_speed_multiplier {
+            problems.push(DifficultyProblem::MaxBelowStarting(self.name.clone()))
+        }
+
+        if let Progress::Seconds(0) | Progress::Distance(0) = self.speed_up.every {
+            problems.push(DifficultyProblem::ZeroSpeedUpInterval(self.name.clone()))
+        }
+
+        // Time & distance can't be compared with each other. Only stages
+        // going by the same one have to be in order
+        for (index, pair) in self.stages.windows(2).enumerate() {
+            let is_out_of_order = match (pair[0].starts_at, pair[1].starts_at) {
+                (Progress::Seconds(a), Progress::Seconds(b)) => b < a,
+                (Progress::Distance(a), Progress::Distance(b)) => b < a,
+                _ => false,
+            };
+
+            if is_out_of_order {
+                problems.push(DifficultyProblem::StageOutOfOrder {
+                    difficulty: self.name.clone(),
+                    stage_index: index + 1,
+                })
+            }
+        }
+
+        problems
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new())
+            .expect("Difficulty couldn't be serialized")
+    }
+
+    /// The ones that come with the game. Easiest first
+    pub fn presets() -> Vec<Difficulty> {
+        vec![Difficulty::easy(), Difficulty::normal(), Difficulty::hard()]
+    }
+
+    /// Slow & steady with fewer enemies. Nothing flies till a while into
+    /// the game
+    pub fn easy() -> Difficulty {
+        Difficulty {
+            name: String::from("easy"),
+            starting_speed_multiplier: 1.0,
+            max_speed_multiplier: 2.0,
+            speed_up: SpeedUp {
+                multiplier: 1.05,
+                every: Progress::Seconds(15),
+            },
+            stages: vec![
+                Stage {
+                    starts_at: Progress::Seconds(0),
+                    enemy_count_in_wave: 2,
+                    enemies: vec![EnemyKind::Bug, EnemyKind::Mouse, EnemyKind::Spider],
+                },
+                Stage {
+                    starts_at: Progress::Seconds(60),
+                    enemy_count_in_wave: 3,
+                    enemies: EnemyKind::ALL.to_vec(),
+                },
+            ],
+        }
+    }
+
+    /// How the game has always been => speeds up by 10% every 10 seconds
+    /// without ever stopping. Replays from before there were difficulties
+    /// are played at this one. So, this shouldn't change
+    pub fn normal() -> Difficulty {
+        Difficulty {
+            name: String::from("normal"),
+            starting_speed_multiplier: 1.0,
+            max_speed_multiplier: f32::INFINITY,
+            speed_up: SpeedUp {
+                multiplier: 1.1,
+                every: Progress::Seconds(10),
+            },
+            stages: vec![Stage {
+                starts_at: Progress::Seconds(0),
+                enemy_count_in_wave: 4,
+                enemies: EnemyKind::ALL.to_vec(),
+            }],
+        }
+    }
+
+    /// Starts fast & only gets busier
+    pub fn hard() -> Difficulty {
+        Difficulty {
+            name: String::from("hard"),
+            starting_speed_multiplier: 1.5,
+            max_speed_multiplier: 5.0,
+            speed_up: SpeedUp {
+                multiplier: 1.1,
+                every: Progress::Seconds(8),
+            },
+            stages: vec![
+                Stage {
+                    starts_at: Progress::Seconds(0),
+                    enemy_count_in_wave: 5,
+                    enemies: EnemyKind::ALL.to_vec(),
+                },
+                Stage {
+                    starts_at: Progress::Seconds(45),
+                    enemy_count_in_wave: 6,
+                    enemies: EnemyKind::ALL.to_vec(),
+                },
+            ],
+        }
+    }
+
+    /// Index of the stage that should be going on `ticks` into the game
+    /// after covering `distance_in_wc`. [None] if there aren't any stages
+    pub fn stage_index_at(&self, ticks: u64, distance_in_wc: u64) -> Option<usize> {
+        if self.stages.is_empty() {
+            return None;
+        }
+
+        let index = self
+            .stages
+            .iter()
+            .rposition(|stage| stage.starts_at.is_reached(ticks, distance_in_wc))
+            .unwrap_or(0);
+
+        Some(index)
+    }
+}
+
+#[derive(Debug)]
+pub enum DifficultyError {
+    Parse(ron::Error),
+    Invalid(Vec<DifficultyProblem>),
+}
+
+#[derive(Debug)]
+pub enum DifficultyProblem {
+    NotPositive {
+        difficulty: String,
+        field: &'static str,
+    },
+    MaxBelowStarting(String),
+    ZeroSpeedUpInterval(String),
+    StageOutOfOrder {
+        difficulty: String,
+        stage_index: usize,
+    },
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DifficultyError::Parse(error) => write!(f, "Difficulty couldn't be parsed: {}", error),
+            DifficultyError::Invalid(problems) => {
+                write!(f, "Difficulty has {} problem(s)", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for DifficultyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DifficultyProblem::NotPositive { difficulty, field } => {
+                write!(f, "{} of {} should be more than 0", field, difficulty)
+            }
+            DifficultyProblem::MaxBelowStarting(difficulty) => write!(
+                f,
+                "max_speed_multiplier of {} is below its starting_speed_multiplier",
+                difficulty
+            ),
+            DifficultyProblem::ZeroSpeedUpInterval(difficulty) => {
+                write!(f, "speed_up.every of {} should be more than 0", difficulty)
+            }
+            DifficultyProblem::StageOutOfOrder {
+                difficulty,
+                stage_index,
+            } => write!(
+                f,
+                "Stage {} of {} starts before the one before it",
+                stage_index, difficulty
+            ),
+        }
+    }
+}
+
+impl Error for DifficultyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        for difficulty in Difficulty::presets() {
+            assert!(difficulty.validate().is_ok(), "{}", difficulty.name);
+        }
+    }
+
+    #[test]
+    fn uncapped_speed_survives_ron() {
+        let normal = Difficulty::normal();
+        assert_eq!(Difficulty::from_ron(&normal.to_ron()).unwrap(), normal);
+    }
+
+    #[test]
+    fn bad_speeds_are_called_out() {
+        let difficulty = Difficulty {
+            starting_speed_multiplier: 2.0,
+            max_speed_multiplier: 1.0,
+            speed_up: SpeedUp {
+                multiplier: -1.0,
+                every: Progress::Seconds(0),
+            },
+            ..Difficulty::easy()
+        };
+
+        match difficulty.validate() {
+            Err(DifficultyError::Invalid(problems)) => assert_eq!(problems.len(), 3),
+            _ => panic!("Should have been invalid"),
+        }
+    }
+
+    #[test]
+    fn stages_out_of_order_are_called_out() {
+        let mut difficulty = Difficulty::hard();
+        difficulty.stages.reverse();
+        assert!(Difficulty::list_from_ron(&format!("[{}]", difficulty.to_ron())).is_err());
+
+        // Time & distance aren't compared
+        difficulty.stages[1].starts_at = Progress::Distance(0);
+        assert!(difficulty.validate().is_ok());
+    }
+}
@@ -1,10 +1,16 @@
+use crate::components::animatable::data::{Clip, Frame};
+use crate::components::Velocity;
+use crate::data::difficulty::Stage;
 use crate::data::{GroundData, WorldData};
+use crate::graphics::data::{EnemyTile, Tile};
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct EnemyData {
     pub min_ticks_between_enemies: u16,
     pub enemy_wave_ticks_count: u16,
     pub enemy_count_in_wave: u8,
+    pub enemy_kinds: Vec<EnemyKind>,
     pub bat_animation: Animation,
     pub bee_animation: Animation,
     pub bug_animation: Animation,
@@ -15,56 +21,194 @@ pub struct EnemyData {
 impl EnemyData {
     /// Shouldn't use [speed_multiplier] to change the animation speed or
     /// the movement speed of the enemies since it is just the player who
-    /// is speeding up & not the enemies
-    pub fn new(world_data: WorldData, ground_data: GroundData) -> Self {
+    /// is speeding up & not the enemies.
+    ///
+    /// How many enemies show up & which ones are up to the `stage` of the
+    /// difficulty being played. No enemies at all without a stage
+    pub fn new(world_data: WorldData, ground_data: GroundData, stage: Option<&Stage>) -> Self {
         // Let's say that one enemy wave is one world length & we want
         // a particular number of enemies in a wave
-        let enemy_count_in_wave = 4u8;
+        let enemy_count_in_wave = stage.map_or(0, |stage| stage.enemy_count_in_wave);
         let wave_length_in_wc = world_data.bounds().width();
-        let wave_ticks_count = (wave_length_in_wc / ground_data.speed_in_wc_per_tick as u32) as u16;
+        let wave_ticks_count = ground_data.ticks_to_cover(wave_length_in_wc);
         Self {
-            min_ticks_between_enemies: wave_ticks_count / (u16::from(enemy_count_in_wave) * 2),
+            min_ticks_between_enemies: wave_ticks_count
+                / (u16::from(enemy_count_in_wave.max(1)) * 2),
             enemy_wave_ticks_count: wave_ticks_count,
             enemy_count_in_wave,
+            enemy_kinds: stage.map_or_else(Vec::new, |stage| stage.enemies.clone()),
 
             // Bats fly fast but since they have sizable wings, needn't
-            // animate that fast
+            // animate that fast. Long, lazy swoops
             bat_animation: Animation {
-                speed_in_wc_per_tick: (ground_data.speed_in_wc_per_tick + 4) as u16,
-                ticks_in_movement: 20,
+                speed_in_wc_per_tick: ground_data.speed_in_wc_per_tick + 4.0,
+                clip: EnemyData::two_step_clip(EnemyTile::BatFly1, EnemyTile::BatFly2, 20),
+                behaviour: Behaviour::SineFlight {
+                    amplitude_in_wc: 24,
+                    ticks_in_period: 90,
+                },
             },
 
             // Bees are slow than bats but owing to their wing size is
-            // going to have a lot more flaps
+            // going to have a lot more flaps. Quick, small bobs
             bee_animation: Animation {
-                speed_in_wc_per_tick: (ground_data.speed_in_wc_per_tick + 2) as u16,
-                ticks_in_movement: 10,
+                speed_in_wc_per_tick: ground_data.speed_in_wc_per_tick + 2.0,
+                clip: EnemyData::two_step_clip(EnemyTile::BeeFly1, EnemyTile::BeeFly2, 10),
+                behaviour: Behaviour::SineFlight {
+                    amplitude_in_wc: 12,
+                    ticks_in_period: 40,
+                },
             },
 
             // Bug needn't move. It just stays put on the ground
             bug_animation: Animation {
-                speed_in_wc_per_tick: (ground_data.speed_in_wc_per_tick) as u16,
-                ticks_in_movement: std::u8::MAX,
+                speed_in_wc_per_tick: ground_data.speed_in_wc_per_tick,
+                clip: EnemyData::two_step_clip(
+                    EnemyTile::BugRun1,
+                    EnemyTile::BugRun2,
+                    u16::from(std::u8::MAX),
+                ),
+                behaviour: Behaviour::Scroll,
             },
 
-            // Mouse should move
+            // Mouse should move & makes a dash for it when it gets close
+            // to the player
             mouse_animation: Animation {
-                speed_in_wc_per_tick: (ground_data.speed_in_wc_per_tick + 1) as u16,
-                ticks_in_movement: 10,
+                speed_in_wc_per_tick: ground_data.speed_in_wc_per_tick + 1.0,
+                clip: EnemyData::two_step_clip(EnemyTile::MouseRun1, EnemyTile::MouseRun2, 10),
+                behaviour: Behaviour::Dash {
+                    range_in_wc: 200,
+                    extra_speed_in_wc_per_tick: 3.0,
+                },
             },
 
             // Spider should move. Our spider is pretty big. So, it can walk
-            // a bit slower than the mouse
+            // a bit slower than the mouse. Hops every now & then
             spider_animation: Animation {
-                speed_in_wc_per_tick: (ground_data.speed_in_wc_per_tick + 2) as u16,
-                ticks_in_movement: 14,
+                speed_in_wc_per_tick: ground_data.speed_in_wc_per_tick + 2.0,
+                clip: EnemyData::two_step_clip(EnemyTile::SpiderRun1, EnemyTile::SpiderRun2, 14),
+                behaviour: Behaviour::Hop {
+                    height_in_wc: 30,
+                    ticks_in_hop: 30,
+                    ticks_between_hops: 40,
+                },
             },
         }
     }
+
+    /// Every enemy goes back & forth between two tiles (flapping wings or
+    /// moving legs)
+    fn two_step_clip(first: EnemyTile, second: EnemyTile, ticks_in_step: u16) -> Clip {
+        Clip::looping(&[
+            Frame::new(Tile::Enemy { tile: first }, ticks_in_step),
+            Frame::new(Tile::Enemy { tile: second }, ticks_in_step),
+        ])
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EnemyKind {
+    Bat,
+    Bee,
+    Bug,
+    Mouse,
+    Spider,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 5] = [
+        EnemyKind::Bat,
+        EnemyKind::Bee,
+        EnemyKind::Bug,
+        EnemyKind::Mouse,
+        EnemyKind::Spider,
+    ];
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Animation {
-    pub speed_in_wc_per_tick: u16,
-    pub ticks_in_movement: u8,
+    pub speed_in_wc_per_tick: f32,
+    pub clip: Clip,
+    pub behaviour: Behaviour,
+}
+
+/// How an enemy moves on top of going left at its speed
+#[derive(Copy, Clone, Debug)]
+pub enum Behaviour {
+    // Nothing more than going left
+    Scroll,
+
+    // Bobs up from where it was spawned & back down again
+    SineFlight {
+        amplitude_in_wc: u8,
+        ticks_in_period: u16,
+    },
+
+    // Goes up in an arc & lands back. Stays on the ground for a while
+    // between hops
+    Hop {
+        height_in_wc: u8,
+        ticks_in_hop: u8,
+        ticks_between_hops: u8,
+    },
+
+    // Speeds up once the player is within range (& still ahead)
+    Dash {
+        range_in_wc: u16,
+        extra_speed_in_wc_per_tick: f32,
+    },
+}
+
+impl Behaviour {
+    /// Going left at `speed_in_wc_per_tick` as this behaviour would have it
+    pub fn velocity(&self, speed_in_wc_per_tick: f32) -> Velocity {
+        match *self {
+            Behaviour::Dash {
+                range_in_wc,
+                extra_speed_in_wc_per_tick,
+            } => Velocity::Dash {
+                speed_in_wc_per_tick,
+                range_in_wc,
+                extra_speed_in_wc_per_tick,
+            },
+            _ => Velocity::Left {
+                speed_in_wc_per_tick,
+            },
+        }
+    }
+
+    /// How high above the spawn height the enemy should be after being
+    /// around for `ticks_alive`. Never goes below where it was spawned
+    /// so that enemies don't sink into the ground or drop on to a player
+    /// that is sliding under them
+    pub fn height_in_wc(&self, ticks_alive: u64) -> i32 {
+        match *self {
+            Behaviour::Scroll | Behaviour::Dash { .. } => 0,
+
+            Behaviour::SineFlight {
+                amplitude_in_wc,
+                ticks_in_period,
+            } => {
+                let angle = 2.0 * std::f32::consts::PI * ticks_alive as f32
+                    / f32::from(ticks_in_period.max(1));
+                (f32::from(amplitude_in_wc) * (1.0 - angle.cos()) / 2.0) as i32
+            }
+
+            Behaviour::Hop {
+                height_in_wc,
+                ticks_in_hop,
+                ticks_between_hops,
+            } => {
+                let ticks_in_cycle = u64::from(ticks_in_hop) + u64::from(ticks_between_hops);
+                let ticks_into_hop = ticks_alive % ticks_in_cycle.max(1);
+                if ticks_into_hop >= u64::from(ticks_in_hop) {
+                    0
+                } else {
+                    // Parabola that starts & ends on the ground
+                    let progress = ticks_into_hop as f32 / f32::from(ticks_in_hop);
+                    (4.0 * f32::from(height_in_wc) * progress * (1.0 - progress)) as i32
+                }
+            }
+        }
+    }
 }
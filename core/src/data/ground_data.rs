@@ -1,12 +1,59 @@
+// Slowest the ground can go
+const MIN_SPEED_IN_WC_PER_TICK: f32 = 0.5;
+
 #[derive(Copy, Clone, Debug)]
 pub struct GroundData {
-    pub speed_in_wc_per_tick: u16,
+    pub speed_in_wc_per_tick: f32,
 }
 
 impl GroundData {
+    /// Ground always moves. Even if a custom difficulty asks for it not to.
+    /// Speed needn't be in whole world coordinates (check out
+    /// [crate::components::Transform]). So, every speed up counts
     pub fn new(speed_multiplier: f32) -> Self {
         Self {
-            speed_in_wc_per_tick: (2.0 * speed_multiplier) as u16,
+            speed_in_wc_per_tick: (2.0 * speed_multiplier).max(MIN_SPEED_IN_WC_PER_TICK),
         }
     }
+
+    /// Ticks it takes the ground to move by `distance_in_wc`
+    pub fn ticks_to_cover(&self, distance_in_wc: u32) -> u16 {
+        (distance_in_wc as f32 / self.speed_in_wc_per_tick) as u16
+    }
+}
+
+/// How fast the ground is going to move, tick after tick. Speed ups that are
+/// already due (check out [crate::systems::GameSpeedUpdater]) are in here.
+/// So, plans (check out [crate::spawn_planner]) see them coming
+#[derive(Clone, Debug)]
+pub struct GroundSpeeds {
+    current: GroundData,
+
+    // In order. Each one holds from its tick till the next one
+    changes: Vec<(u64, GroundData)>,
+}
+
+impl GroundSpeeds {
+    /// Never changes from `ground_data`
+    pub fn steady(ground_data: GroundData) -> Self {
+        Self {
+            current: ground_data,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Changes to `ground_data` from `tick` on. Has to come after the
+    /// changes there already are
+    pub fn with_change(mut self, tick: u64, ground_data: GroundData) -> Self {
+        self.changes.push((tick, ground_data));
+        self
+    }
+
+    pub fn at(&self, tick: u64) -> GroundData {
+        self.changes
+            .iter()
+            .rev()
+            .find(|(from_tick, _)| *from_tick <= tick)
+            .map_or(self.current, |(_, ground_data)| *ground_data)
+    }
 }
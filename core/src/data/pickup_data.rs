@@ -0,0 +1,33 @@
+use crate::data::{GroundData, WorldData};
+
+#[derive(Copy, Clone, Debug)]
+pub struct PickupData {
+    pub min_ticks_between_pickups: u16,
+    pub pickup_wave_ticks_count: u16,
+    pub pickup_count_in_wave: u8,
+    pub points_in_coin: u64,
+
+    // Coins within range are pulled towards the player for a while
+    pub ticks_in_magnet: u16,
+    pub magnet_range_in_wc: u16,
+    pub magnet_speed_in_wc_per_tick: u16,
+}
+
+impl PickupData {
+    pub fn new(world_data: WorldData, ground_data: GroundData) -> Self {
+        // Like enemies, a pickup wave is one world length. There are fewer
+        // pickups than enemies in a wave to keep them worth chasing
+        let pickup_count_in_wave = 3u8;
+        let wave_length_in_wc = world_data.bounds().width();
+        let wave_ticks_count = ground_data.ticks_to_cover(wave_length_in_wc);
+        Self {
+            min_ticks_between_pickups: wave_ticks_count / (u16::from(pickup_count_in_wave) * 2),
+            pickup_wave_ticks_count: wave_ticks_count,
+            pickup_count_in_wave,
+            points_in_coin: 10,
+            ticks_in_magnet: 600,
+            magnet_range_in_wc: 240,
+            magnet_speed_in_wc_per_tick: 8,
+        }
+    }
+}
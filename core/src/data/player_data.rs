@@ -1,8 +1,10 @@
+use crate::components::animatable::data::{Clip, Frame};
+use crate::graphics::data::{CharacterTile, Tile};
+
 #[derive(Copy, Clone, Debug)]
 pub struct PlayerData {
     pub ticks_in_max_jump: u8,
     pub ticks_in_slide: u8,
-    pub ticks_in_run_step: u8,
     pub ticks_between_consecutive_jumps: u8,
     pub ticks_between_consecutive_slides: u8,
     pub ticks_multiplier_for_faster_running: f32,
@@ -10,6 +12,31 @@ pub struct PlayerData {
     pub max_jump_height_in_wc: u8,
     pub speed_in_wc_per_tick_fast_run: u16,
     pub speed_in_wc_per_tick_slow_run: u16,
+
+    // Jump can still be made for these many ticks after running off the
+    // ground & a jump pressed these many ticks before it is possible to
+    // jump again isn't lost
+    pub ticks_in_coyote_time: u8,
+    pub ticks_in_jump_buffer: u8,
+
+    // Gravity is multiplied by these when coming down & when floating around
+    // the top of a jump (as long as the vertical speed is below the given one)
+    pub fall_gravity_multiplier: f32,
+    pub apex_hang_gravity_multiplier: f32,
+    pub apex_hang_speed_in_wc_per_tick: f32,
+
+    // Every hit costs a life & the game is over when there are none left.
+    // For a while after a hit, the player blinks & can't be hit again
+    pub lives: u8,
+    pub ticks_in_invulnerability: u16,
+    pub ticks_in_invulnerability_blink: u8,
+
+    // What the player looks like while doing each of these
+    pub still_clip: Clip,
+    pub run_clip: Clip,
+    pub jump_clip: Clip,
+    pub slide_clip: Clip,
+    pub hit_clip: Clip,
 }
 
 impl PlayerData {
@@ -17,7 +44,6 @@ impl PlayerData {
         Self {
             ticks_in_max_jump: 60,
             ticks_in_slide: 30,
-            ticks_in_run_step: 16,
             ticks_between_consecutive_jumps: 10,
             ticks_between_consecutive_slides: 10,
             ticks_multiplier_for_faster_running: 0.5,
@@ -25,6 +51,46 @@ impl PlayerData {
             max_jump_height_in_wc: 120,
             speed_in_wc_per_tick_fast_run: 4,
             speed_in_wc_per_tick_slow_run: 4,
+            ticks_in_coyote_time: 6,
+            ticks_in_jump_buffer: 8,
+            fall_gravity_multiplier: 2.0,
+            apex_hang_gravity_multiplier: 0.5,
+            apex_hang_speed_in_wc_per_tick: 1.0,
+            lives: 1,
+            ticks_in_invulnerability: 120,
+            ticks_in_invulnerability_blink: 8,
+            still_clip: Clip::still(Tile::Character {
+                tile: CharacterTile::Still,
+            }),
+            run_clip: PlayerData::run_clip(16),
+            jump_clip: Clip::still(Tile::Character {
+                tile: CharacterTile::Jump,
+            }),
+            slide_clip: Clip::still(Tile::Character {
+                tile: CharacterTile::Slide,
+            }),
+            hit_clip: Clip::still(Tile::Character {
+                tile: CharacterTile::Hit,
+            }),
         }
     }
+
+    /// More than one life lets the player take that many hits before
+    /// the game is over
+    pub fn with_lives(self, lives: u8) -> PlayerData {
+        if lives == 0 {
+            panic!("Player needs at least one life!")
+        }
+
+        PlayerData { lives, ..self }
+    }
+
+    fn run_clip(ticks_in_run_step: u16) -> Clip {
+        let frame = |tile| Frame::new(Tile::Character { tile }, ticks_in_run_step);
+        Clip::looping(&[
+            frame(CharacterTile::Run1),
+            frame(CharacterTile::Run2),
+            frame(CharacterTile::Run3),
+        ])
+    }
 }
@@ -6,6 +6,7 @@ pub struct WorldData {
     sky_color: Color,
     instructions_bg_color: Color,
     world_bounds: Rect,
+    rng_seed: Option<u64>,
 }
 
 impl Default for WorldData {
@@ -16,6 +17,7 @@ impl Default for WorldData {
             sky_color: Color::from_rgba(58, 154, 216, u8::max_value()),
             instructions_bg_color: Color::from_rgba(0, 0, 0, u8::max_value() / 2),
             world_bounds: Rect::new(-((width / 2) as i32), -((height / 2) as i32), width, height),
+            rng_seed: None,
         }
     }
 }
@@ -25,6 +27,15 @@ impl WorldData {
         WorldData::default()
     }
 
+    /// Every game will be played with the same seed. Without this, a
+    /// random seed is picked for every game
+    pub fn with_rng_seed(self, rng_seed: u64) -> WorldData {
+        WorldData {
+            rng_seed: Some(rng_seed),
+            ..self
+        }
+    }
+
     pub fn sky_color(&self) -> Color {
         self.sky_color
     }
@@ -37,6 +48,10 @@ impl WorldData {
         self.world_bounds
     }
 
+    pub fn rng_seed(&self) -> Option<u64> {
+        self.rng_seed
+    }
+
     pub fn world_surface_at(&self) -> i32 {
         self.bounds().bottom() / 3
     }
@@ -0,0 +1,133 @@
+use crate::color::Color;
+use crate::components::player::Player;
+use crate::components::{Cloud, Drawable, Enemy, Ground, Pickup, Transform};
+use crate::data::WorldData;
+use crate::graphics::data::{LetterTile, NumberTile, Tile};
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::rect::Rect;
+use crate::render::Layer;
+use crate::resources::GamePlay;
+use specs::join::Join;
+use specs::{Component, ReadStorage, World, WorldExt};
+
+// Text is small enough to stay out of the way but big enough to be read
+const GLYPH_HEIGHT_IN_WC: u32 = 12;
+const GLYPH_SPACING_IN_WC: i32 = 1;
+const LINE_SPACING_IN_WC: i32 = 4;
+const MARGIN_IN_WC: i32 = 8;
+
+/// Outline frontends draw over the game when debugging is turned on
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DebugShape {
+    pub world_bounds: Rect,
+    pub color: Color,
+}
+
+/// Everything frontends should draw over the game when debugging is turned
+/// on => outlines of what is in the world & some numbers about the game
+#[derive(Default)]
+pub struct DebugOverlay {
+    pub shapes: Vec<DebugShape>,
+    pub drawables: Vec<(Transform, Drawable)>,
+}
+
+impl DebugOverlay {
+    /// Frontends know how fast they are drawing. Hence, `fps` comes from them
+    pub(crate) fn build(
+        world: &World,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        fps: u32,
+    ) -> DebugOverlay {
+        let mut shapes = Vec::new();
+        add_bounds::<Ground>(world, Color::from_rgba(255, 165, 0, 255), &mut shapes);
+        add_bounds::<Cloud>(world, Color::from_rgba(255, 255, 255, 255), &mut shapes);
+        add_bounds::<Pickup>(world, Color::from_rgba(255, 255, 0, 255), &mut shapes);
+        add_bounds::<Enemy>(world, Color::from_rgba(255, 0, 0, 255), &mut shapes);
+        add_bounds::<Player>(world, Color::from_rgba(0, 255, 0, 255), &mut shapes);
+        add_hitboxes::<Enemy>(world, tile_atlas, &mut shapes);
+        add_hitboxes::<Player>(world, tile_atlas, &mut shapes);
+
+        let game_play = world.fetch::<GamePlay>();
+        let lines = [
+            format!("TICK {}", game_play.ticks_animated()),
+            format!("BACKLOG {}", game_play.ticks_to_animate()),
+            format!("ENTITIES {}", world.entities().join().count()),
+            format!("FPS {}", fps),
+        ];
+
+        let mut drawables = Vec::new();
+        let mut top = world_data.bounds().top() + MARGIN_IN_WC;
+        for line in lines.iter() {
+            let mut left = world_data.bounds().left() + MARGIN_IN_WC;
+            for character in line.chars() {
+                let transform = Transform::new(left, top);
+                let drawable = build_glyph(tile_atlas, character);
+                left = drawable.world_bounds(&transform).right() + GLYPH_SPACING_IN_WC;
+                drawables.push((transform, drawable));
+            }
+
+            top += GLYPH_HEIGHT_IN_WC as i32 + LINE_SPACING_IN_WC;
+        }
+
+        DebugOverlay { shapes, drawables }
+    }
+}
+
+fn add_bounds<C: Component>(world: &World, color: Color, shapes: &mut Vec<DebugShape>) {
+    let transforms_storage: ReadStorage<Transform> = world.read_storage();
+    let drawables_storage: ReadStorage<Drawable> = world.read_storage();
+    let components_storage: ReadStorage<C> = world.read_storage();
+    for (transform, drawable, _) in
+        (&transforms_storage, &drawables_storage, &components_storage).join()
+    {
+        shapes.push(DebugShape {
+            world_bounds: drawable.world_bounds(transform),
+            color,
+        })
+    }
+}
+
+/// Only for those that can run into each other
+fn add_hitboxes<C: Component>(world: &World, tile_atlas: &TileAtlas, shapes: &mut Vec<DebugShape>) {
+    let transforms_storage: ReadStorage<Transform> = world.read_storage();
+    let drawables_storage: ReadStorage<Drawable> = world.read_storage();
+    let components_storage: ReadStorage<C> = world.read_storage();
+    for (transform, drawable, _) in
+        (&transforms_storage, &drawables_storage, &components_storage).join()
+    {
+        for world_bounds in drawable.world_hitboxes(transform, tile_atlas) {
+            shapes.push(DebugShape {
+                world_bounds,
+                color: Color::from_rgba(255, 0, 255, 255),
+            })
+        }
+    }
+}
+
+fn build_glyph(tile_atlas: &TileAtlas, character: char) -> Drawable {
+    let tile_data = tile_atlas.tile_data(glyph_tile(character));
+    let tile_bounds = tile_data.bounds_in_tile_sheet;
+    let width = tile_bounds.width() * GLYPH_HEIGHT_IN_WC / tile_bounds.height();
+    Drawable {
+        tile_data,
+        width_in_wc: width.max(1),
+        height_in_wc: GLYPH_HEIGHT_IN_WC,
+        layer: Layer::Hud,
+    }
+}
+
+/// Anything that can't be shown is left blank
+fn glyph_tile(character: char) -> Tile {
+    match character.to_digit(10) {
+        Some(digit) => Tile::Number {
+            tile: NumberTile::ALL[digit as usize],
+        },
+        None if character.is_ascii_uppercase() => Tile::Letter {
+            tile: LetterTile::ALL[(character as u8 - b'A') as usize],
+        },
+        None => Tile::Letter {
+            tile: LetterTile::SPACE,
+        },
+    }
+}
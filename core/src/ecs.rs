@@ -1,11 +1,16 @@
 use crate::components;
+use crate::data::difficulty::Difficulty;
 use crate::data::enemy_data::EnemyData;
-use crate::data::{CloudData, GroundData, PlayerData, WorldData};
-use crate::entities::{GroundEntity, IconEntity, LetterEntity, PlayerEntity, ScoreEntity};
-use crate::resources::{EventQueue, GamePlay};
+use crate::data::{CloudData, GroundData, GroundSpeeds, PickupData, PlayerData, WorldData};
+use crate::entities::{
+    GroundEntity, IconEntity, LetterEntity, LifeEntity, PlayerEntity, ScoreEntity,
+};
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::resources::{EventQueue, GamePlay, GameRng, GroundLaidOut};
 use crate::systems::{
-    CloudSystem, CollisionSystem, EnemySystem, EventSystem, GamePlayTickUpdater, GameSpeedUpdater,
-    GroundSystem, PlayerSystem, ScoreSystem,
+    AnimationSystem, CloudSystem, CollisionSystem, EnemySystem, EventSystem, GamePlayTickUpdater,
+    GameSpeedUpdater, GroundSystem, LifeSystem, MovementSystem, PickupSystem, PlayerSystem,
+    ScoreSystem,
 };
 use specs::{Dispatcher, DispatcherBuilder, World, WorldExt};
 
@@ -14,6 +19,9 @@ const ICON_BOTTOM_MULTIPLIER: i32 = 3;
 pub struct Ecs<'a, 'b> {
     pub world: World,
     world_data: WorldData,
+    player_data: PlayerData,
+    difficulty: Difficulty,
+    tile_atlas: TileAtlas,
     dispatcher: Option<Dispatcher<'a, 'b>>,
 }
 
@@ -25,17 +33,33 @@ impl<'a, 'b> Ecs<'a, 'b> {
         }
     }
 
-    pub fn setup(world_data: WorldData) -> Ecs<'a, 'b> {
+    pub fn setup(
+        world_data: WorldData,
+        player_data: PlayerData,
+        difficulty: Difficulty,
+        tile_atlas: TileAtlas,
+        game_play: GamePlay,
+        best_score: u64,
+    ) -> Ecs<'a, 'b> {
         let mut world = World::new();
 
-        // Insert resources
-        let ground_data = Ecs::build_default_ground_data();
-        world.insert(CloudData::new(world_data, ground_data));
-        world.insert(EnemyData::new(world_data, ground_data));
-        world.insert(PlayerData::new());
+        // Insert resources. Clouds don't care about the difficulty
+        let ground_data = GroundData::new(difficulty.starting_speed_multiplier);
+        world.insert(CloudData::new(world_data, Ecs::build_default_ground_data()));
+        world.insert(EnemyData::new(
+            world_data,
+            ground_data,
+            difficulty.stages.first(),
+        ));
+        world.insert(PickupData::new(world_data, ground_data));
+        world.insert(player_data);
         world.insert(ground_data);
+        world.insert(GroundSpeeds::steady(ground_data));
         world.insert(EventQueue::new());
-        world.insert(GamePlay::new());
+        world.insert(game_play);
+        world.insert(GameRng::new(
+            world_data.rng_seed().unwrap_or_else(GameRng::random_seed),
+        ));
 
         // Register components
         world.register::<components::Animatable>();
@@ -43,38 +67,101 @@ impl<'a, 'b> Ecs<'a, 'b> {
         world.register::<components::Drawable>();
         world.register::<components::Enemy>();
         world.register::<components::Ground>();
+        world.register::<components::Hidden>();
         world.register::<components::Icon>();
         world.register::<components::input::InputControlled>();
         world.register::<components::Letter>();
+        world.register::<components::Life>();
+        world.register::<components::Pickup>();
         world.register::<components::player::Player>();
         world.register::<components::score::Score>();
+        world.register::<components::ShieldBubble>();
+        world.register::<components::Transform>();
+        world.register::<components::Velocity>();
 
         // Create entities
-        GroundEntity::create_all_tiles(&mut world, &world_data);
-        PlayerEntity::create(&mut world, &world_data);
-        ScoreEntity::create_all_tiles(&mut world, &world_data);
+        let ground_laid_out_till =
+            GroundEntity::create_all_tiles(&mut world, &world_data, &tile_atlas);
+        world.insert(GroundLaidOut {
+            till: ground_laid_out_till,
+        });
+        PlayerEntity::create(&mut world, &world_data, &player_data, &tile_atlas);
+        let score_left = ScoreEntity::create_all_tiles(&mut world, &world_data, &tile_atlas);
+
+        // No point in showing a single life. Getting hit ends the game
+        // like it always has
+        if player_data.lives > 1 {
+            LifeEntity::create_all_tiles(
+                &mut world,
+                &world_data,
+                &tile_atlas,
+                player_data.lives,
+                ScoreEntity::bottom(&world_data, &tile_atlas),
+            );
+        }
+
+        ScoreEntity::create_best_score_tiles(
+            &mut world,
+            &world_data,
+            &tile_atlas,
+            score_left,
+            best_score,
+        );
 
         Ecs {
             world,
             world_data,
+            player_data,
+            difficulty,
+            tile_atlas,
             dispatcher: None,
         }
     }
 
+    /// Along with the difficulty that is going to be played. Mirrored above
+    /// the icons like the new record on the game end screen
     pub fn show_instructions(&mut self) {
-        let bottom = IconEntity::create_direction_tiles_at_world_center(&mut self.world);
+        let bottom =
+            IconEntity::create_direction_tiles_at_world_center(&mut self.world, &self.tile_atlas);
         LetterEntity::create_game_instructions_tiles_at_world_center(
             &mut self.world,
+            &self.tile_atlas,
             bottom * ICON_BOTTOM_MULTIPLIER,
         );
+
+        LetterEntity::create_text_tiles_at_world_center(
+            &mut self.world,
+            &self.tile_atlas,
+            -bottom * ICON_BOTTOM_MULTIPLIER,
+            &self.difficulty.name,
+        );
     }
 
-    pub fn show_game_end(&mut self) {
-        let bottom = IconEntity::create_retry_tile_at_world_center(&mut self.world);
+    pub fn show_game_end(&mut self, is_new_record: bool) {
+        let bottom =
+            IconEntity::create_retry_tile_at_world_center(&mut self.world, &self.tile_atlas);
         LetterEntity::create_retry_tiles_at_world_center(
             &mut self.world,
+            &self.tile_atlas,
             bottom * ICON_BOTTOM_MULTIPLIER,
         );
+
+        // Mirror the retry instructions above the icon
+        if is_new_record {
+            LetterEntity::create_new_record_tiles_at_world_center(
+                &mut self.world,
+                &self.tile_atlas,
+                -bottom * ICON_BOTTOM_MULTIPLIER,
+            );
+        }
+    }
+
+    pub fn show_paused(&mut self) {
+        LetterEntity::create_paused_tiles_at_world_center(&mut self.world, &self.tile_atlas, 0);
+    }
+
+    pub fn hide_paused(&mut self) {
+        LetterEntity::remove_all_tiles(&mut self.world);
     }
 
     pub fn start_game_play(&mut self) {
@@ -88,22 +175,65 @@ impl<'a, 'b> Ecs<'a, 'b> {
             .with(GamePlayTickUpdater, game_play_tick_updater, &[])
             .with(EventSystem, "event_system", &[game_play_tick_updater])
             .with(
-                GameSpeedUpdater::new(self.world_data),
+                GameSpeedUpdater::new(self.world_data, self.player_data, self.difficulty.clone()),
                 "game_speed_updater",
-                &[],
+                &[game_play_tick_updater],
             )
             .with_barrier() // To let event system & game updaters to work before any other systems
+            // Everything that moves is moved before anything else looks at where things are
+            .with(MovementSystem::new(self.world_data), "movement_system", &[])
+            // Cloud, ground, enemy & pickup systems draw from `GameRng`. Since all of them write
+            // to it, they are run one after the other in the order they are added here.
+            // This keeps the random numbers each of them gets the same for a given seed
             .with(
-                CloudSystem::new(Ecs::build_default_ground_data(), self.world_data),
+                CloudSystem::new(
+                    Ecs::build_default_ground_data(),
+                    self.world_data,
+                    self.tile_atlas.clone(),
+                ),
                 "cloud_system",
                 &[],
             )
-            .with(GroundSystem::new(self.world_data), "ground_system", &[])
-            .with(PlayerSystem::new(self.world_data), "player_system", &[])
-            .with(EnemySystem::new(self.world_data), "enemy_system", &[])
-            .with(ScoreSystem, "score_system", &[])
+            .with(
+                GroundSystem::new(self.world_data, self.tile_atlas.clone()),
+                "ground_system",
+                &[],
+            )
+            .with(
+                PlayerSystem::new(self.world_data, self.tile_atlas.clone()),
+                "player_system",
+                &[],
+            )
+            // Player & enemies get to choose what to animate before it is done.
+            // Enemies being animated before they do their thing is as good as
+            // after their last tick
+            .with(
+                AnimationSystem::new(self.tile_atlas.clone()),
+                "animation_system",
+                &[],
+            )
+            .with(
+                EnemySystem::new(self.world_data, self.tile_atlas.clone()),
+                "enemy_system",
+                &[],
+            )
+            .with(
+                PickupSystem::new(self.world_data, self.tile_atlas.clone()),
+                "pickup_system",
+                &[],
+            )
+            .with(
+                ScoreSystem::new(self.tile_atlas.clone()),
+                "score_system",
+                &[],
+            )
             .with_barrier()
-            .with(CollisionSystem, "collision_system", &[])
+            .with(
+                CollisionSystem::new(self.tile_atlas.clone()),
+                "collision_system",
+                &[],
+            )
+            .with(LifeSystem, "life_system", &[])
             .build();
 
         self.dispatcher = Some(dispatcher)
@@ -4,13 +4,17 @@ mod ground_entity;
 mod icon_entity;
 mod instruction_entitities_helper;
 mod letter_entity;
+mod life_entity;
+mod pickup_entity;
 mod player_entity;
 mod score_entity;
 
-pub use cloud_entity::CloudEntity;
+pub use cloud_entity::{CloudEntity, CloudStorages};
 pub use enemy_entity::EnemyEntity;
-pub use ground_entity::GroundEntity;
+pub use ground_entity::{GroundEntity, GroundStorages};
 pub use icon_entity::IconEntity;
 pub use letter_entity::LetterEntity;
+pub use life_entity::LifeEntity;
+pub use pickup_entity::{PickupEntity, PickupStorages};
 pub use player_entity::PlayerEntity;
 pub use score_entity::ScoreEntity;
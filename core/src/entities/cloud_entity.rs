@@ -1,10 +1,12 @@
-use crate::components::{Cloud, Drawable};
+use crate::components::{Cloud, Drawable, Transform, Velocity};
 use crate::data::{GroundData, WorldData};
 use crate::graphics::data;
 use crate::graphics::data::CloudTile;
-use crate::rect::Rect;
-use rand::Rng;
-use specs::{Entities, WriteStorage};
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::render::Layer;
+use crate::resources::GameRng;
+use specs::shred::ResourceId;
+use specs::{Entities, SystemData, World, WriteStorage};
 use std::convert::TryFrom;
 
 const MIN_SPEED_NEGATOR: i32 = 2;
@@ -15,14 +17,25 @@ const CLOUD_MIN_DIST_MULTIPLIER: f32 = 0.25;
 
 pub struct CloudEntity;
 
+/// Everything a cloud is made of. Systems that create clouds can make this
+/// a part of their own system data
+#[derive(SystemData)]
+pub struct CloudStorages<'a> {
+    pub entities: Entities<'a>,
+    pub clouds_storage: WriteStorage<'a, Cloud>,
+    pub drawables_storage: WriteStorage<'a, Drawable>,
+    pub transforms_storage: WriteStorage<'a, Transform>,
+    pub velocities_storage: WriteStorage<'a, Velocity>,
+}
+
 impl CloudEntity {
     pub fn create(
         ground_data: &GroundData,
         world_data: &WorldData,
+        tile_atlas: &TileAtlas,
         tile: CloudTile,
-        entities: &Entities,
-        drawables_storage: &mut WriteStorage<Drawable>,
-        clouds_storage: &mut WriteStorage<Cloud>,
+        rng: &mut GameRng,
+        storages: &mut CloudStorages,
     ) {
         let world_surface = world_data.world_surface_at();
         let sky_range = (world_data.bounds().top() - world_surface).abs();
@@ -32,52 +45,46 @@ impl CloudEntity {
 
         let tile_world_bottom = world_data.world_surface_at()
             - cloud_min_distance_from_ground
-            - rand::thread_rng().gen_range(0, cloud_range) as i32;
+            - rng.gen_range(0, cloud_range);
+
+        // Speed is picked before the size. Swapping these around would change
+        // the clouds every seed comes up with
+        let velocity = Velocity::Left {
+            speed_in_wc_per_tick: CloudEntity::random_cloud_speed_in_wc_per_tick(ground_data, rng),
+        };
 
-        entities
+        // Comes in from just outside the right edge of the world
+        let drawable = CloudEntity::build_drawable(tile_atlas, tile, rng);
+        let transform = Transform::new(
+            world_data.bounds().right(),
+            tile_world_bottom - i32::try_from(drawable.height_in_wc).expect("u32 too big for i32"),
+        );
+
+        storages
+            .entities
             .build_entity()
-            .with(
-                Cloud::new(CloudEntity::random_cloud_speed_in_wc_per_tick(ground_data)),
-                clouds_storage,
-            )
-            .with(
-                CloudEntity::build_drawable_with_left_bottom(
-                    tile,
-                    world_data.bounds().right(),
-                    tile_world_bottom,
-                ),
-                drawables_storage,
-            )
+            .with(Cloud, &mut storages.clouds_storage)
+            .with(transform, &mut storages.transforms_storage)
+            .with(velocity, &mut storages.velocities_storage)
+            .with(drawable, &mut storages.drawables_storage)
             .build();
     }
 
-    fn random_cloud_speed_in_wc_per_tick(ground_data: &GroundData) -> u16 {
-        let random_cloud_speed_negator =
-            rand::thread_rng().gen_range(MIN_SPEED_NEGATOR, MAX_SPEED_NEGATOR);
+    fn random_cloud_speed_in_wc_per_tick(ground_data: &GroundData, rng: &mut GameRng) -> f32 {
+        let random_cloud_speed_negator = rng.gen_range(MIN_SPEED_NEGATOR, MAX_SPEED_NEGATOR);
 
-        (i32::from(ground_data.speed_in_wc_per_tick) - random_cloud_speed_negator).max(1) as u16
+        (ground_data.speed_in_wc_per_tick - random_cloud_speed_negator as f32).max(1.0)
     }
 
-    fn build_drawable_with_left_bottom(
-        tile: CloudTile,
-        world_left: i32,
-        world_bottom: i32,
-    ) -> Drawable {
-        let tile_data = data::build_tile_data(data::Tile::Cloud { tile });
-        let divider =
-            rand::thread_rng().gen_range(MIN_TILE_TO_WORLD_DIVIDER, MAX_TILE_TO_WORLD_DIVIDER);
-
-        let width_in_world = (tile_data.bounds_in_tile_sheet.width() as f32 / divider) as u32;
-        let height_in_world = (tile_data.bounds_in_tile_sheet.height() as f32 / divider) as u32;
+    fn build_drawable(tile_atlas: &TileAtlas, tile: CloudTile, rng: &mut GameRng) -> Drawable {
+        let tile_data = tile_atlas.tile_data(data::Tile::Cloud { tile });
+        let divider = rng.gen_range(MIN_TILE_TO_WORLD_DIVIDER, MAX_TILE_TO_WORLD_DIVIDER);
 
         Drawable {
             tile_data,
-            world_bounds: Rect::new(
-                world_left,
-                world_bottom - i32::try_from(height_in_world).expect("u32 too big for i32"),
-                width_in_world,
-                height_in_world,
-            ),
+            width_in_wc: (tile_data.bounds_in_tile_sheet.width() as f32 / divider) as u32,
+            height_in_wc: (tile_data.bounds_in_tile_sheet.height() as f32 / divider) as u32,
+            layer: Layer::Clouds,
         }
     }
 }
@@ -1,12 +1,14 @@
 use crate::components;
 use crate::components::enemy::data::Position;
-use crate::components::{Animatable, Drawable, Enemy};
+use crate::components::{Animatable, Drawable, Enemy, Transform, Velocity};
 use crate::data::enemy_data::EnemyData;
 use crate::data::{PlayerData, WorldData};
 use crate::entities::PlayerEntity;
 use crate::graphics::data;
 use crate::graphics::data::EnemyTile;
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::rect::Rect;
+use crate::render::Layer;
 use specs::{Entities, WriteStorage};
 use std::convert::TryFrom;
 
@@ -19,16 +21,16 @@ const TILE_TO_WORLD_DIVIDER_SPIDER: f32 = 3.0;
 pub struct EnemyEntity;
 
 impl EnemyEntity {
-    pub fn create(
+    /// Builds everything an enemy is made of without adding it to the world.
+    /// This way, it can be looked at before deciding to spawn it
+    pub fn build(
         enemy_data: &EnemyData,
         player_data: &PlayerData,
         world_data: &WorldData,
+        tile_atlas: &TileAtlas,
         tile: EnemyTile,
-        entities: &Entities,
-        animatables_storage: &mut WriteStorage<Animatable>,
-        drawables_storage: &mut WriteStorage<Drawable>,
-        enemies_storage: &mut WriteStorage<components::Enemy>,
-    ) {
+        current_tick: u64,
+    ) -> (Enemy, Animatable, Transform, Velocity, Drawable) {
         let animation = match tile {
             EnemyTile::BatFly1 | EnemyTile::BatFly2 => enemy_data.bat_animation,
             EnemyTile::BeeFly1 | EnemyTile::BeeFly2 => enemy_data.bee_animation,
@@ -41,40 +43,57 @@ impl EnemyEntity {
         let tile_world_bottom = match position {
             Position::Low => world_data.world_surface_at(),
             // To force player to slide
-            Position::Mid => PlayerEntity::top_when_sliding(world_data),
+            Position::Mid => PlayerEntity::top_when_sliding(world_data, tile_atlas),
             // Offset later to make computations uniform
             Position::High => PlayerEntity::bottom_when_max_jumping(world_data, player_data),
         };
 
-        let mut drawable = EnemyEntity::build_drawable_with_left_bottom(
-            tile,
+        // Comes in from just outside the right edge of the world
+        let drawable = EnemyEntity::build_drawable(tile_atlas, tile);
+        let mut transform = Transform::new(
             world_data.bounds().right(),
-            tile_world_bottom,
+            tile_world_bottom - i32::try_from(drawable.height_in_wc).expect("u32 too big for i32"),
         );
 
         // For high enemy, we want to force enemy to not jump over it
         if position == Position::High {
-            drawable
-                .world_bounds
-                .offset(0, (drawable.world_bounds.height() / 2) as i32)
+            transform.offset(0, (drawable.height_in_wc / 2) as i32)
         }
 
+        let enemy = Enemy::new(
+            animation.behaviour,
+            position,
+            current_tick,
+            drawable.world_bounds(&transform).bottom(),
+        );
+
+        let animatable = Animatable::new(animation.clip);
+        let velocity = animation.behaviour.velocity(animation.speed_in_wc_per_tick);
+
+        (enemy, animatable, transform, velocity, drawable)
+    }
+
+    pub fn create(
+        (enemy, animatable, transform, velocity, drawable): (
+            Enemy,
+            Animatable,
+            Transform,
+            Velocity,
+            Drawable,
+        ),
+        entities: &Entities,
+        animatables_storage: &mut WriteStorage<Animatable>,
+        drawables_storage: &mut WriteStorage<Drawable>,
+        enemies_storage: &mut WriteStorage<components::Enemy>,
+        transforms_storage: &mut WriteStorage<Transform>,
+        velocities_storage: &mut WriteStorage<Velocity>,
+    ) {
         entities
             .build_entity()
-            .with(
-                Enemy::new(
-                    animation.speed_in_wc_per_tick,
-                    animation.ticks_in_movement,
-                    position,
-                ),
-                enemies_storage,
-            )
-            .with(
-                Animatable {
-                    current_step_started_at_tick: 0,
-                },
-                animatables_storage,
-            )
+            .with(enemy, enemies_storage)
+            .with(animatable, animatables_storage)
+            .with(transform, transforms_storage)
+            .with(velocity, velocities_storage)
             .with(drawable, drawables_storage)
             .build();
     }
@@ -89,42 +108,20 @@ impl EnemyEntity {
         }
     }
 
-    /// Instead of (left, bottom) like we do for player, we are taking
-    /// in (right, bottom) because enemies travel right to left
-    pub fn build_drawable_with_right_bottom(
-        tile: EnemyTile,
-        world_right: i32,
-        world_bottom: i32,
-    ) -> Drawable {
-        let tile_data = data::build_tile_data(data::Tile::Enemy { tile });
-        let (width_in_world, height_in_world) =
+    /// Only the size. Where it goes is up to its [Transform]
+    pub fn build_drawable(tile_atlas: &TileAtlas, tile: EnemyTile) -> Drawable {
+        let tile_data = tile_atlas.tile_data(data::Tile::Enemy { tile });
+        let (width_in_wc, height_in_wc) =
             EnemyEntity::build_world_bounds(tile, &tile_data.bounds_in_tile_sheet);
 
         Drawable {
             tile_data,
-            world_bounds: Rect::new(
-                world_right - i32::try_from(width_in_world).expect("u32 too big for i32"),
-                world_bottom - i32::try_from(height_in_world).expect("u32 too big for i32"),
-                width_in_world,
-                height_in_world,
-            ),
+            width_in_wc,
+            height_in_wc,
+            layer: Layer::Enemies,
         }
     }
 
-    fn build_drawable_with_left_bottom(
-        tile: EnemyTile,
-        world_left: i32,
-        world_bottom: i32,
-    ) -> Drawable {
-        // To prevent logic repetition, we will create a drawable with right & offset as required
-        let mut drawable =
-            EnemyEntity::build_drawable_with_right_bottom(tile, world_left, world_bottom);
-        drawable
-            .world_bounds
-            .offset(drawable.world_bounds.width() as i32, 0);
-        drawable
-    }
-
     fn build_world_bounds(tile: EnemyTile, tile_bounds: &Rect) -> (u32, u32) {
         let divider = match tile {
             EnemyTile::BatFly1 | EnemyTile::BatFly2 => TILE_TO_WORLD_DIVIDER_BAT,
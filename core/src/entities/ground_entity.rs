@@ -1,33 +1,79 @@
-use crate::components::{Drawable, Ground};
+use crate::components::ground::data::Terrain;
+use crate::components::{Drawable, Ground, Transform, Velocity};
 use crate::data::WorldData;
 use crate::graphics::data;
 use crate::graphics::data::PlatformTile;
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::rect::Rect;
-use specs::{Entities, World, WorldExt, WriteStorage};
-use std::iter::Iterator;
+use crate::render::Layer;
+use specs::shred::ResourceId;
+use specs::{Entities, SystemData, World, WriteStorage};
 
 const GROUND_TILE_WORLD_DIMENSION: u8 = 50;
+const RAISED_PLATFORM_HEIGHT_IN_WC: u8 = 100;
 
 pub struct GroundEntity;
 
+/// Everything a ground tile is made of. Systems that create ground can
+/// make this a part of their own system data
+#[derive(SystemData)]
+pub struct GroundStorages<'a> {
+    pub entities: Entities<'a>,
+    pub drawables_storage: WriteStorage<'a, Drawable>,
+    pub grounds_storage: WriteStorage<'a, Ground>,
+    pub transforms_storage: WriteStorage<'a, Transform>,
+    pub velocities_storage: WriteStorage<'a, Velocity>,
+}
+
 impl GroundEntity {
-    pub fn create_all_tiles(world: &mut World, world_data: &WorldData) {
+    /// Returns the left of the column that would come next
+    pub fn create_all_tiles(
+        world: &mut World,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+    ) -> i32 {
         GroundEntity::create_all_world_tiles_start_at_world_x(
             world_data.bounds().left(),
             world_data,
-            &world.entities(),
-            &mut world.write_storage(),
-            &mut world.write_storage(),
-        );
+            tile_atlas,
+            &mut world.system_data(),
+        )
     }
 
+    /// Fills the world with flat ground starting at `starting_at_world_x`.
+    /// Returns the left of the column that would come next
     pub fn create_all_world_tiles_start_at_world_x(
         starting_at_world_x: i32,
         world_data: &WorldData,
-        entities: &Entities,
-        drawables_storage: &mut WriteStorage<Drawable>,
-        grounds_storage: &mut WriteStorage<Ground>,
-    ) {
+        tile_atlas: &TileAtlas,
+        storages: &mut GroundStorages,
+    ) -> i32 {
+        // It is ..= is so that we don't miss covering the last pixel
+        let mut column = Transform::new(starting_at_world_x, world_data.world_surface_at());
+        while column.left() <= world_data.bounds().right() {
+            column = GroundEntity::create_column_at(
+                column,
+                Terrain::Flat,
+                world_data,
+                tile_atlas,
+                storages,
+            );
+        }
+
+        column.left()
+    }
+
+    /// `column` is where the top left of the column goes (on the surface).
+    /// Returns where the column that would come next goes. All the ground
+    /// moves together. So, `column` should be in step with the rest of the
+    /// tiles (check out [Transform::origin])
+    pub fn create_column_at(
+        column: Transform,
+        terrain: Terrain,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        storages: &mut GroundStorages,
+    ) -> Transform {
         // `ceil` is to make sure that even if ground tiles don't exactly add up to
         // the surface, we can still enough tiles to cover the required area
         let total_row_count = ((world_data.bounds().bottom() - world_data.world_surface_at())
@@ -35,54 +81,85 @@ impl GroundEntity {
             / f32::from(GROUND_TILE_WORLD_DIMENSION))
         .ceil() as u8;
 
-        for row_number in 0..total_row_count {
-            let tile = if row_number == 0 {
-                PlatformTile::GrassyGround
-            } else {
-                PlatformTile::Ground
-            };
-
-            GroundEntity::create_ground_row_starting_at_world_x(
-                starting_at_world_x,
-                world_data.world_surface_at() + i32::from(GROUND_TILE_WORLD_DIMENSION * row_number),
-                tile,
-                world_data,
-                entities,
-                drawables_storage,
-                grounds_storage,
-            )
+        if terrain != Terrain::Pit {
+            for row_number in 0..total_row_count {
+                let tile = if row_number == 0 {
+                    PlatformTile::GrassyGround
+                } else {
+                    PlatformTile::Ground
+                };
+
+                let mut transform = column;
+                transform.offset(0, i32::from(GROUND_TILE_WORLD_DIMENSION * row_number));
+                GroundEntity::create_tile(transform, tile, tile_atlas, storages)
+            }
+        }
+
+        if terrain == Terrain::Raised {
+            let mut transform = column;
+            transform.offset(0, -i32::from(RAISED_PLATFORM_HEIGHT_IN_WC));
+            GroundEntity::create_tile(transform, PlatformTile::GrassyGround, tile_atlas, storages)
         }
+
+        let mut next_column = column;
+        next_column.offset(GROUND_TILE_WORLD_DIMENSION.into(), 0);
+        next_column
     }
 
-    fn create_ground_row_starting_at_world_x(
-        starting_at_world_x: i32,
-        tile_top_at_world_y: i32,
+    /// Tops that can be stood on in a column (check out [GroundEntity::create_column_at])
+    pub fn column_surfaces(world_left: i32, terrain: Terrain, world_data: &WorldData) -> Vec<Rect> {
+        let tops = match terrain {
+            Terrain::Flat => vec![world_data.world_surface_at()],
+            Terrain::Pit => vec![],
+            Terrain::Raised => vec![
+                world_data.world_surface_at(),
+                GroundEntity::raised_platform_top(world_data),
+            ],
+        };
+
+        tops.into_iter()
+            .map(|top| {
+                Rect::new(
+                    world_left,
+                    top,
+                    GROUND_TILE_WORLD_DIMENSION.into(),
+                    GROUND_TILE_WORLD_DIMENSION.into(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn column_width() -> i32 {
+        GROUND_TILE_WORLD_DIMENSION.into()
+    }
+
+    /// High enough for the player to run under it & low enough to be
+    /// reached with a long jump
+    pub fn raised_platform_top(world_data: &WorldData) -> i32 {
+        world_data.world_surface_at() - i32::from(RAISED_PLATFORM_HEIGHT_IN_WC)
+    }
+
+    fn create_tile(
+        transform: Transform,
         tile: PlatformTile,
-        world_data: &WorldData,
-        entities: &Entities,
-        drawables_storage: &mut WriteStorage<Drawable>,
-        grounds_storage: &mut WriteStorage<Ground>,
+        tile_atlas: &TileAtlas,
+        storages: &mut GroundStorages,
     ) {
-        // It is ..= is so that we don't miss covering the last pixel
-        for world_left in (starting_at_world_x..=world_data.bounds().right())
-            .step_by(GROUND_TILE_WORLD_DIMENSION.into())
-        {
-            entities
-                .build_entity()
-                .with(Ground, grounds_storage)
-                .with(
-                    Drawable {
-                        tile_data: data::build_tile_data(data::Tile::Platform { tile }),
-                        world_bounds: Rect::new(
-                            world_left,
-                            tile_top_at_world_y,
-                            GROUND_TILE_WORLD_DIMENSION.into(),
-                            GROUND_TILE_WORLD_DIMENSION.into(),
-                        ),
-                    },
-                    drawables_storage,
-                )
-                .build();
-        }
+        storages
+            .entities
+            .build_entity()
+            .with(Ground, &mut storages.grounds_storage)
+            .with(transform, &mut storages.transforms_storage)
+            .with(Velocity::Ground, &mut storages.velocities_storage)
+            .with(
+                Drawable {
+                    tile_data: tile_atlas.tile_data(data::Tile::Platform { tile }),
+                    width_in_wc: GROUND_TILE_WORLD_DIMENSION.into(),
+                    height_in_wc: GROUND_TILE_WORLD_DIMENSION.into(),
+                    layer: Layer::Ground,
+                },
+                &mut storages.drawables_storage,
+            )
+            .build();
     }
 }
@@ -1,5 +1,6 @@
 use crate::components::Icon;
 use crate::graphics::data::{IconTile, Tile};
+use crate::graphics::tile_atlas::TileAtlas;
 use specs::World;
 
 const TILE_TO_WORLD_DIVIDER: u32 = 1;
@@ -9,9 +10,13 @@ pub struct IconEntity;
 
 impl IconEntity {
     /// Returns the max bottom of all the tiles created
-    pub fn create_direction_tiles_at_world_center(world: &mut World) -> i32 {
+    pub fn create_direction_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+    ) -> i32 {
         IconEntity::create_tiles_at_world_center(
             world,
+            tile_atlas,
             &[
                 Tile::Icon {
                     tile: IconTile::Left,
@@ -28,9 +33,10 @@ impl IconEntity {
     }
 
     /// Returns the max bottom of all the tiles created
-    pub fn create_retry_tile_at_world_center(world: &mut World) -> i32 {
+    pub fn create_retry_tile_at_world_center(world: &mut World, tile_atlas: &TileAtlas) -> i32 {
         IconEntity::create_tiles_at_world_center(
             world,
+            tile_atlas,
             &[Tile::Icon {
                 tile: IconTile::Retry,
             }],
@@ -41,9 +47,14 @@ impl IconEntity {
         super::instruction_entitities_helper::remove_all_entities_matching_type::<Icon>(world);
     }
 
-    fn create_tiles_at_world_center(world: &mut World, tiles: &[Tile]) -> i32 {
+    fn create_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        tiles: &[Tile],
+    ) -> i32 {
         super::instruction_entitities_helper::create_tiles_at_world_center(
             world,
+            tile_atlas,
             Icon,
             TILE_TO_WORLD_DIVIDER,
             ICON_SPACING as i32,
@@ -1,7 +1,8 @@
-use crate::components::Drawable;
-use crate::graphics::data;
+use crate::components::{Drawable, Transform};
 use crate::graphics::data::Tile;
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::rect::Rect;
+use crate::render::Layer;
 use specs::join::Join;
 use specs::WorldExt;
 use specs::{Builder, Component, World};
@@ -9,6 +10,7 @@ use specs::{Builder, Component, World};
 /// Returns the max bottom of all the tiles created
 pub(super) fn create_tiles_at_world_center<T>(
     world: &mut World,
+    tile_atlas: &TileAtlas,
     component: T,
     tile_to_world_divider: u32,
     tile_spacing: i32,
@@ -21,10 +23,10 @@ where
     // Initially create drawables at 0, 0
     let mut last_icon_x_end_at = 0;
     let mut max_drawable_height = 0;
-    let drawables: Vec<Drawable> = tiles
+    let drawables: Vec<(Transform, Drawable)> = tiles
         .into_iter()
         .map(|tile| {
-            let tile_data = data::build_tile_data(tile.clone());
+            let tile_data = tile_atlas.tile_data(*tile);
             let width_in_world = tile_data.bounds_in_tile_sheet.width() / tile_to_world_divider;
             let height_in_world = tile_data.bounds_in_tile_sheet.height() / tile_to_world_divider;
 
@@ -41,10 +43,14 @@ where
                 max_drawable_height = height_in_world;
             }
 
-            Drawable {
+            let drawable = Drawable {
                 tile_data,
-                world_bounds: bounds_in_world,
-            }
+                width_in_wc: width_in_world,
+                height_in_wc: height_in_world,
+                layer: Layer::Instructions,
+            };
+
+            (Transform::new(bounds_in_world.left(), 0), drawable)
         })
         .collect();
 
@@ -53,9 +59,14 @@ where
     let width_offset = -last_icon_x_end_at / 2;
 
     // Offset drawables & create entities
-    for mut drawable in drawables.into_iter() {
-        drawable.world_bounds.offset(width_offset, height_offset);
-        world.create_entity().with(component).with(drawable).build();
+    for (mut transform, drawable) in drawables.into_iter() {
+        transform.offset(width_offset, height_offset);
+        world
+            .create_entity()
+            .with(component)
+            .with(transform)
+            .with(drawable)
+            .build();
     }
 
     height_offset.abs()
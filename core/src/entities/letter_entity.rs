@@ -1,5 +1,6 @@
 use crate::components::Letter;
 use crate::graphics::data::{LetterTile, Tile};
+use crate::graphics::tile_atlas::TileAtlas;
 use specs::World;
 
 const TILE_TO_WORLD_DIVIDER: u32 = 8;
@@ -8,9 +9,14 @@ const LETTER_SPACING: u32 = 2;
 pub struct LetterEntity;
 
 impl LetterEntity {
-    pub fn create_game_instructions_tiles_at_world_center(world: &mut World, extra_y_offset: i32) {
+    pub fn create_game_instructions_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+    ) {
         LetterEntity::create_tiles_at_world_center(
             world,
+            tile_atlas,
             extra_y_offset,
             &[
                 Tile::Letter {
@@ -77,9 +83,14 @@ impl LetterEntity {
         )
     }
 
-    pub fn create_retry_tiles_at_world_center(world: &mut World, extra_y_offset: i32) {
+    pub fn create_retry_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+    ) {
         LetterEntity::create_tiles_at_world_center(
             world,
+            tile_atlas,
             extra_y_offset,
             &[
                 Tile::Letter {
@@ -161,13 +172,117 @@ impl LetterEntity {
         )
     }
 
+    pub fn create_new_record_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+    ) {
+        LetterEntity::create_tiles_at_world_center(
+            world,
+            tile_atlas,
+            extra_y_offset,
+            &[
+                Tile::Letter {
+                    tile: LetterTile::N,
+                },
+                Tile::Letter {
+                    tile: LetterTile::E,
+                },
+                Tile::Letter {
+                    tile: LetterTile::W,
+                },
+                Tile::Letter {
+                    tile: LetterTile::SPACE,
+                },
+                Tile::Letter {
+                    tile: LetterTile::R,
+                },
+                Tile::Letter {
+                    tile: LetterTile::E,
+                },
+                Tile::Letter {
+                    tile: LetterTile::C,
+                },
+                Tile::Letter {
+                    tile: LetterTile::O,
+                },
+                Tile::Letter {
+                    tile: LetterTile::R,
+                },
+                Tile::Letter {
+                    tile: LetterTile::D,
+                },
+            ],
+        )
+    }
+
+    pub fn create_paused_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+    ) {
+        LetterEntity::create_tiles_at_world_center(
+            world,
+            tile_atlas,
+            extra_y_offset,
+            &[
+                Tile::Letter {
+                    tile: LetterTile::P,
+                },
+                Tile::Letter {
+                    tile: LetterTile::A,
+                },
+                Tile::Letter {
+                    tile: LetterTile::U,
+                },
+                Tile::Letter {
+                    tile: LetterTile::S,
+                },
+                Tile::Letter {
+                    tile: LetterTile::E,
+                },
+                Tile::Letter {
+                    tile: LetterTile::D,
+                },
+            ],
+        )
+    }
+
+    /// Anything other than letters turns into a space
+    pub fn create_text_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+        text: &str,
+    ) {
+        let tiles: Vec<Tile> = text
+            .chars()
+            .map(|c| {
+                let tile = match c.to_ascii_uppercase() {
+                    c @ 'A'..='Z' => LetterTile::ALL[(c as u8 - b'A') as usize],
+                    _ => LetterTile::SPACE,
+                };
+
+                Tile::Letter { tile }
+            })
+            .collect();
+
+        LetterEntity::create_tiles_at_world_center(world, tile_atlas, extra_y_offset, &tiles)
+    }
+
     pub fn remove_all_tiles(world: &mut World) {
         super::instruction_entitities_helper::remove_all_entities_matching_type::<Letter>(world);
     }
 
-    fn create_tiles_at_world_center(world: &mut World, extra_y_offset: i32, tiles: &[Tile]) {
+    fn create_tiles_at_world_center(
+        world: &mut World,
+        tile_atlas: &TileAtlas,
+        extra_y_offset: i32,
+        tiles: &[Tile],
+    ) {
         super::instruction_entitities_helper::create_tiles_at_world_center(
             world,
+            tile_atlas,
             Letter,
             TILE_TO_WORLD_DIVIDER,
             LETTER_SPACING as i32,
@@ -0,0 +1,54 @@
+use crate::components::{Drawable, Life, Transform};
+use crate::data::WorldData;
+use crate::graphics::data;
+use crate::graphics::data::CharacterTile;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::render::Layer;
+use specs::{Builder, World, WorldExt};
+
+const TILE_TO_WORLD_DIVIDER: u32 = 4;
+const DISTANCE_FROM_WORLD_EDGE: i32 = 16;
+const DISTANCE_FROM_TOP: i32 = 8;
+const LIFE_SPACING: u32 = 4;
+
+pub struct LifeEntity;
+
+impl LifeEntity {
+    /// One small player per life, lined up to the left of the right edge
+    /// of the world & placed a little below `top`
+    pub fn create_all_tiles(
+        world: &mut World,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        lives: u8,
+        top: i32,
+    ) {
+        let tile_data = tile_atlas.tile_data(data::Tile::Character {
+            tile: CharacterTile::Still,
+        });
+
+        let width_in_world = tile_data.bounds_in_tile_sheet.width() / TILE_TO_WORLD_DIVIDER;
+        let height_in_world = tile_data.bounds_in_tile_sheet.height() / TILE_TO_WORLD_DIVIDER;
+        let right = world_data.bounds().right() - DISTANCE_FROM_WORLD_EDGE;
+
+        for index in 0..lives {
+            let distance_from_right =
+                i32::from(lives - index) * (width_in_world + LIFE_SPACING) as i32;
+
+            world
+                .create_entity()
+                .with(Life { index })
+                .with(Transform::new(
+                    right - distance_from_right,
+                    top + DISTANCE_FROM_TOP,
+                ))
+                .with(Drawable {
+                    tile_data,
+                    width_in_wc: width_in_world,
+                    height_in_wc: height_in_world,
+                    layer: Layer::Hud,
+                })
+                .build();
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::components::pickup::data::Kind;
+use crate::components::{Drawable, Pickup, ShieldBubble, Transform, Velocity};
+use crate::data::WorldData;
+use crate::graphics::data;
+use crate::graphics::data::PlatformTile;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::rect::Rect;
+use crate::render::Layer;
+use specs::shred::ResourceId;
+use specs::{Entities, SystemData, World, WriteStorage};
+use std::convert::TryFrom;
+
+const TILE_TO_WORLD_DIVIDER_COIN: f32 = 1.5;
+const TILE_TO_WORLD_DIVIDER_POWER_UP: f32 = 2.5;
+const SHIELD_BUBBLE_MARGIN_IN_WC: u32 = 8;
+
+pub struct PickupEntity;
+
+/// Everything a pickup is made of. Systems that create pickups can make
+/// this a part of their own system data
+#[derive(SystemData)]
+pub struct PickupStorages<'a> {
+    pub entities: Entities<'a>,
+    pub drawables_storage: WriteStorage<'a, Drawable>,
+    pub pickups_storage: WriteStorage<'a, Pickup>,
+    pub transforms_storage: WriteStorage<'a, Transform>,
+    pub velocities_storage: WriteStorage<'a, Velocity>,
+}
+
+impl PickupEntity {
+    /// Pickups are built just outside the right edge of the world, with
+    /// their bottom `height_in_wc` above the surface. Nothing is added to
+    /// the world. This way, it can be looked at before deciding to spawn it
+    pub fn build(
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        kind: Kind,
+        height_in_wc: i32,
+    ) -> (Transform, Drawable) {
+        let drawable = PickupEntity::build_drawable(tile_atlas, kind);
+        let transform = Transform::new(
+            world_data.bounds().right(),
+            world_data.world_surface_at()
+                - height_in_wc
+                - i32::try_from(drawable.height_in_wc).expect("u32 too big for i32"),
+        );
+
+        (transform, drawable)
+    }
+
+    pub fn create(
+        kind: Kind,
+        (transform, drawable): (Transform, Drawable),
+        storages: &mut PickupStorages,
+    ) {
+        // Pickups just lie around. They move along with the ground
+        storages
+            .entities
+            .build_entity()
+            .with(Pickup::new(kind), &mut storages.pickups_storage)
+            .with(transform, &mut storages.transforms_storage)
+            .with(Velocity::Ground, &mut storages.velocities_storage)
+            .with(drawable, &mut storages.drawables_storage)
+            .build();
+    }
+
+    pub fn create_shield_bubble(
+        tile_atlas: &TileAtlas,
+        player_bounds: &Rect,
+        entities: &Entities,
+        drawables_storage: &mut WriteStorage<Drawable>,
+        shield_bubbles_storage: &mut WriteStorage<ShieldBubble>,
+        transforms_storage: &mut WriteStorage<Transform>,
+    ) {
+        let (transform, drawable) = PickupEntity::build_shield_bubble(tile_atlas, player_bounds);
+        entities
+            .build_entity()
+            .with(ShieldBubble, shield_bubbles_storage)
+            .with(transform, transforms_storage)
+            .with(drawable, drawables_storage)
+            .build();
+    }
+
+    /// Bubble is a little bigger than the player & is centered on them
+    pub fn build_shield_bubble(
+        tile_atlas: &TileAtlas,
+        player_bounds: &Rect,
+    ) -> (Transform, Drawable) {
+        let size = player_bounds.width().max(player_bounds.height()) + SHIELD_BUBBLE_MARGIN_IN_WC;
+        let half_size = i32::try_from(size / 2).expect("u32 too big for i32");
+        let transform = Transform::new(
+            player_bounds.left() + (player_bounds.width() / 2) as i32 - half_size,
+            player_bounds.top() + (player_bounds.height() / 2) as i32 - half_size,
+        );
+
+        let drawable = Drawable {
+            tile_data: tile_atlas.tile_data(data::Tile::Platform {
+                tile: PlatformTile::Shield,
+            }),
+            width_in_wc: size,
+            height_in_wc: size,
+            layer: Layer::ShieldBubble,
+        };
+
+        (transform, drawable)
+    }
+
+    fn build_drawable(tile_atlas: &TileAtlas, kind: Kind) -> Drawable {
+        let (tile, divider) = match kind {
+            Kind::Coin => (PlatformTile::Coin, TILE_TO_WORLD_DIVIDER_COIN),
+            Kind::Shield => (PlatformTile::Shield, TILE_TO_WORLD_DIVIDER_POWER_UP),
+            Kind::Magnet => (PlatformTile::Magnet, TILE_TO_WORLD_DIVIDER_POWER_UP),
+        };
+
+        let tile_data = tile_atlas.tile_data(data::Tile::Platform { tile });
+        Drawable {
+            tile_data,
+            width_in_wc: (tile_data.bounds_in_tile_sheet.width() as f32 / divider) as u32,
+            height_in_wc: (tile_data.bounds_in_tile_sheet.height() as f32 / divider) as u32,
+            layer: Layer::Pickups,
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::components::input::InputControlled;
 use crate::components::player::Player;
-use crate::components::{Animatable, Drawable};
+use crate::components::{Animatable, Drawable, Transform};
 use crate::data::{PlayerData, WorldData};
 use crate::graphics::data;
 use crate::graphics::data::CharacterTile;
-use crate::rect::Rect;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::render::Layer;
 use specs::{Builder, World, WorldExt};
 use std::convert::TryFrom;
 
@@ -13,39 +14,35 @@ const TILE_TO_WORLD_DIVIDER: u32 = 2;
 pub struct PlayerEntity;
 
 impl PlayerEntity {
-    pub fn create(world: &mut World, world_data: &WorldData) {
+    pub fn create(
+        world: &mut World,
+        world_data: &WorldData,
+        player_data: &PlayerData,
+        tile_atlas: &TileAtlas,
+    ) {
+        let drawable = PlayerEntity::build_drawable(tile_atlas, CharacterTile::Still);
         world
             .create_entity()
-            .with(Player::new())
+            .with(Player::new(player_data.lives))
             .with(InputControlled::new())
-            .with(Animatable {
-                current_step_started_at_tick: 0,
-            })
-            .with(PlayerEntity::build_drawable_with_left_bottom(
-                CharacterTile::Still,
+            .with(Animatable::new(player_data.still_clip))
+            .with(Transform::new(
                 world_data.bounds().left() + (world_data.bounds().width() / 8) as i32,
-                world_data.world_surface_at(),
+                world_data.world_surface_at()
+                    - i32::try_from(drawable.height_in_wc).expect("u32 too big for i32"),
             ))
+            .with(drawable)
             .build();
     }
 
-    pub fn build_drawable_with_left_bottom(
-        tile: CharacterTile,
-        world_left: i32,
-        world_bottom: i32,
-    ) -> Drawable {
-        let tile_data = data::build_tile_data(data::Tile::Character { tile });
-        let width_in_world = tile_data.bounds_in_tile_sheet.width() / TILE_TO_WORLD_DIVIDER;
-        let height_in_world = tile_data.bounds_in_tile_sheet.height() / TILE_TO_WORLD_DIVIDER;
-
+    /// Only the size. Where it goes is up to its [Transform]
+    pub fn build_drawable(tile_atlas: &TileAtlas, tile: CharacterTile) -> Drawable {
+        let tile_data = tile_atlas.tile_data(data::Tile::Character { tile });
         Drawable {
             tile_data,
-            world_bounds: Rect::new(
-                world_left,
-                world_bottom - i32::try_from(height_in_world).expect("u32 too big for i32"),
-                width_in_world,
-                height_in_world,
-            ),
+            width_in_wc: tile_data.bounds_in_tile_sheet.width() / TILE_TO_WORLD_DIVIDER,
+            height_in_wc: tile_data.bounds_in_tile_sheet.height() / TILE_TO_WORLD_DIVIDER,
+            layer: Layer::Player,
         }
     }
 
@@ -53,13 +50,9 @@ impl PlayerEntity {
         world_data.world_surface_at() - i32::from(player_data.max_jump_height_in_wc)
     }
 
-    pub fn top_when_sliding(world_data: &WorldData) -> i32 {
-        PlayerEntity::build_drawable_with_left_bottom(
-            CharacterTile::Slide,
-            world_data.bounds().right(),
-            world_data.world_surface_at(),
-        )
-        .world_bounds
-        .top()
+    pub fn top_when_sliding(world_data: &WorldData, tile_atlas: &TileAtlas) -> i32 {
+        let drawable = PlayerEntity::build_drawable(tile_atlas, CharacterTile::Slide);
+        world_data.world_surface_at()
+            - i32::try_from(drawable.height_in_wc).expect("u32 too big for i32")
     }
 }
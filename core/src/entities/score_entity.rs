@@ -1,58 +1,122 @@
 use crate::components::score::{Position, Score};
-use crate::components::Drawable;
+use crate::components::{Drawable, Transform};
 use crate::data::WorldData;
 use crate::graphics::data;
 use crate::graphics::data::NumberTile;
-use crate::rect::Rect;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::render::Layer;
 use specs::{Builder, World, WorldExt};
 
 const TILE_TO_WORLD_DIVIDER: u32 = 8;
+const BEST_SCORE_TILE_TO_WORLD_DIVIDER: u32 = 12;
 const DISTANCE_FROM_WORLD_EDGE: i32 = 16;
 const NUMBER_SPACING: u32 = 2;
 
 pub struct ScoreEntity;
 
 impl ScoreEntity {
-    pub fn create_all_tiles(world: &mut World, world_data: &WorldData) {
-        ScoreEntity::create_tile(world, world_data, Position::One);
-        ScoreEntity::create_tile(world, world_data, Position::Ten);
-        ScoreEntity::create_tile(world, world_data, Position::Hundred);
-        ScoreEntity::create_tile(world, world_data, Position::Thousand);
-        ScoreEntity::create_tile(world, world_data, Position::TenThousand);
-        ScoreEntity::create_tile(world, world_data, Position::HundredThousand);
+    /// Returns the left of the left most tile
+    pub fn create_all_tiles(
+        world: &mut World,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+    ) -> i32 {
+        let mut left = world_data.bounds().right();
+        for position in Position::ALL.iter() {
+            let (transform, drawable) = ScoreEntity::build_drawable(
+                world_data,
+                tile_atlas,
+                world_data.bounds().right() - DISTANCE_FROM_WORLD_EDGE,
+                TILE_TO_WORLD_DIVIDER,
+                position,
+                NumberTile::Zero,
+            );
+
+            left = transform.left();
+            world
+                .create_entity()
+                .with(Score {
+                    position: *position,
+                })
+                .with(transform)
+                .with(drawable)
+                .build();
+        }
+
+        left
+    }
+
+    /// Bottom of the tiles created by [ScoreEntity::create_all_tiles]
+    pub fn bottom(world_data: &WorldData, tile_atlas: &TileAtlas) -> i32 {
+        let (transform, drawable) = ScoreEntity::build_drawable(
+            world_data,
+            tile_atlas,
+            world_data.bounds().right(),
+            TILE_TO_WORLD_DIVIDER,
+            &Position::One,
+            NumberTile::Zero,
+        );
+
+        drawable.world_bounds(&transform).bottom()
     }
 
-    fn create_tile(world: &mut World, world_data: &WorldData, position: Position) {
-        let tile_data = data::build_tile_data(data::Tile::Number {
-            tile: NumberTile::Zero,
-        });
-        let width_in_world = tile_data.bounds_in_tile_sheet.width() / TILE_TO_WORLD_DIVIDER;
-        let height_in_world = tile_data.bounds_in_tile_sheet.height() / TILE_TO_WORLD_DIVIDER;
-
-        let distance_from_world_right = DISTANCE_FROM_WORLD_EDGE
-            + match position {
-                Position::One => 1,
-                Position::Ten => 2,
-                Position::Hundred => 3,
-                Position::Thousand => 4,
-                Position::TenThousand => 5,
-                Position::HundredThousand => 6,
-            } * (width_in_world + NUMBER_SPACING) as i32;
+    /// Best score doesn't change during a game. So, these are plain drawables
+    /// that aren't updated by any system. They are smaller than the live score
+    /// to tell them apart & are placed to the left of `right`
+    pub fn create_best_score_tiles(
+        world: &mut World,
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        right: i32,
+        best_score: u64,
+    ) {
+        for position in Position::ALL.iter() {
+            let (transform, drawable) = ScoreEntity::build_drawable(
+                world_data,
+                tile_atlas,
+                right - DISTANCE_FROM_WORLD_EDGE,
+                BEST_SCORE_TILE_TO_WORLD_DIVIDER,
+                position,
+                position.digit_tile(best_score),
+            );
+
+            world.create_entity().with(transform).with(drawable).build();
+        }
+    }
+
+    fn build_drawable(
+        world_data: &WorldData,
+        tile_atlas: &TileAtlas,
+        right: i32,
+        tile_to_world_divider: u32,
+        position: &Position,
+        tile: NumberTile,
+    ) -> (Transform, Drawable) {
+        let tile_data = tile_atlas.tile_data(data::Tile::Number { tile });
+        let width_in_world = tile_data.bounds_in_tile_sheet.width() / tile_to_world_divider;
+        let height_in_world = tile_data.bounds_in_tile_sheet.height() / tile_to_world_divider;
+
+        let distance_from_right = match position {
+            Position::One => 1,
+            Position::Ten => 2,
+            Position::Hundred => 3,
+            Position::Thousand => 4,
+            Position::TenThousand => 5,
+            Position::HundredThousand => 6,
+        } * (width_in_world + NUMBER_SPACING) as i32;
+
+        let transform = Transform::new(
+            right - distance_from_right,
+            world_data.bounds().top() + DISTANCE_FROM_WORLD_EDGE,
+        );
 
         let drawable = Drawable {
             tile_data,
-            world_bounds: Rect::new(
-                world_data.bounds().right() - distance_from_world_right,
-                world_data.bounds().top() + DISTANCE_FROM_WORLD_EDGE,
-                width_in_world,
-                height_in_world,
-            ),
+            width_in_wc: width_in_world,
+            height_in_wc: height_in_world,
+            layer: Layer::Hud,
         };
 
-        world
-            .create_entity()
-            .with(Score { position })
-            .with(drawable)
-            .build();
+        (transform, drawable)
     }
 }
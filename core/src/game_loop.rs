@@ -1,45 +1,253 @@
-use crate::components::Drawable;
-use crate::data::WorldData;
+use crate::clock::{Clock, RealTimeClock};
+use crate::components::{Drawable, Hidden, Transform};
+use crate::data::difficulty::Difficulty;
+use crate::data::{PlayerData, WorldData};
+use crate::debug::DebugOverlay;
 use crate::ecs::Ecs;
-use crate::input::{Event, Keycode};
-use crate::resources::{EventQueue, GamePlay};
-use specs::shred::FetchMut;
+use crate::gestures::GestureRecognizer;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::high_scores::HighScores;
+use crate::input::{Action, Event};
+use crate::key_bindings::{ActionMapper, KeyBindings};
+use crate::replay::Replay;
+use crate::resources::{EventQueue, GamePlay, GameRng};
+use specs::shred::{Fetch, FetchMut};
 use specs::{ReadStorage, WorldExt};
+use std::sync::Arc;
 
 enum HandleInputResult {
     NoAction,
     StartGamePlay,
+    PreviousDifficulty,
+    NextDifficulty,
+    PauseGamePlay,
+    ResumeGamePlay,
     RestartGame,
     Quit,
 }
 
 pub enum GameLoopResult {
     Continue,
+    GameOver,
     Quit,
 }
 
 pub struct GameLoop<'a, 'b> {
     world_data: WorldData,
+    player_data: PlayerData,
+    difficulties: Vec<Difficulty>,
+    difficulty_index: usize,
+    tile_atlas: TileAtlas,
+    clock: Arc<dyn Clock>,
     ecs: Ecs<'a, 'b>,
+    replay: Replay,
+    high_scores: HighScores,
+    action_mapper: ActionMapper,
+    gesture_recognizer: GestureRecognizer,
+    is_debug_enabled: bool,
 }
 
 impl<'a, 'b> GameLoop<'a, 'b> {
-    pub fn new(world_data: WorldData) -> GameLoop<'a, 'b> {
-        let mut ecs = Ecs::setup(world_data);
+    pub fn new(
+        world_data: WorldData,
+        tile_atlas: TileAtlas,
+        high_scores: HighScores,
+        key_bindings: KeyBindings,
+    ) -> GameLoop<'a, 'b> {
+        GameLoop::new_with_clock(
+            world_data,
+            tile_atlas,
+            high_scores,
+            key_bindings,
+            Arc::new(RealTimeClock::new()),
+        )
+    }
+
+    /// Game play will advance only as much as `clock` does. Check out
+    /// [crate::clock::ManualClock] to be in control of the time
+    pub fn new_with_clock(
+        world_data: WorldData,
+        tile_atlas: TileAtlas,
+        high_scores: HighScores,
+        key_bindings: KeyBindings,
+        clock: Arc<dyn Clock>,
+    ) -> GameLoop<'a, 'b> {
+        let player_data = PlayerData::new();
+        let difficulties = Difficulty::presets();
+        let difficulty_index = difficulties
+            .iter()
+            .position(|difficulty| *difficulty == Difficulty::default())
+            .unwrap_or(0);
+
+        let mut ecs = Ecs::setup(
+            world_data,
+            player_data,
+            difficulties[difficulty_index].clone(),
+            tile_atlas.clone(),
+            GamePlay::new(Arc::clone(&clock)),
+            high_scores.best(player_data.lives),
+        );
+        ecs.show_instructions();
+
+        let replay = Replay::new(
+            ecs.world.fetch::<GameRng>().seed(),
+            difficulties[difficulty_index].clone(),
+            player_data.lives,
+        );
+
+        GameLoop {
+            world_data,
+            player_data,
+            difficulties,
+            difficulty_index,
+            tile_atlas,
+            clock,
+            ecs,
+            replay,
+            high_scores,
+            action_mapper: ActionMapper::new(key_bindings),
+            gesture_recognizer: GestureRecognizer::new(world_data),
+            is_debug_enabled: false,
+        }
+    }
+
+    /// Games will be played with `player_data` instead of the defaults. For
+    /// eg., to give the player a few lives (check out [PlayerData::with_lives]).
+    /// Has to be called before the game is started
+    pub fn with_player_data(self, player_data: PlayerData) -> GameLoop<'a, 'b> {
+        GameLoop {
+            player_data,
+            ..self
+        }
+        .with_fresh_ecs()
+    }
+
+    /// Difficulties players can pick from on the start screen (with left &
+    /// right) instead of the presets. For eg., to add some custom ones. The
+    /// one that is picked right now stays picked if it is still around. Has
+    /// to be called before the game is started
+    pub fn with_difficulties(self, difficulties: Vec<Difficulty>) -> GameLoop<'a, 'b> {
+        if difficulties.is_empty() {
+            panic!("There should be at least one difficulty to play with")
+        }
+
+        let difficulty_index = difficulties
+            .iter()
+            .position(|other| other.name == self.difficulty().name)
+            .unwrap_or(0);
+
+        GameLoop {
+            difficulties,
+            difficulty_index,
+            ..self
+        }
+        .with_fresh_ecs()
+    }
+
+    /// Whatever has been set up so far is thrown away & the start screen
+    /// is shown again
+    fn with_fresh_ecs(self) -> GameLoop<'a, 'b> {
+        let mut ecs = self.build_ecs();
         ecs.show_instructions();
 
-        GameLoop { world_data, ecs }
+        let replay = Replay::new(
+            ecs.world.fetch::<GameRng>().seed(),
+            self.difficulty().clone(),
+            self.player_data.lives,
+        );
+
+        GameLoop {
+            ecs,
+            replay,
+            ..self
+        }
+    }
+
+    fn build_ecs(&self) -> Ecs<'a, 'b> {
+        Ecs::setup(
+            self.world_data,
+            self.player_data,
+            self.difficulty().clone(),
+            self.tile_atlas.clone(),
+            GamePlay::new(Arc::clone(&self.clock)),
+            self.high_scores.best(self.player_data.lives),
+        )
+    }
+
+    /// The one that is being played (or is going to be played once the game
+    /// is started)
+    pub fn difficulty(&self) -> &Difficulty {
+        &self.difficulties[self.difficulty_index]
     }
 
     pub fn event_queue(&mut self) -> FetchMut<EventQueue> {
         self.ecs.world.fetch_mut::<EventQueue>()
     }
 
+    pub fn game_play(&self) -> Fetch<GamePlay> {
+        self.ecs.world.fetch::<GamePlay>()
+    }
+
+    /// Seed of the game currently being played. Playing again with the same
+    /// seed (check out [WorldData::with_rng_seed]) & the same input would
+    /// reproduce the game
+    pub fn rng_seed(&self) -> u64 {
+        self.ecs.world.fetch::<GameRng>().seed()
+    }
+
+    /// Replay of the game currently being played (or the one that just
+    /// came to an end)
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Includes the score of the game that just came to an end. Frontends
+    /// should persist these on [GameLoopResult::GameOver]
+    pub fn high_scores(&self) -> &HighScores {
+        &self.high_scores
+    }
+
+    pub fn transforms_storage(&self) -> ReadStorage<Transform> {
+        self.ecs.world.read_storage()
+    }
+
     pub fn drawables_storage(&self) -> ReadStorage<Drawable> {
         self.ecs.world.read_storage()
     }
 
+    /// Drawables of these entities shouldn't be drawn
+    pub fn hidden_storage(&self) -> ReadStorage<Hidden> {
+        self.ecs.world.read_storage()
+    }
+
+    /// Frontends should draw this over everything else. Empty unless
+    /// debugging is turned on (check out [Action::ToggleDebug])
+    pub fn debug_overlay(&self, fps: u32) -> DebugOverlay {
+        if self.is_debug_enabled {
+            DebugOverlay::build(&self.ecs.world, &self.world_data, &self.tile_atlas, fps)
+        } else {
+            DebugOverlay::default()
+        }
+    }
+
     pub fn execute(&mut self) -> GameLoopResult {
+        // Everyone down the line only cares about actions & not the keys
+        // that were pressed or the gestures that were made for them
+        {
+            let is_game_play_allowed = self.ecs.world.fetch::<GamePlay>().is_allowed();
+            let mut event_queue = self.ecs.world.fetch_mut::<EventQueue>();
+            self.action_mapper.map(&mut event_queue);
+            self.gesture_recognizer
+                .map(&mut event_queue, self.clock.now(), is_game_play_allowed);
+
+            // Debugging can be turned on or off at any time. Even when paused
+            for event in event_queue.iter() {
+                if let Event::ActionDown(Action::ToggleDebug) = event {
+                    self.is_debug_enabled = !self.is_debug_enabled
+                }
+            }
+        }
+
         // Check & finish the game or start a new game if required
         let mut game_loop_result = GameLoopResult::Continue;
         let handle_input_result =
@@ -50,42 +258,131 @@ impl<'a, 'b> GameLoop<'a, 'b> {
             HandleInputResult::Quit => game_loop_result = GameLoopResult::Quit,
             HandleInputResult::StartGamePlay => {
                 &mut self.ecs.world.fetch_mut::<GamePlay>().mark_started();
-                self.ecs.start_game_play()
+                self.ecs.start_game_play();
+                GameLoop::drop_game_play_presses(&mut self.ecs.world.fetch_mut());
+            }
+            HandleInputResult::PreviousDifficulty | HandleInputResult::NextDifficulty => {
+                let count = self.difficulties.len();
+                self.difficulty_index = match handle_input_result {
+                    HandleInputResult::PreviousDifficulty => self.difficulty_index + count - 1,
+                    _ => self.difficulty_index + 1,
+                } % count;
+
+                self.ecs = self.build_ecs();
+                self.ecs.show_instructions();
+                self.replay = Replay::new(
+                    self.rng_seed(),
+                    self.difficulty().clone(),
+                    self.player_data.lives,
+                );
+            }
+            HandleInputResult::PauseGamePlay => {
+                self.ecs.world.fetch_mut::<GamePlay>().mark_paused();
+                self.ecs.show_paused()
+            }
+            HandleInputResult::ResumeGamePlay => {
+                self.ecs.world.fetch_mut::<GamePlay>().mark_resumed();
+                self.ecs.hide_paused();
+
+                // Systems don't see any events while paused. So, a key released
+                // during the pause would otherwise stay engaged. Let go of
+                // everything & have the player press again if they want to.
+                // These go through the systems (& into the replay) in this frame
+                let mut event_queue = self.ecs.world.fetch_mut::<EventQueue>();
+                for action in &[
+                    Action::Jump,
+                    Action::Slide,
+                    Action::MoveLeft,
+                    Action::MoveRight,
+                ] {
+                    event_queue.add_event(Event::ActionUp(*action))
+                }
             }
             HandleInputResult::RestartGame => {
-                self.ecs = Ecs::setup(self.world_data);
+                self.ecs = self.build_ecs();
 
                 // Needn't show instructions again & can directly start playing
                 &mut self.ecs.world.fetch_mut::<GamePlay>().mark_started();
-                self.ecs.start_game_play()
+                self.ecs.start_game_play();
+                GameLoop::drop_game_play_presses(&mut self.ecs.world.fetch_mut());
+                self.replay = Replay::new(
+                    self.rng_seed(),
+                    self.difficulty().clone(),
+                    self.player_data.lives,
+                );
             }
         }
 
-        // Work the systems
+        // Work the systems & keep track of what they worked with
         let is_game_play_allowed = self.ecs.world.fetch::<GamePlay>().is_allowed();
         if is_game_play_allowed {
-            self.ecs.dispatch()
+            self.ecs.dispatch();
+            self.replay.record_frame(
+                self.ecs.world.fetch::<GamePlay>().ticks_to_animate(),
+                &self.ecs.world.fetch::<EventQueue>(),
+            );
         }
 
         // If game came to an end, reflect that correctly
         if is_game_play_allowed && self.ecs.world.fetch::<GamePlay>().is_over() {
-            self.ecs.show_game_end()
+            let score = self.ecs.world.fetch::<GamePlay>().score();
+            let is_new_record = self.high_scores.add(score, self.player_data.lives);
+            self.ecs.show_game_end(is_new_record);
+            if let GameLoopResult::Continue = game_loop_result {
+                game_loop_result = GameLoopResult::GameOver
+            }
         }
 
         game_loop_result
     }
 
+    /// Whatever started the game might be bound to game play actions too (for
+    /// eg., A on a gamepad starts & jumps). It is meant to start the game &
+    /// not to play it. Releases are let through as they are harmless
+    fn drop_game_play_presses(event_queue: &mut EventQueue) {
+        event_queue.retain(|event| match event {
+            Event::ActionDown(action) => match action {
+                Action::Jump | Action::Slide | Action::MoveLeft | Action::MoveRight => false,
+                Action::Start | Action::Pause | Action::Quit | Action::ToggleDebug => true,
+            },
+            _ => true,
+        })
+    }
+
     fn handle_input(event_queue: &EventQueue, game_play: &GamePlay) -> HandleInputResult {
         for event in event_queue.iter() {
             match event {
                 Event::Quit => return HandleInputResult::Quit,
-                Event::KeyDown(keycode) => match keycode {
-                    Keycode::Escape => return HandleInputResult::Quit,
-                    Keycode::Space => {
+                // Nobody's watching. Pause instead of letting the player
+                // run into an enemy
+                Event::FocusLost if game_play.is_allowed() => {
+                    return HandleInputResult::PauseGamePlay;
+                }
+                Event::ActionDown(action) => match action {
+                    Action::Quit => return HandleInputResult::Quit,
+                    // Taps come in as starts. On touch screens, that is the
+                    // only way to get going again after being paused
+                    Action::Start => {
                         if !game_play.is_started() {
                             return HandleInputResult::StartGamePlay;
                         } else if game_play.is_over() {
                             return HandleInputResult::RestartGame;
+                        } else if game_play.is_paused() {
+                            return HandleInputResult::ResumeGamePlay;
+                        }
+                    }
+                    // Difficulty can only be picked before the game starts
+                    Action::MoveLeft if !game_play.is_started() => {
+                        return HandleInputResult::PreviousDifficulty;
+                    }
+                    Action::MoveRight if !game_play.is_started() => {
+                        return HandleInputResult::NextDifficulty;
+                    }
+                    Action::Pause => {
+                        if game_play.is_paused() {
+                            return HandleInputResult::ResumeGamePlay;
+                        } else if game_play.is_allowed() {
+                            return HandleInputResult::PauseGamePlay;
                         }
                     }
                     _ => {}
@@ -97,3 +394,137 @@ impl<'a, 'b> GameLoop<'a, 'b> {
         HandleInputResult::NoAction
     }
 }
+
+/// Everything a bot needs to play the game by itself (check out the headless
+/// frontend). Frontends that are played by people have no use for these
+#[cfg(feature = "autopilot")]
+mod autopilot {
+    use super::GameLoop;
+    use crate::components::input::InputControlled;
+    use crate::components::player::Player;
+    use crate::components::{Animatable, Drawable, Enemy, Ground, Transform, Velocity};
+    use crate::data::{GroundData, GroundSpeeds, PlayerData};
+    use crate::resources::{GamePlay, GroundLaidOut};
+    use crate::spawn_planner::{PlannedInput, Snapshot, SpawnPlanner};
+    use specs::{Join, WorldExt};
+
+    impl<'a, 'b> GameLoop<'a, 'b> {
+        /// How fast things are going right now. Plans (check out
+        /// [GameLoop::plan_way_through]) see speed ups coming as long as every
+        /// frame animates as many ticks as this one
+        pub fn ground_data(&self) -> GroundData {
+            *self.ecs.world.fetch::<GroundData>()
+        }
+
+        /// Where the ground that has been laid out so far ends. Jumps ahead
+        /// when more terrain is picked
+        pub fn ground_laid_out(&self) -> GroundLaidOut {
+            *self.ecs.world.fetch::<GroundLaidOut>()
+        }
+
+        /// Input for every tick from the one that is going to be animated next,
+        /// till everything that is on its way has gone past the player. [None]
+        /// if there is no way through (or if there isn't a game going on). Good
+        /// enough for a bot to play the game (check out the headless frontend)
+        pub fn plan_way_through(&self) -> Option<Vec<PlannedInput>> {
+            let world = &self.ecs.world;
+            let next_tick = {
+                let game_play = world.fetch::<GamePlay>();
+                game_play.ticks_animated() + game_play.ticks_to_animate()
+            };
+
+            let players_storage = world.read_storage::<Player>();
+            let animatables_storage = world.read_storage::<Animatable>();
+            let drawables_storage = world.read_storage::<Drawable>();
+            let input_controlled_storage = world.read_storage::<InputControlled>();
+            let enemies_storage = world.read_storage::<Enemy>();
+            let grounds_storage = world.read_storage::<Ground>();
+            let transforms_storage = world.read_storage::<Transform>();
+            let velocities_storage = world.read_storage::<Velocity>();
+
+            let player = (
+                &players_storage,
+                &animatables_storage,
+                &transforms_storage,
+                &drawables_storage,
+                &input_controlled_storage,
+            )
+                .join()
+                .next()?;
+
+            let snapshot = Snapshot::new(
+                next_tick,
+                player,
+                (
+                    &enemies_storage,
+                    &animatables_storage,
+                    &transforms_storage,
+                    &velocities_storage,
+                    &drawables_storage,
+                )
+                    .join(),
+                (&grounds_storage, &transforms_storage, &drawables_storage)
+                    .join()
+                    .map(|(_, transform, drawable)| (transform, drawable)),
+                world.fetch::<GroundLaidOut>().till,
+            );
+
+            SpawnPlanner::new(self.world_data, self.tile_atlas.clone()).find_way_through(
+                &snapshot,
+                &world.fetch::<PlayerData>(),
+                &world.fetch::<GroundSpeeds>(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::input::{Pointer, PointerId};
+    use std::time::Duration;
+
+    #[test]
+    fn tap_resumes_paused_game() {
+        let clock = ManualClock::new();
+        let mut game_loop = GameLoop::new_with_clock(
+            WorldData::new(),
+            TileAtlas::from_ron(include_str!("../../assets_processed/tile_atlas.ron")).unwrap(),
+            HighScores::new(),
+            KeyBindings::default(),
+            Arc::new(clock.clone()),
+        );
+
+        game_loop
+            .event_queue()
+            .add_event(Event::ActionDown(Action::Start));
+        game_loop.execute();
+        game_loop.event_queue().reset();
+
+        // Nobody's watching
+        game_loop.event_queue().add_event(Event::FocusLost);
+        game_loop.execute();
+        game_loop.event_queue().reset();
+        assert!(game_loop.game_play().is_paused());
+
+        // A finger going down & coming up a little later
+        let pointer = Pointer {
+            id: PointerId::Touch(0),
+            x: 0,
+            y: 0,
+        };
+
+        game_loop
+            .event_queue()
+            .add_event(Event::PointerDown(pointer));
+        game_loop.execute();
+        game_loop.event_queue().reset();
+        assert!(game_loop.game_play().is_paused());
+
+        clock.advance(Duration::from_millis(50));
+        game_loop.event_queue().add_event(Event::PointerUp(pointer));
+        game_loop.execute();
+        assert!(!game_loop.game_play().is_paused());
+    }
+}
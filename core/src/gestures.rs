@@ -0,0 +1,462 @@
+use crate::data::WorldData;
+use crate::input::{Action, Event, Pointer, PointerId};
+use crate::resources::EventQueue;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A pointer has to travel at least this much for it to be a swipe
+const SWIPE_DISTANCE_IN_WC: i32 = 40;
+
+/// A pointer has to stay down for at least this long for it to be a hold
+const HOLD_DURATION: Duration = Duration::from_millis(150);
+
+/// What a pointer that is down has turned out to be so far
+enum Gesture {
+    // Could still turn out to be anything
+    Undecided { down_at: Duration },
+
+    // Swiped or held. The action stays down till the pointer is lifted
+    Engaged(Action),
+}
+
+struct TrackedPointer {
+    start: Pointer,
+    gesture: Gesture,
+}
+
+/// Turns pointer events into action events. Each pointer is one of
+///     - a swipe up or down => jump or slide till the pointer is lifted
+///     - a hold on the left or right half of the world => move left or
+///       right till the pointer is lifted. Only while the game is being
+///       played
+///     - a swipe left or right => move left or right till the pointer is
+///       lifted. Only while the game isn't being played (for eg., to pick
+///       a difficulty on the start screen)
+///     - a tap => start (restart or resume) the game
+///
+/// There is nothing to move outside of game play. Holds aren't recognized
+/// then, so that a slow tap is still a tap
+/// Pointers are kept apart by their IDs. So, one finger can hold to move
+/// while another swipes
+pub(crate) struct GestureRecognizer {
+    world_center_x: i32,
+    pointers: HashMap<PointerId, TrackedPointer>,
+
+    // Actions that went down & up in the same frame. Systems would never
+    // notice them if both were delivered together. So, ups are held back
+    // for a frame
+    deferred_action_ups: Vec<Action>,
+}
+
+impl GestureRecognizer {
+    pub(crate) fn new(world_data: WorldData) -> GestureRecognizer {
+        let bounds = world_data.bounds();
+        GestureRecognizer {
+            world_center_x: bounds.left() + (bounds.width() / 2) as i32,
+            pointers: HashMap::new(),
+            deferred_action_ups: Vec::new(),
+        }
+    }
+
+    /// Replaces pointer events in the queue with action events. Every other
+    /// event is left as it is. Should be called once every frame, even if
+    /// there are no events, as holds are recognized with the passage of time
+    pub(crate) fn map(
+        &mut self,
+        event_queue: &mut EventQueue,
+        now: Duration,
+        is_game_play_allowed: bool,
+    ) {
+        let events: Vec<Event> = event_queue.drain().collect();
+        let mut action_downs = Vec::new();
+        for action in self.deferred_action_ups.drain(..) {
+            event_queue.add_event(Event::ActionUp(action))
+        }
+
+        for event in events {
+            match event {
+                Event::PointerDown(pointer) => {
+                    // A pointer going down again without coming up (can
+                    // happen if the window lost track of it) is a new start
+                    self.lift(pointer.id, event_queue, &action_downs);
+                    self.pointers.insert(
+                        pointer.id,
+                        TrackedPointer {
+                            start: pointer,
+                            gesture: Gesture::Undecided { down_at: now },
+                        },
+                    );
+                }
+
+                Event::PointerMove(pointer) => {
+                    let swipe = match self.pointers.get(&pointer.id) {
+                        Some(TrackedPointer {
+                            start,
+                            gesture: Gesture::Undecided { .. },
+                        }) => GestureRecognizer::swipe(*start, pointer, is_game_play_allowed),
+                        _ => None,
+                    };
+
+                    if let Some(action) = swipe {
+                        self.engage(pointer.id, action, event_queue, &mut action_downs)
+                    }
+                }
+
+                Event::PointerUp(pointer) => self.lift(pointer.id, event_queue, &action_downs),
+                _ => event_queue.add_event(event),
+            }
+        }
+
+        // Pointers that have been down long enough without swiping are holds
+        if !is_game_play_allowed {
+            return;
+        }
+
+        let holds: Vec<(PointerId, Action)> = self
+            .pointers
+            .iter()
+            .filter_map(|(id, tracked)| match tracked.gesture {
+                Gesture::Undecided { down_at } if now >= down_at + HOLD_DURATION => {
+                    if tracked.start.x < self.world_center_x {
+                        Some((*id, Action::MoveLeft))
+                    } else {
+                        Some((*id, Action::MoveRight))
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (id, action) in holds {
+            self.engage(id, action, event_queue, &mut action_downs)
+        }
+    }
+
+    fn engage(
+        &mut self,
+        id: PointerId,
+        action: Action,
+        event_queue: &mut EventQueue,
+        action_downs: &mut Vec<Action>,
+    ) {
+        // Another pointer could already be at it
+        if !self.is_engaged(action) {
+            event_queue.add_event(Event::ActionDown(action));
+            action_downs.push(action);
+        }
+
+        if let Some(tracked) = self.pointers.get_mut(&id) {
+            tracked.gesture = Gesture::Engaged(action)
+        }
+    }
+
+    fn lift(&mut self, id: PointerId, event_queue: &mut EventQueue, action_downs: &[Action]) {
+        match self.pointers.remove(&id).map(|tracked| tracked.gesture) {
+            None => {}
+            Some(Gesture::Undecided { .. }) => {
+                event_queue.add_event(Event::ActionDown(Action::Start));
+                self.deferred_action_ups.push(Action::Start)
+            }
+            Some(Gesture::Engaged(action)) => {
+                if self.is_engaged(action) {
+                    // Another pointer is still at it
+                } else if action_downs.contains(&action) {
+                    self.deferred_action_ups.push(action)
+                } else {
+                    event_queue.add_event(Event::ActionUp(action))
+                }
+            }
+        }
+    }
+
+    fn is_engaged(&self, action: Action) -> bool {
+        self.pointers.values().any(|tracked| match tracked.gesture {
+            Gesture::Engaged(engaged_action) => engaged_action == action,
+            Gesture::Undecided { .. } => false,
+        })
+    }
+
+    /// Only vertical swipes mean something while the game is being played
+    /// (holds are for moving then). Y grows downwards in the world
+    fn swipe(start: Pointer, current: Pointer, is_game_play_allowed: bool) -> Option<Action> {
+        let dx = current.x - start.x;
+        let dy = current.y - start.y;
+        if dy.abs() >= SWIPE_DISTANCE_IN_WC && dy.abs() >= dx.abs() {
+            if dy < 0 {
+                Some(Action::Jump)
+            } else {
+                Some(Action::Slide)
+            }
+        } else if dx.abs() >= SWIPE_DISTANCE_IN_WC && !is_game_play_allowed {
+            if dx < 0 {
+                Some(Action::MoveLeft)
+            } else {
+                Some(Action::MoveRight)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINGER: PointerId = PointerId::Touch(0);
+    const OTHER_FINGER: PointerId = PointerId::Touch(1);
+
+    fn pointer(id: PointerId, x: i32, y: i32) -> Pointer {
+        Pointer { id, x, y }
+    }
+
+    /// Runs a frame of game play at `now_in_ms` with `events` & returns
+    /// what came out
+    fn map(recognizer: &mut GestureRecognizer, events: &[Event], now_in_ms: u64) -> Vec<Event> {
+        map_with(recognizer, events, now_in_ms, true)
+    }
+
+    /// Same as [map] but for a frame outside of game play
+    fn map_on_start_screen(
+        recognizer: &mut GestureRecognizer,
+        events: &[Event],
+        now_in_ms: u64,
+    ) -> Vec<Event> {
+        map_with(recognizer, events, now_in_ms, false)
+    }
+
+    fn map_with(
+        recognizer: &mut GestureRecognizer,
+        events: &[Event],
+        now_in_ms: u64,
+        is_game_play_allowed: bool,
+    ) -> Vec<Event> {
+        let mut event_queue = EventQueue::new();
+        for event in events {
+            event_queue.add_event(*event)
+        }
+
+        recognizer.map(
+            &mut event_queue,
+            Duration::from_millis(now_in_ms),
+            is_game_play_allowed,
+        );
+        event_queue.iter().copied().collect()
+    }
+
+    fn recognizer() -> GestureRecognizer {
+        // World is centered around (0, 0)
+        GestureRecognizer::new(WorldData::default())
+    }
+
+    #[test]
+    fn swipe_up_jumps_till_lifted() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 10, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 15, -SWIPE_DISTANCE_IN_WC));
+        let up = Event::PointerUp(pointer(FINGER, 15, -SWIPE_DISTANCE_IN_WC));
+
+        assert_eq!(map(&mut recognizer, &[down], 0), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[moved], 20),
+            vec![Event::ActionDown(Action::Jump)]
+        );
+
+        // Staying down doesn't turn a swipe into a hold
+        assert_eq!(map(&mut recognizer, &[], 1000), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[up], 1020),
+            vec![Event::ActionUp(Action::Jump)]
+        );
+    }
+
+    #[test]
+    fn swipe_down_slides_till_lifted() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 10, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 5, SWIPE_DISTANCE_IN_WC));
+        let up = Event::PointerUp(pointer(FINGER, 5, SWIPE_DISTANCE_IN_WC));
+
+        assert_eq!(
+            map(&mut recognizer, &[down, moved], 0),
+            vec![Event::ActionDown(Action::Slide)]
+        );
+        assert_eq!(
+            map(&mut recognizer, &[up], 20),
+            vec![Event::ActionUp(Action::Slide)]
+        );
+    }
+
+    #[test]
+    fn swipe_lifted_in_the_same_frame_comes_up_in_the_next_one() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 0, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 0, -SWIPE_DISTANCE_IN_WC));
+        let up = Event::PointerUp(pointer(FINGER, 0, -SWIPE_DISTANCE_IN_WC));
+
+        assert_eq!(
+            map(&mut recognizer, &[down, moved, up], 0),
+            vec![Event::ActionDown(Action::Jump)]
+        );
+        assert_eq!(
+            map(&mut recognizer, &[], 20),
+            vec![Event::ActionUp(Action::Jump)]
+        );
+    }
+
+    #[test]
+    fn tap_starts() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 0, 0));
+        let up = Event::PointerUp(pointer(FINGER, 0, 0));
+
+        assert_eq!(map(&mut recognizer, &[down], 0), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[up], 50),
+            vec![Event::ActionDown(Action::Start)]
+        );
+        assert_eq!(
+            map(&mut recognizer, &[], 70),
+            vec![Event::ActionUp(Action::Start)]
+        );
+        assert_eq!(map(&mut recognizer, &[], 90), vec![]);
+    }
+
+    #[test]
+    fn drag_shorter_than_a_swipe_is_a_tap() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 0, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 0, 1 - SWIPE_DISTANCE_IN_WC));
+        let up = Event::PointerUp(pointer(FINGER, 0, 1 - SWIPE_DISTANCE_IN_WC));
+
+        assert_eq!(map(&mut recognizer, &[down, moved], 0), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[up], 50),
+            vec![Event::ActionDown(Action::Start)]
+        );
+    }
+
+    #[test]
+    fn mostly_sideways_drag_is_not_a_swipe() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 0, 0));
+        let moved = Event::PointerMove(pointer(
+            FINGER,
+            SWIPE_DISTANCE_IN_WC * 2,
+            -SWIPE_DISTANCE_IN_WC,
+        ));
+
+        assert_eq!(map(&mut recognizer, &[down, moved], 0), vec![]);
+    }
+
+    #[test]
+    fn hold_moves_towards_its_side_till_lifted() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, -100, 0));
+        let up = Event::PointerUp(pointer(FINGER, -100, 0));
+
+        assert_eq!(map(&mut recognizer, &[down], 0), vec![]);
+        assert_eq!(map(&mut recognizer, &[], 100), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[], 150),
+            vec![Event::ActionDown(Action::MoveLeft)]
+        );
+        assert_eq!(
+            map(&mut recognizer, &[up], 500),
+            vec![Event::ActionUp(Action::MoveLeft)]
+        );
+
+        let down = Event::PointerDown(pointer(FINGER, 100, 0));
+        assert_eq!(map(&mut recognizer, &[down], 1000), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[], 1200),
+            vec![Event::ActionDown(Action::MoveRight)]
+        );
+    }
+
+    #[test]
+    fn slow_tap_on_start_screen_is_still_a_tap() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, -100, 0));
+        let up = Event::PointerUp(pointer(FINGER, -100, 0));
+
+        assert_eq!(map_on_start_screen(&mut recognizer, &[down], 0), vec![]);
+        assert_eq!(map_on_start_screen(&mut recognizer, &[], 500), vec![]);
+        assert_eq!(
+            map_on_start_screen(&mut recognizer, &[up], 520),
+            vec![Event::ActionDown(Action::Start)]
+        );
+    }
+
+    #[test]
+    fn sideways_swipe_on_start_screen_moves_till_lifted() {
+        let mut recognizer = recognizer();
+        let down = Event::PointerDown(pointer(FINGER, 100, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 100 - SWIPE_DISTANCE_IN_WC, 5));
+        let up = Event::PointerUp(pointer(FINGER, 100 - SWIPE_DISTANCE_IN_WC, 5));
+
+        assert_eq!(map_on_start_screen(&mut recognizer, &[down], 0), vec![]);
+        assert_eq!(
+            map_on_start_screen(&mut recognizer, &[moved], 500),
+            vec![Event::ActionDown(Action::MoveLeft)]
+        );
+        assert_eq!(
+            map_on_start_screen(&mut recognizer, &[up], 520),
+            vec![Event::ActionUp(Action::MoveLeft)]
+        );
+
+        // Up & down still jump & slide
+        let down = Event::PointerDown(pointer(FINGER, 0, 0));
+        let moved = Event::PointerMove(pointer(FINGER, 5, SWIPE_DISTANCE_IN_WC));
+        assert_eq!(
+            map_on_start_screen(&mut recognizer, &[down, moved], 1000),
+            vec![Event::ActionDown(Action::Slide)]
+        );
+    }
+
+    #[test]
+    fn pointers_are_kept_apart() {
+        let mut recognizer = recognizer();
+
+        // One finger holds to move while another swipes to jump
+        let hold_down = Event::PointerDown(pointer(FINGER, -100, 0));
+        assert_eq!(map(&mut recognizer, &[hold_down], 0), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[], 200),
+            vec![Event::ActionDown(Action::MoveLeft)]
+        );
+
+        let swipe_down = Event::PointerDown(pointer(OTHER_FINGER, 100, 0));
+        let swipe_move = Event::PointerMove(pointer(OTHER_FINGER, 100, -SWIPE_DISTANCE_IN_WC));
+        let swipe_up = Event::PointerUp(pointer(OTHER_FINGER, 100, -SWIPE_DISTANCE_IN_WC));
+        assert_eq!(
+            map(&mut recognizer, &[swipe_down, swipe_move], 220),
+            vec![Event::ActionDown(Action::Jump)]
+        );
+        assert_eq!(
+            map(&mut recognizer, &[swipe_up], 240),
+            vec![Event::ActionUp(Action::Jump)]
+        );
+
+        // The mouse doesn't get mixed up with fingers
+        let mouse_down = Event::PointerDown(pointer(PointerId::Mouse, -100, 0));
+        let mouse_up = Event::PointerUp(pointer(PointerId::Mouse, -100, 0));
+        assert_eq!(map(&mut recognizer, &[mouse_down], 260), vec![]);
+        assert_eq!(map(&mut recognizer, &[], 500), vec![]);
+
+        // Move left stays down till both the finger & the mouse let go
+        let hold_up = Event::PointerUp(pointer(FINGER, -100, 0));
+        assert_eq!(map(&mut recognizer, &[hold_up], 520), vec![]);
+        assert_eq!(
+            map(&mut recognizer, &[mouse_up], 540),
+            vec![Event::ActionUp(Action::MoveLeft)]
+        );
+    }
+
+    #[test]
+    fn other_events_pass_through() {
+        let mut recognizer = recognizer();
+        let events = [Event::FocusLost, Event::ActionDown(Action::Pause)];
+        assert_eq!(map(&mut recognizer, &events, 0), events.to_vec());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::graphics::data::{Tile, TileData, TileSheet};
+use crate::rect::Rect;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+
+/// What lives in the manifest file bundled with the tile sheets. Sheets &
+/// tiles are keyed by their names (check out [TileSheet::name] &
+/// [Tile::name]). BTreeMaps to keep the file stable when it is written out.
+///
+/// Hitboxes are relative to the top left of their tile. A tile without any
+/// can be hit anywhere within its bounds. Listing none for a tile isn't the
+/// same as leaving it out though (a tile that can't be hit is a mistake)
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TileAtlasManifest {
+    pub sheets: BTreeMap<String, SheetManifest>,
+    pub tiles: BTreeMap<String, Rect>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hitboxes: BTreeMap<String, Vec<Rect>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SheetManifest {
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TileAtlasManifest {
+    pub fn from_ron(text: &str) -> Result<TileAtlasManifest, TileAtlasError> {
+        ron::from_str(text).map_err(TileAtlasError::Parse)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new().with_depth_limit(2))
+            .expect("Tile atlas manifest couldn't be serialized")
+    }
+}
+
+/// Where to find every tile. Can only be built from a manifest that
+/// accounts for every tile the game uses. This way, a bad manifest is
+/// caught upfront instead of drawing garbage halfway through a game
+#[derive(Clone, Debug)]
+pub struct TileAtlas {
+    sheet_images: HashMap<TileSheet, String>,
+    tiles_data: HashMap<Tile, TileData>,
+    hitboxes: HashMap<Tile, Vec<Rect>>,
+}
+
+impl TileAtlas {
+    pub fn from_ron(text: &str) -> Result<TileAtlas, TileAtlasError> {
+        TileAtlas::from_manifest(&TileAtlasManifest::from_ron(text)?)
+    }
+
+    pub fn from_manifest(manifest: &TileAtlasManifest) -> Result<TileAtlas, TileAtlasError> {
+        let mut problems = Vec::new();
+
+        // Sheets first since tiles are checked against them
+        let known_sheets: HashMap<String, TileSheet> = TileSheet::ALL
+            .iter()
+            .map(|&sheet| (sheet.name(), sheet))
+            .collect();
+
+        let mut sheets = HashMap::new();
+        for (name, sheet_manifest) in manifest.sheets.iter() {
+            match known_sheets.get(name) {
+                None => problems.push(TileAtlasProblem::UnknownSheet(name.clone())),
+                Some(&sheet) => {
+                    sheets.insert(sheet, sheet_manifest);
+                }
+            }
+        }
+
+        for sheet in TileSheet::ALL.iter() {
+            if !sheets.contains_key(sheet) {
+                problems.push(TileAtlasProblem::MissingSheet(sheet.name()))
+            }
+        }
+
+        // Now the tiles
+        let known_tiles: HashMap<String, Tile> = Tile::all()
+            .into_iter()
+            .map(|tile| (tile.name(), tile))
+            .collect();
+
+        let mut tiles_data = HashMap::new();
+        for (name, &bounds) in manifest.tiles.iter() {
+            let tile = match known_tiles.get(name) {
+                Some(&tile) => tile,
+                None => {
+                    problems.push(TileAtlasProblem::UnknownTile(name.clone()));
+                    continue;
+                }
+            };
+
+            if let Some(sheet_manifest) = sheets.get(&tile.tile_sheet()) {
+                let is_within_sheet = bounds.width() > 0
+                    && bounds.height() > 0
+                    && bounds.left() >= 0
+                    && bounds.top() >= 0
+                    && bounds.right() <= sheet_manifest.width as i32
+                    && bounds.bottom() <= sheet_manifest.height as i32;
+
+                if !is_within_sheet {
+                    problems.push(TileAtlasProblem::OutOfBounds {
+                        tile: name.clone(),
+                        bounds,
+                        sheet_width: sheet_manifest.width,
+                        sheet_height: sheet_manifest.height,
+                    })
+                }
+            }
+
+            tiles_data.insert(
+                tile,
+                TileData {
+                    tile,
+                    tile_sheet: tile.tile_sheet(),
+                    bounds_in_tile_sheet: bounds,
+                },
+            );
+        }
+
+        for tile in Tile::all() {
+            if !tiles_data.contains_key(&tile) {
+                problems.push(TileAtlasProblem::MissingTile(tile.name()))
+            }
+        }
+
+        // Hitboxes last since they are checked against the tiles
+        let mut hitboxes = HashMap::new();
+        for (name, tile_hitboxes) in manifest.hitboxes.iter() {
+            let tile = match known_tiles.get(name) {
+                Some(&tile) => tile,
+                None => {
+                    problems.push(TileAtlasProblem::UnknownTile(name.clone()));
+                    continue;
+                }
+            };
+
+            if tile_hitboxes.is_empty() {
+                problems.push(TileAtlasProblem::NoHitboxes(name.clone()));
+            }
+
+            if let Some(tile_data) = tiles_data.get(&tile) {
+                let tile_width = tile_data.bounds_in_tile_sheet.width();
+                let tile_height = tile_data.bounds_in_tile_sheet.height();
+                for &hitbox in tile_hitboxes.iter() {
+                    let is_within_tile = hitbox.width() > 0
+                        && hitbox.height() > 0
+                        && hitbox.left() >= 0
+                        && hitbox.top() >= 0
+                        && hitbox.right() <= tile_width as i32
+                        && hitbox.bottom() <= tile_height as i32;
+
+                    if !is_within_tile {
+                        problems.push(TileAtlasProblem::HitboxOutOfBounds {
+                            tile: name.clone(),
+                            hitbox,
+                            tile_width,
+                            tile_height,
+                        })
+                    }
+                }
+            }
+
+            hitboxes.insert(tile, tile_hitboxes.clone());
+        }
+
+        for (tile, tile_data) in tiles_data.iter() {
+            let bounds = tile_data.bounds_in_tile_sheet;
+            hitboxes
+                .entry(*tile)
+                .or_insert_with(|| vec![Rect::new(0, 0, bounds.width(), bounds.height())]);
+        }
+
+        if problems.is_empty() {
+            Ok(TileAtlas {
+                sheet_images: sheets
+                    .into_iter()
+                    .map(|(sheet, sheet_manifest)| (sheet, sheet_manifest.image.clone()))
+                    .collect(),
+                tiles_data,
+                hitboxes,
+            })
+        } else {
+            Err(TileAtlasError::Invalid(problems))
+        }
+    }
+
+    /// Name of the image file (in the assets) holding the sheet
+    pub fn image_file(&self, tile_sheet: TileSheet) -> &str {
+        // Validation makes sure that every sheet is around
+        &self.sheet_images[&tile_sheet]
+    }
+
+    pub fn tile_data(&self, tile: Tile) -> TileData {
+        // Validation makes sure that every tile is around
+        self.tiles_data[&tile]
+    }
+
+    /// Relative to the top left of the tile, in tile sheet pixels
+    pub fn hitboxes(&self, tile: Tile) -> &[Rect] {
+        // Every tile gets at least its whole bounds as the hitbox
+        &self.hitboxes[&tile]
+    }
+}
+
+#[derive(Debug)]
+pub enum TileAtlasError {
+    Parse(ron::Error),
+    Invalid(Vec<TileAtlasProblem>),
+}
+
+#[derive(Debug)]
+pub enum TileAtlasProblem {
+    UnknownSheet(String),
+    MissingSheet(String),
+    UnknownTile(String),
+    MissingTile(String),
+    OutOfBounds {
+        tile: String,
+        bounds: Rect,
+        sheet_width: u32,
+        sheet_height: u32,
+    },
+    HitboxOutOfBounds {
+        tile: String,
+        hitbox: Rect,
+        tile_width: u32,
+        tile_height: u32,
+    },
+    NoHitboxes(String),
+}
+
+impl fmt::Display for TileAtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TileAtlasError::Parse(error) => {
+                write!(f, "Tile atlas manifest couldn't be parsed: {}", error)
+            }
+            TileAtlasError::Invalid(problems) => {
+                write!(f, "Tile atlas manifest has {} problem(s)", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for TileAtlasProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TileAtlasProblem::UnknownSheet(name) => write!(f, "Unknown sheet: {}", name),
+            TileAtlasProblem::MissingSheet(name) => write!(f, "Missing sheet: {}", name),
+            TileAtlasProblem::UnknownTile(name) => write!(f, "Unknown tile: {}", name),
+            TileAtlasProblem::MissingTile(name) => write!(f, "Missing tile: {}", name),
+            TileAtlasProblem::OutOfBounds {
+                tile,
+                bounds,
+                sheet_width,
+                sheet_height,
+            } => write!(
+                f,
+                "Tile {} at (x: {}, y: {}, width: {}, height: {}) doesn't fit in its \
+                 {}x{} sheet",
+                tile,
+                bounds.x(),
+                bounds.y(),
+                bounds.width(),
+                bounds.height(),
+                sheet_width,
+                sheet_height
+            ),
+            TileAtlasProblem::HitboxOutOfBounds {
+                tile,
+                hitbox,
+                tile_width,
+                tile_height,
+            } => write!(
+                f,
+                "Hitbox of tile {} at (x: {}, y: {}, width: {}, height: {}) doesn't fit \
+                 in the {}x{} tile",
+                tile,
+                hitbox.x(),
+                hitbox.y(),
+                hitbox.width(),
+                hitbox.height(),
+                tile_width,
+                tile_height
+            ),
+            TileAtlasProblem::NoHitboxes(name) => {
+                write!(f, "Tile {} has an empty list of hitboxes", name)
+            }
+        }
+    }
+}
+
+impl Error for TileAtlasError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> TileAtlasManifest {
+        TileAtlasManifest::from_ron(include_str!("../../../assets_processed/tile_atlas.ron"))
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_list_of_hitboxes_is_called_out() {
+        // Would otherwise never collide with anything
+        let mut manifest = manifest();
+        let name = manifest.tiles.keys().next().unwrap().clone();
+        manifest.hitboxes.insert(name.clone(), Vec::new());
+
+        match TileAtlas::from_manifest(&manifest) {
+            Err(TileAtlasError::Invalid(problems)) => assert!(problems.iter().any(
+                |problem| matches!(problem, TileAtlasProblem::NoHitboxes(tile) if *tile == name)
+            )),
+            _ => panic!("Empty list of hitboxes should have been called out"),
+        }
+    }
+
+    #[test]
+    fn left_out_hitboxes_cover_the_whole_tile() {
+        let mut manifest = manifest();
+        let name = manifest.tiles.keys().next().unwrap().clone();
+        manifest.hitboxes.remove(&name);
+
+        let tile = Tile::all()
+            .into_iter()
+            .find(|tile| tile.name() == name)
+            .unwrap();
+
+        let bounds = manifest.tiles[&name];
+        let tile_atlas = TileAtlas::from_manifest(&manifest).unwrap();
+        assert_eq!(
+            tile_atlas.hitboxes(tile),
+            &[Rect::new(0, 0, bounds.width(), bounds.height())]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Only these many scores are kept around
+const MAX_HIGH_SCORES_COUNT: usize = 10;
+
+/// Best scores across games (highest first). This knows nothing about
+/// where the scores are kept between sessions. That is for the frontends
+/// to decide since it depends on the platform
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HighScores {
+    scores: Vec<u64>,
+
+    // Every extra life is another chance to score more. So, games played
+    // with more than one life are kept apart (by the number of lives)
+    #[serde(default)]
+    scores_with_lives: BTreeMap<u8, Vec<u64>>,
+}
+
+impl HighScores {
+    pub fn new() -> HighScores {
+        HighScores::default()
+    }
+
+    pub fn from_ron(text: &str) -> Result<HighScores, ron::Error> {
+        let mut high_scores: HighScores = ron::from_str(text)?;
+
+        // Could have been edited by hand. Restore order & limit
+        let scores_with_lives = high_scores.scores_with_lives.values_mut();
+        for scores in std::iter::once(&mut high_scores.scores).chain(scores_with_lives) {
+            scores.sort_unstable_by(|a, b| b.cmp(a));
+            scores.truncate(MAX_HIGH_SCORES_COUNT);
+        }
+
+        Ok(high_scores)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::to_string(self).expect("High scores couldn't be serialized")
+    }
+
+    /// Of games played with `lives`
+    pub fn scores(&self, lives: u8) -> &[u64] {
+        if lives == 1 {
+            &self.scores
+        } else {
+            self.scores_with_lives
+                .get(&lives)
+                .map_or(&[], |scores| scores)
+        }
+    }
+
+    /// Of games played with `lives`. 0 if no such game has been recorded yet
+    pub fn best(&self, lives: u8) -> u64 {
+        self.scores(lives).first().copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if `score` is better than every score seen before in
+    /// games played with `lives`. Games that didn't score at all aren't
+    /// worth remembering
+    pub fn add(&mut self, score: u64, lives: u8) -> bool {
+        if score == 0 {
+            return false;
+        }
+
+        let is_new_record = score > self.best(lives);
+        let scores = if lives == 1 {
+            &mut self.scores
+        } else {
+            self.scores_with_lives.entry(lives).or_default()
+        };
+
+        let index = scores
+            .iter()
+            .position(|high_score| score > *high_score)
+            .unwrap_or(scores.len());
+
+        if index < MAX_HIGH_SCORES_COUNT {
+            scores.insert(index, score);
+            scores.truncate(MAX_HIGH_SCORES_COUNT);
+        }
+
+        is_new_record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_are_kept_highest_first() {
+        let mut high_scores = HighScores::new();
+        assert!(high_scores.add(20, 1));
+        assert!(!high_scores.add(10, 1));
+        assert!(high_scores.add(30, 1));
+        assert!(!high_scores.add(30, 1));
+        assert_eq!(high_scores.scores(1), &[30, 30, 20, 10]);
+        assert_eq!(high_scores.best(1), 30);
+    }
+
+    #[test]
+    fn only_the_best_few_are_kept() {
+        let mut high_scores = HighScores::new();
+        for score in 1..=MAX_HIGH_SCORES_COUNT as u64 + 5 {
+            high_scores.add(score, 1);
+        }
+
+        // Too low to make the cut
+        assert!(!high_scores.add(1, 1));
+
+        let scores = high_scores.scores(1);
+        assert_eq!(scores.len(), MAX_HIGH_SCORES_COUNT);
+        assert_eq!(scores.last(), Some(&6));
+    }
+
+    #[test]
+    fn games_without_a_score_are_not_kept() {
+        let mut high_scores = HighScores::new();
+        assert!(!high_scores.add(0, 1));
+        assert!(high_scores.scores(1).is_empty());
+        assert_eq!(high_scores.best(1), 0);
+    }
+
+    #[test]
+    fn games_with_more_lives_are_kept_apart() {
+        let mut high_scores = HighScores::new();
+        high_scores.add(10, 1);
+        assert!(high_scores.add(5, 3));
+        assert_eq!(high_scores.scores(1), &[10]);
+        assert_eq!(high_scores.scores(3), &[5]);
+        assert!(high_scores.scores(2).is_empty());
+    }
+
+    #[test]
+    fn hand_edited_scores_are_put_back_in_order() {
+        let high_scores =
+            HighScores::from_ron("(scores: [1, 3, 2, 4, 5, 6, 7, 8, 9, 10, 11, 12])").unwrap();
+        assert_eq!(high_scores.scores(1), &[12, 11, 10, 9, 8, 7, 6, 5, 4, 3]);
+    }
+
+    #[test]
+    fn scores_survive_ron() {
+        let mut high_scores = HighScores::new();
+        high_scores.add(10, 1);
+        high_scores.add(20, 2);
+
+        let high_scores = HighScores::from_ron(&high_scores.to_ron()).unwrap();
+        assert_eq!(high_scores.scores(1), &[10]);
+        assert_eq!(high_scores.scores(2), &[20]);
+    }
+
+    #[test]
+    fn bad_ron_is_an_error() {
+        assert!(HighScores::from_ron("(scores: [ten])").is_err());
+    }
+}
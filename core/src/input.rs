@@ -1,9 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Event {
     Quit,
+    FocusLost,
+
+    /// Keys are what the frontends deliver. [crate::game_loop::GameLoop]
+    /// turns them into actions (check out [crate::key_bindings::KeyBindings])
+    /// before the rest of the game gets to see them
     KeyDown(Keycode),
     KeyUp(Keycode),
+
+    ActionDown(Action),
+    ActionUp(Action),
+
+    /// Touches & mouse presses. [crate::game_loop::GameLoop] turns them
+    /// into actions too (check out [crate::gestures::GestureRecognizer])
+    PointerDown(Pointer),
+    PointerMove(Pointer),
+    PointerUp(Pointer),
+}
+
+/// Where a pointer is, in world coordinates. `id` tells apart pointers that
+/// are down at the same time
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Pointer {
+    pub id: PointerId,
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum PointerId {
+    Mouse,
+
+    /// Fingers go by whatever IDs the platform gives them
+    Touch(i64),
+}
+
+/// What a player can do. Any number of keys can be bound to each of these
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Action {
+    Jump,
+    Slide,
+    MoveLeft,
+    MoveRight,
+    Start,
+    Pause,
+    Quit,
+
+    // Shows what the game sees (like hitboxes) over the game
+    ToggleDebug,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Jump,
+        Action::Slide,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Start,
+        Action::Pause,
+        Action::Quit,
+        Action::ToggleDebug,
+    ];
 }
 
+/// Physical keys. Frontends map whatever keys they can to these
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Keycode {
     Up,
     Down,
@@ -11,4 +75,131 @@ pub enum Keycode {
     Right,
     Escape,
     Space,
+    Return,
+    Backspace,
+    Tab,
+    LShift,
+    RShift,
+    LCtrl,
+    RCtrl,
+
+    // Letters
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+
+    // Numbers in the main row
+    Num0,
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    Num6,
+    Num7,
+    Num8,
+    Num9,
+
+    // Function keys
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+}
+
+impl Keycode {
+    pub const ALL: [Keycode; 61] = [
+        Keycode::Up,
+        Keycode::Down,
+        Keycode::Left,
+        Keycode::Right,
+        Keycode::Escape,
+        Keycode::Space,
+        Keycode::Return,
+        Keycode::Backspace,
+        Keycode::Tab,
+        Keycode::LShift,
+        Keycode::RShift,
+        Keycode::LCtrl,
+        Keycode::RCtrl,
+        Keycode::A,
+        Keycode::B,
+        Keycode::C,
+        Keycode::D,
+        Keycode::E,
+        Keycode::F,
+        Keycode::G,
+        Keycode::H,
+        Keycode::I,
+        Keycode::J,
+        Keycode::K,
+        Keycode::L,
+        Keycode::M,
+        Keycode::N,
+        Keycode::O,
+        Keycode::P,
+        Keycode::Q,
+        Keycode::R,
+        Keycode::S,
+        Keycode::T,
+        Keycode::U,
+        Keycode::V,
+        Keycode::W,
+        Keycode::X,
+        Keycode::Y,
+        Keycode::Z,
+        Keycode::Num0,
+        Keycode::Num1,
+        Keycode::Num2,
+        Keycode::Num3,
+        Keycode::Num4,
+        Keycode::Num5,
+        Keycode::Num6,
+        Keycode::Num7,
+        Keycode::Num8,
+        Keycode::Num9,
+        Keycode::F1,
+        Keycode::F2,
+        Keycode::F3,
+        Keycode::F4,
+        Keycode::F5,
+        Keycode::F6,
+        Keycode::F7,
+        Keycode::F8,
+        Keycode::F9,
+        Keycode::F10,
+        Keycode::F11,
+        Keycode::F12,
+    ];
 }
@@ -1,33 +1,95 @@
 use crate::components::input::InputControlled;
+use crate::data::PlayerData;
 
+/// Which part of the jump the player is in. Each part has its own gravity
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Phase {
+    // Going up with `up` held
+    Rise,
+
+    // Floating around the top with `up` held. Gives the player a little
+    // more time to line up the landing
+    ApexHang,
+
+    // Coming down. Either the jump is over or `up` was let go of early
+    Fall,
+}
+
+/// The height of a jump is worked out a phase at a time. Every time the
+/// phase changes, a new parabola is started from wherever the last one
+/// left off
+#[derive(Copy, Clone)]
 pub struct JumpPhysics {
     start_at_tick: u64,
     initial_height: i32,
     initial_jump_velocity: f32,
     gravity: f32,
-    is_lower_gravity: bool,
+    phase: Phase,
+    fall_gravity_multiplier: f32,
+    apex_hang_gravity_multiplier: f32,
+    apex_hang_speed_in_wc_per_tick: f32,
 }
 
 impl JumpPhysics {
-    pub fn from_ground(
+    pub fn from_ground(current_tick: u64, player_data: &PlayerData) -> JumpPhysics {
+        JumpPhysics::from_height(current_tick, 0, player_data)
+    }
+
+    /// A jump that doesn't start from the ground. For eg., a jump made
+    /// during coyote time
+    pub fn from_height(current_tick: u64, height: i32, player_data: &PlayerData) -> JumpPhysics {
+        let gravity = JumpPhysics::compute_gravity(
+            player_data.ticks_in_max_jump,
+            player_data.max_jump_height_in_wc,
+        );
+
+        let initial_velocity =
+            JumpPhysics::compute_initial_velocity(player_data.ticks_in_max_jump, gravity);
+
+        JumpPhysics::new(
+            current_tick,
+            height,
+            initial_velocity,
+            gravity,
+            Phase::Rise,
+            player_data,
+        )
+    }
+
+    /// Dropping down from `height` without having jumped. For eg., running
+    /// off an edge
+    pub fn fall(current_tick: u64, height: i32, player_data: &PlayerData) -> JumpPhysics {
+        let gravity = JumpPhysics::compute_gravity(
+            player_data.ticks_in_max_jump,
+            player_data.max_jump_height_in_wc,
+        );
+
+        JumpPhysics::new(current_tick, height, 0.0, gravity, Phase::Fall, player_data)
+    }
+
+    fn new(
         current_tick: u64,
-        ticks_in_max_jump: u8,
-        max_jump_height_in_wc: u8,
+        height: i32,
+        initial_jump_velocity: f32,
+        gravity: f32,
+        phase: Phase,
+        player_data: &PlayerData,
     ) -> JumpPhysics {
-        let gravity = JumpPhysics::compute_gravity(ticks_in_max_jump, max_jump_height_in_wc);
-        let initial_velocity = JumpPhysics::compute_initial_velocity(ticks_in_max_jump, gravity);
         JumpPhysics {
             start_at_tick: current_tick,
-            initial_height: 0,
-            initial_jump_velocity: initial_velocity,
+            initial_height: height,
+            initial_jump_velocity,
             gravity,
-            is_lower_gravity: true,
+            phase,
+            fall_gravity_multiplier: player_data.fall_gravity_multiplier,
+            apex_hang_gravity_multiplier: player_data.apex_hang_gravity_multiplier,
+            apex_hang_speed_in_wc_per_tick: player_data.apex_hang_speed_in_wc_per_tick,
         }
     }
 
     pub fn compute_height(&self, current_tick: u64) -> i32 {
         let ticks_since_jump_started = (current_tick - self.start_at_tick) as f32;
-        (((self.gravity * ticks_since_jump_started.powf(2.0)) / 2.0)
+        (((self.phase_gravity() * ticks_since_jump_started.powf(2.0)) / 2.0)
             + (self.initial_jump_velocity * ticks_since_jump_started)) as i32
             + self.initial_height
     }
@@ -38,24 +100,40 @@ impl JumpPhysics {
         current_height: i32,
         input_ctrl: &InputControlled,
     ) -> JumpPhysics {
-        if !self.is_lower_gravity {
-            self
-        } else {
-            let ticks_since_jump_started = (current_tick - self.start_at_tick) as f32;
-            let current_velocity =
-                self.initial_jump_velocity + (self.gravity * ticks_since_jump_started);
-
-            if input_ctrl.up_engaged() && current_velocity > 0.0 {
-                self
-            } else {
-                JumpPhysics {
-                    start_at_tick: current_tick,
-                    initial_height: current_height,
-                    initial_jump_velocity: 0.0,
-                    gravity: self.gravity * 2.0,
-                    is_lower_gravity: false,
-                }
+        let ticks_since_jump_started = (current_tick - self.start_at_tick) as f32;
+        let current_velocity =
+            self.initial_jump_velocity + (self.phase_gravity() * ticks_since_jump_started);
+
+        let is_near_apex = current_velocity.abs() < self.apex_hang_speed_in_wc_per_tick;
+        let (phase, velocity) = match self.phase {
+            Phase::Fall => return self,
+
+            // Letting go on the way up cuts the jump short
+            Phase::Rise if !input_ctrl.up_engaged() => (Phase::Fall, 0.0),
+            Phase::Rise if is_near_apex => (Phase::ApexHang, current_velocity),
+            Phase::Rise if current_velocity <= 0.0 => (Phase::Fall, current_velocity),
+            Phase::Rise => return self,
+
+            Phase::ApexHang if !input_ctrl.up_engaged() || !is_near_apex => {
+                (Phase::Fall, current_velocity.min(0.0))
             }
+            Phase::ApexHang => return self,
+        };
+
+        JumpPhysics {
+            start_at_tick: current_tick,
+            initial_height: current_height,
+            initial_jump_velocity: velocity,
+            phase,
+            ..self
+        }
+    }
+
+    fn phase_gravity(&self) -> f32 {
+        match self.phase {
+            Phase::Rise => self.gravity,
+            Phase::ApexHang => self.gravity * self.apex_hang_gravity_multiplier,
+            Phase::Fall => self.gravity * self.fall_gravity_multiplier,
         }
     }
 
@@ -74,3 +152,179 @@ impl JumpPhysics {
         -gravity * ticks_to_hit_apex
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::input::data::Direction;
+
+    /// A plain parabola without any of the extras
+    fn plain_player_data() -> PlayerData {
+        PlayerData {
+            fall_gravity_multiplier: 1.0,
+            apex_hang_gravity_multiplier: 1.0,
+            apex_hang_speed_in_wc_per_tick: 0.0,
+            ..PlayerData::new()
+        }
+    }
+
+    fn up_held() -> InputControlled {
+        let mut input_ctrl = InputControlled::new();
+        input_ctrl.update_key_down(Direction::Up);
+        input_ctrl
+    }
+
+    /// Heights from `start_tick` till the player is back on the ground (or
+    /// till `max_ticks` have passed). Works through the jump the same way
+    /// [crate::systems::PlayerSystem] does. `up` is let go of at
+    /// `release_at_tick`, if given
+    fn heights(
+        mut jump_physics: JumpPhysics,
+        start_tick: u64,
+        release_at_tick: Option<u64>,
+        max_ticks: u64,
+    ) -> Vec<i32> {
+        let mut input_ctrl = up_held();
+        let mut heights = Vec::new();
+        for current_tick in start_tick..start_tick + max_ticks {
+            if Some(current_tick) == release_at_tick {
+                input_ctrl.update_key_up(Direction::Up);
+            }
+
+            let height = jump_physics.compute_height(current_tick);
+            heights.push(height);
+            if height <= 0 && current_tick > start_tick {
+                break;
+            }
+
+            jump_physics =
+                jump_physics.update_gravity_if_required(current_tick, height, &input_ctrl);
+        }
+
+        heights
+    }
+
+    fn apex(heights: &[i32]) -> (usize, i32) {
+        heights
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|(_, height)| *height)
+            .unwrap()
+    }
+
+    #[test]
+    fn full_jump_tops_out_half_way_through() {
+        let player_data = plain_player_data();
+        let heights = heights(JumpPhysics::from_ground(0, &player_data), 0, None, 1000);
+
+        // Heights are rounded down. So, the top is flat for a few ticks
+        let apex_height = apex(&heights).1;
+        let ticks_to_apex = usize::from(player_data.ticks_in_max_jump / 2);
+        assert_eq!(heights[ticks_to_apex], apex_height);
+        assert!((apex_height - i32::from(player_data.max_jump_height_in_wc)).abs() <= 1);
+        assert_eq!(
+            heights.len() - 1,
+            usize::from(player_data.ticks_in_max_jump)
+        );
+    }
+
+    #[test]
+    fn full_jump_rises_and_falls_alike() {
+        let player_data = plain_player_data();
+        let jump_physics = JumpPhysics::from_ground(0, &player_data);
+        let ticks_in_max_jump = u64::from(player_data.ticks_in_max_jump);
+        for tick in 0..=ticks_in_max_jump {
+            let rising = jump_physics.compute_height(tick);
+            let falling = jump_physics.compute_height(ticks_in_max_jump - tick);
+            assert!((rising - falling).abs() <= 1, "{} vs {}", rising, falling);
+        }
+    }
+
+    #[test]
+    fn letting_go_early_cuts_the_jump_short() {
+        let player_data = PlayerData::new();
+        let full = heights(JumpPhysics::from_ground(0, &player_data), 0, None, 1000);
+        let short = heights(JumpPhysics::from_ground(0, &player_data), 0, Some(10), 1000);
+
+        // Goes up as usual till then & only comes down after
+        assert_eq!(full[..=10], short[..=10]);
+        assert!(short[10..].windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(apex(&short).1 < apex(&full).1 * 2 / 3);
+        assert!(short.len() < full.len());
+    }
+
+    #[test]
+    fn apex_hang_stays_up_longer_than_a_plain_jump() {
+        let ticks_near_apex = |player_data: PlayerData| {
+            let heights = heights(JumpPhysics::from_ground(0, &player_data), 0, None, 1000);
+            let apex_height = apex(&heights).1;
+            heights
+                .iter()
+                .filter(|height| **height >= apex_height - 5)
+                .count()
+        };
+
+        let plain = plain_player_data();
+        let hanging = PlayerData {
+            apex_hang_gravity_multiplier: 0.5,
+            apex_hang_speed_in_wc_per_tick: 1.0,
+            ..plain_player_data()
+        };
+
+        assert!(ticks_near_apex(hanging) > ticks_near_apex(plain));
+    }
+
+    #[test]
+    fn falls_faster_than_it_rises() {
+        let player_data = PlayerData {
+            fall_gravity_multiplier: 2.0,
+            ..plain_player_data()
+        };
+
+        let heights = heights(JumpPhysics::from_ground(0, &player_data), 0, None, 1000);
+        let apex_tick = apex(&heights).0;
+        assert!(heights.len() - 1 - apex_tick < apex_tick);
+    }
+
+    #[test]
+    fn jump_from_height_goes_up_from_there() {
+        let player_data = plain_player_data();
+        let from_ground = heights(JumpPhysics::from_ground(0, &player_data), 0, None, 1000);
+        let from_height = heights(
+            JumpPhysics::from_height(500, 40, &player_data),
+            500,
+            None,
+            1000,
+        );
+
+        // Same jump. Just moved up (mid-air starts don't care when they start)
+        assert_eq!(from_height[0], 40);
+        for (tick, height) in from_ground.iter().enumerate() {
+            assert_eq!(from_height[tick], height + 40);
+        }
+
+        assert!(from_height.len() > from_ground.len());
+    }
+
+    #[test]
+    fn fall_only_comes_down() {
+        let player_data = PlayerData::new();
+        let falling = heights(JumpPhysics::fall(200, 100, &player_data), 200, None, 1000);
+
+        assert_eq!(falling[0], 100);
+        assert!(falling.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(*falling.last().unwrap() <= 0);
+
+        // Falls are as quick as the end of a jump
+        let plain_falling = heights(
+            JumpPhysics::fall(200, 100, &plain_player_data()),
+            200,
+            None,
+            1000,
+        );
+
+        assert!(player_data.fall_gravity_multiplier > 1.0);
+        assert!(falling.len() < plain_falling.len());
+    }
+}
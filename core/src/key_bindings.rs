@@ -0,0 +1,203 @@
+use crate::input::{Action, Event, Keycode};
+use crate::resources::EventQueue;
+use std::collections::{BTreeMap, HashSet};
+
+/// Keys bound to each action. Any number of keys can be bound to an action
+/// & a key can be bound to more than one action too
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    bindings: BTreeMap<Action, Vec<Keycode>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        let mut bindings = BTreeMap::new();
+        bindings.insert(Action::Jump, vec![Keycode::Up, Keycode::W]);
+        bindings.insert(Action::Slide, vec![Keycode::Down, Keycode::S]);
+        bindings.insert(Action::MoveLeft, vec![Keycode::Left, Keycode::A]);
+        bindings.insert(Action::MoveRight, vec![Keycode::Right, Keycode::D]);
+        bindings.insert(Action::Start, vec![Keycode::Space, Keycode::Return]);
+        bindings.insert(Action::Pause, vec![Keycode::P]);
+        bindings.insert(Action::Quit, vec![Keycode::Escape]);
+        bindings.insert(Action::ToggleDebug, vec![Keycode::F3]);
+        KeyBindings { bindings }
+    }
+}
+
+impl KeyBindings {
+    /// Actions missing from `text` keep their default keys. To leave an
+    /// action without any key, bind it to an empty list
+    pub fn from_ron(text: &str) -> Result<KeyBindings, ron::Error> {
+        let overrides: BTreeMap<Action, Vec<Keycode>> = ron::from_str(text)?;
+        let mut key_bindings = KeyBindings::default();
+        key_bindings.bindings.extend(overrides);
+        Ok(key_bindings)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(&self.bindings, ron::ser::PrettyConfig::new())
+            .expect("Key bindings couldn't be serialized")
+    }
+
+    pub fn keys(&self, action: Action) -> &[Keycode] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    fn actions(&self, keycode: Keycode) -> impl Iterator<Item = Action> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, keycodes)| keycodes.contains(&keycode))
+            .map(|(action, _)| *action)
+    }
+}
+
+/// Turns key events into action events as per the bindings. Keeps track of
+/// the keys that are held down to make sure that
+///     - an action is down as long as at least one of its keys is down
+///     - repeated key downs (which some platforms send while a key is being
+///       held) don't turn into repeated action downs
+pub(crate) struct ActionMapper {
+    key_bindings: KeyBindings,
+    pressed_keys: HashSet<Keycode>,
+}
+
+impl ActionMapper {
+    pub(crate) fn new(key_bindings: KeyBindings) -> ActionMapper {
+        ActionMapper {
+            key_bindings,
+            pressed_keys: HashSet::new(),
+        }
+    }
+
+    /// Replaces key events in the queue with action events. Every other
+    /// event is left as it is
+    pub(crate) fn map(&mut self, event_queue: &mut EventQueue) {
+        let events: Vec<Event> = event_queue.drain().collect();
+        for event in events {
+            match event {
+                Event::KeyDown(keycode) => {
+                    if self.pressed_keys.insert(keycode) {
+                        for action in self.key_bindings.actions(keycode) {
+                            if !self.is_any_other_key_pressed(action, keycode) {
+                                event_queue.add_event(Event::ActionDown(action))
+                            }
+                        }
+                    }
+                }
+
+                Event::KeyUp(keycode) => {
+                    if self.pressed_keys.remove(&keycode) {
+                        for action in self.key_bindings.actions(keycode) {
+                            if !self.is_any_other_key_pressed(action, keycode) {
+                                event_queue.add_event(Event::ActionUp(action))
+                            }
+                        }
+                    }
+                }
+
+                // Keys let go of elsewhere won't be reported to us. Consider
+                // them all released or the next press would be swallowed
+                Event::FocusLost => {
+                    for action in Action::ALL.iter() {
+                        let is_down = self
+                            .key_bindings
+                            .keys(*action)
+                            .iter()
+                            .any(|key| self.pressed_keys.contains(key));
+
+                        if is_down {
+                            event_queue.add_event(Event::ActionUp(*action))
+                        }
+                    }
+
+                    self.pressed_keys.clear();
+                    event_queue.add_event(event)
+                }
+
+                _ => event_queue.add_event(event),
+            }
+        }
+    }
+
+    fn is_any_other_key_pressed(&self, action: Action, keycode: Keycode) -> bool {
+        self.key_bindings
+            .keys(action)
+            .iter()
+            .any(|key| *key != keycode && self.pressed_keys.contains(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Events that come out of mapping `events`
+    fn map(action_mapper: &mut ActionMapper, events: &[Event]) -> Vec<Event> {
+        let mut event_queue = EventQueue::new();
+        for event in events {
+            event_queue.add_event(*event)
+        }
+
+        action_mapper.map(&mut event_queue);
+        event_queue.iter().copied().collect()
+    }
+
+    #[test]
+    fn overrides_leave_other_actions_alone() {
+        let key_bindings = KeyBindings::from_ron("{Jump: [Space], Pause: []}").unwrap();
+        assert_eq!(key_bindings.keys(Action::Jump), &[Keycode::Space]);
+        assert!(key_bindings.keys(Action::Pause).is_empty());
+        assert_eq!(
+            key_bindings.keys(Action::Slide),
+            KeyBindings::default().keys(Action::Slide)
+        );
+    }
+
+    #[test]
+    fn bad_ron_is_an_error() {
+        assert!(KeyBindings::from_ron("{Jump: [NotAKey]}").is_err());
+    }
+
+    #[test]
+    fn held_keys_do_not_repeat_actions() {
+        let mut action_mapper = ActionMapper::new(KeyBindings::default());
+        let events = map(
+            &mut action_mapper,
+            &[
+                Event::KeyDown(Keycode::Up),
+                Event::KeyDown(Keycode::Up),
+                Event::KeyDown(Keycode::W),
+                Event::KeyUp(Keycode::Up),
+                Event::KeyUp(Keycode::W),
+            ],
+        );
+
+        // Jump is down from the first press till both keys are let go of
+        assert_eq!(
+            events,
+            vec![
+                Event::ActionDown(Action::Jump),
+                Event::ActionUp(Action::Jump)
+            ]
+        );
+    }
+
+    #[test]
+    fn losing_focus_lets_go_of_everything() {
+        let mut action_mapper = ActionMapper::new(KeyBindings::default());
+        map(
+            &mut action_mapper,
+            &[Event::KeyDown(Keycode::Up), Event::KeyDown(Keycode::Left)],
+        );
+
+        let events = map(&mut action_mapper, &[Event::FocusLost]);
+        assert_eq!(events.len(), 3);
+        assert!(events.contains(&Event::ActionUp(Action::Jump)));
+        assert!(events.contains(&Event::ActionUp(Action::MoveLeft)));
+        assert_eq!(events.last(), Some(&Event::FocusLost));
+
+        // The key up never came in. The next press still counts
+        let events = map(&mut action_mapper, &[Event::KeyDown(Keycode::Up)]);
+        assert_eq!(events, vec![Event::ActionDown(Action::Jump)]);
+    }
+}
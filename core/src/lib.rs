@@ -1,13 +1,21 @@
+pub mod clock;
 pub mod color;
 pub mod components;
 pub mod data;
+pub mod debug;
 pub mod ecs;
 pub mod entities;
 pub mod game_loop;
+pub mod gestures;
 pub mod graphics;
+pub mod high_scores;
 pub mod input;
 pub mod jump_physics;
+pub mod key_bindings;
 pub mod rect;
 pub mod render;
+pub mod replay;
 pub mod resources;
+pub mod spawn_planner;
+mod surfaces;
 pub mod systems;
@@ -1,19 +1,157 @@
+use crate::color::Color;
+use crate::components::{Drawable, Hidden, Transform};
+use crate::data::WorldData;
+use crate::debug::DebugOverlay;
 use crate::graphics::data::TileSheet;
+use crate::rect::Rect;
+use specs::join::Join;
+use specs::ReadStorage;
+use std::convert::TryFrom;
 
-// Tiles should be drawn in a particular order to get the correct Z-index cheaply.
-//
-// NOTE: Instead of using tile sheet for ordering, we should instead use the
-// component type for this purpose. It would be a more flexible way to do it
-//
-// Until then: gotta make sure that there is no overlap between these orders.
-// Otherwise, there is no use in doing this at all!
-
-pub const GAME_PLAY_DRAW_ORDER: [TileSheet; 5] = [
-    TileSheet::Cloud,
-    TileSheet::Platform,
-    TileSheet::Enemy,
-    TileSheet::Character,
-    TileSheet::Number,
-];
-
-pub const INSTRUCTIONS_DRAW_ORDER: [TileSheet; 2] = [TileSheet::Letter, TileSheet::Icon];
+/// How whatever is drawn is mixed with what has already been drawn
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Blend {
+    None,
+    Alpha,
+}
+
+/// Everything a frontend has to be able to do to draw the game. Putting it
+/// all together into a frame is left to [draw_frame]. All bounds handed
+/// out are in screen coordinates
+pub trait Renderer {
+    /// Width & height of what is being drawn on
+    fn viewport_size(&self) -> (u32, u32);
+
+    fn set_blend(&mut self, blend: Blend);
+
+    fn clear(&mut self, color: Color);
+
+    fn fill_rect(&mut self, bounds: &Rect, color: Color);
+
+    fn outline_rect(&mut self, bounds: &Rect, color: Color);
+
+    fn draw_tile(&mut self, tile_sheet: TileSheet, bounds_in_tile_sheet: &Rect, bounds: &Rect);
+
+    /// Shows whatever has been drawn since the last time
+    fn present(&mut self);
+}
+
+/// How deep a drawable is drawn. Everything in a layer is drawn over
+/// everything in the layers before it. Doesn't matter which tile sheet a
+/// drawable comes from
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Layer {
+    Clouds,
+    Ground,
+    Pickups,
+
+    // Behind the player & whatever is coming at them, so that both can
+    // still be seen clearly
+    ShieldBubble,
+
+    Enemies,
+    Player,
+    Hud,
+
+    // Instructions (& such) are put up over a backdrop to make them really
+    // obvious against the game going on behind them
+    Instructions,
+}
+
+impl Layer {
+    pub fn is_over_backdrop(&self) -> bool {
+        *self == Layer::Instructions
+    }
+}
+
+/// Everything that isn't hidden in the order it should be drawn in. Order
+/// among drawables in the same layer isn't guaranteed
+pub fn drawables_in_draw_order<'a>(
+    transforms_storage: &'a ReadStorage<Transform>,
+    drawables_storage: &'a ReadStorage<Drawable>,
+    hidden_storage: &ReadStorage<Hidden>,
+) -> Vec<(&'a Transform, &'a Drawable)> {
+    let mut drawables: Vec<(&Transform, &Drawable)> =
+        (transforms_storage, drawables_storage, !hidden_storage)
+            .join()
+            .map(|(transform, drawable, _)| (transform, drawable))
+            .collect();
+
+    drawables.sort_by_key(|(_, drawable)| drawable.layer);
+    drawables
+}
+
+/// Draws everything there is to show for a frame using just what `renderer`
+/// can do
+pub fn draw_frame(
+    renderer: &mut impl Renderer,
+    world_data: &WorldData,
+    transforms_storage: ReadStorage<Transform>,
+    drawables_storage: ReadStorage<Drawable>,
+    hidden_storage: ReadStorage<Hidden>,
+    debug_overlay: &DebugOverlay,
+) {
+    let viewport_size = renderer.viewport_size();
+
+    // No need for alpha compositing for game play drawables
+    renderer.set_blend(Blend::None);
+    renderer.clear(world_data.sky_color());
+
+    // Display game play & whatever is shown over it in one go
+    let mut is_backdrop_drawn = false;
+    for (transform, drawable) in
+        drawables_in_draw_order(&transforms_storage, &drawables_storage, &hidden_storage)
+    {
+        // Instructions go over a different color rect for being really obvious
+        if drawable.layer.is_over_backdrop() && !is_backdrop_drawn {
+            renderer.set_blend(Blend::Alpha);
+            renderer.fill_rect(
+                &world_to_screen_coordinates(&world_data.bounds(), viewport_size),
+                world_data.instructions_bg_color(),
+            );
+
+            is_backdrop_drawn = true
+        }
+
+        draw_drawable(renderer, transform, drawable, viewport_size);
+    }
+
+    // Debug overlay goes over everything else
+    renderer.set_blend(Blend::None);
+    for debug_shape in &debug_overlay.shapes {
+        renderer.outline_rect(
+            &world_to_screen_coordinates(&debug_shape.world_bounds, viewport_size),
+            debug_shape.color,
+        );
+    }
+
+    for (transform, drawable) in &debug_overlay.drawables {
+        draw_drawable(renderer, transform, drawable, viewport_size);
+    }
+
+    renderer.present()
+}
+
+fn draw_drawable(
+    renderer: &mut impl Renderer,
+    transform: &Transform,
+    drawable: &Drawable,
+    viewport_size: (u32, u32),
+) {
+    renderer.draw_tile(
+        drawable.tile_data.tile_sheet,
+        &drawable.tile_data.bounds_in_tile_sheet,
+        &world_to_screen_coordinates(&drawable.world_bounds(transform), viewport_size),
+    )
+}
+
+/// World has its origin at the center where as the screen has it at the top left
+fn world_to_screen_coordinates(world_bounds: &Rect, (width, height): (u32, u32)) -> Rect {
+    let mut screen_coordinates = *world_bounds;
+    screen_coordinates.offset(
+        i32::try_from(width / 2).expect("u32/2 is not i32!"),
+        i32::try_from(height / 2).expect("u32/2 is not i32!"),
+    );
+
+    screen_coordinates
+}
@@ -0,0 +1,331 @@
+use crate::clock::{Clock, ManualClock};
+use crate::data::difficulty::Difficulty;
+use crate::data::{PlayerData, WorldData};
+use crate::input::{Action, Event};
+use crate::resources::EventQueue;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+/// Everything required to play a game exactly the same way again => the
+/// seed for randomness, the difficulty, the number of lives & the input
+/// delivered to game play systems along with the number of ticks each
+/// frame covered.
+///
+/// Why frames & not just ticks? => some systems (like the one spawning
+/// enemies) run once per frame irrespective of the number of ticks the
+/// frame covers. To reproduce a game, frames have to be reproduced too
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Replay {
+    rng_seed: u64,
+
+    // Replays from before there were difficulties to pick from were all
+    // played on what is now the normal one
+    #[serde(default)]
+    difficulty: Difficulty,
+
+    // Same goes for lives. Games used to end with the first hit
+    #[serde(default = "Replay::default_lives")]
+    lives: u8,
+    frames: Vec<ReplayFrame>,
+}
+
+/// `count` consecutive frames, each of which covered `ticks` ticks &
+/// delivered `events`. Only frames without any events are collapsed
+/// together. This keeps replays small since most frames have no input
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct ReplayFrame {
+    ticks: u64,
+    count: u32,
+    events: Vec<Event>,
+}
+
+impl Replay {
+    pub fn new(rng_seed: u64, difficulty: Difficulty, lives: u8) -> Replay {
+        Replay {
+            rng_seed,
+            difficulty,
+            lives,
+            frames: Vec::new(),
+        }
+    }
+
+    fn default_lives() -> u8 {
+        1
+    }
+
+    /// Replays that couldn't have come out of a game (like ones without any
+    /// lives) are turned down
+    pub fn from_ron(text: &str) -> Result<Replay, ReplayError> {
+        let replay: Replay = ron::from_str(text).map_err(ReplayError::Parse)?;
+        if replay.lives == 0 {
+            return Err(ReplayError::NoLives);
+        }
+
+        Ok(replay)
+    }
+
+    pub fn to_ron(&self) -> String {
+        // A depth of 2 puts every frame in its own line
+        ron::ser::to_string_pretty(self, PrettyConfig::new().with_depth_limit(2))
+            .expect("Replay couldn't be serialized")
+    }
+
+    pub fn rng_seed(&self) -> u64 {
+        self.rng_seed
+    }
+
+    pub fn difficulty(&self) -> &Difficulty {
+        &self.difficulty
+    }
+
+    pub fn lives(&self) -> u8 {
+        self.lives
+    }
+
+    /// Total number of ticks covered by all the frames
+    pub fn ticks(&self) -> u64 {
+        self.frames
+            .iter()
+            .map(|frame| frame.ticks * u64::from(frame.count))
+            .sum()
+    }
+
+    /// Should be called once for every frame the game play systems work
+    /// on, after they are done with it
+    pub fn record_frame(&mut self, ticks: u64, event_queue: &EventQueue) {
+        let events: Vec<Event> = event_queue
+            .iter()
+            .filter(|event| Replay::is_replayable(event))
+            .copied()
+            .collect();
+
+        if events.is_empty() {
+            if let Some(last_frame) = self.frames.last_mut() {
+                if last_frame.events.is_empty() && last_frame.ticks == ticks {
+                    last_frame.count += 1;
+                    return;
+                }
+            }
+        }
+
+        self.frames.push(ReplayFrame {
+            ticks,
+            count: 1,
+            events,
+        })
+    }
+
+    /// Only the actions game play systems care about are worth replaying.
+    /// Others (like quitting) are for the game loop to deal with. Keys &
+    /// pointers are already turned into actions by the time frames are
+    /// recorded. So, replays don't depend on the key bindings either
+    fn is_replayable(event: &Event) -> bool {
+        match event {
+            Event::ActionDown(action) | Event::ActionUp(action) => match action {
+                Action::Jump | Action::Slide | Action::MoveLeft | Action::MoveRight => true,
+                Action::Start | Action::Pause | Action::Quit | Action::ToggleDebug => false,
+            },
+            Event::KeyDown(_)
+            | Event::KeyUp(_)
+            | Event::PointerDown(_)
+            | Event::PointerMove(_)
+            | Event::PointerUp(_)
+            | Event::Quit
+            | Event::FocusLost => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Parse(ron::Error),
+    NoLives,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Parse(error) => write!(f, "Replay couldn't be parsed: {}", error),
+            ReplayError::NoLives => write!(f, "Replay has to have at least one life"),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// Feeds a [Replay] to a [crate::game_loop::GameLoop] one frame at a time.
+/// The game loop has to be built with [ReplayPlayer::world_data],
+/// [ReplayPlayer::player_data] & [ReplayPlayer::clock] (& only
+/// [ReplayPlayer::difficulty] to pick from) for the replay to play out
+/// as recorded
+pub struct ReplayPlayer {
+    replay: Replay,
+    clock: ManualClock,
+    frame_index: usize,
+    frame_repeat_index: u32,
+    is_game_play_started: bool,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> ReplayPlayer {
+        ReplayPlayer {
+            replay,
+            clock: ManualClock::new(),
+            frame_index: 0,
+            frame_repeat_index: 0,
+            is_game_play_started: false,
+        }
+    }
+
+    pub fn world_data(&self, world_data: WorldData) -> WorldData {
+        world_data.with_rng_seed(self.replay.rng_seed)
+    }
+
+    pub fn player_data(&self, player_data: PlayerData) -> PlayerData {
+        player_data.with_lives(self.replay.lives)
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.replay.difficulty.clone()
+    }
+
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::new(self.clock.clone())
+    }
+
+    /// Populates the events of the next frame & moves the clock forward by
+    /// as many ticks as that frame covered. Returns `false` once all the
+    /// frames have been played
+    pub fn reset_and_populate(&mut self, event_queue: &mut EventQueue) -> bool {
+        event_queue.reset();
+
+        let frame = match self.replay.frames.get(self.frame_index) {
+            None => return false,
+            Some(frame) => frame,
+        };
+
+        // Recording starts with the frame the game play started in. Make
+        // sure that the game play starts in that frame during replay too
+        if !self.is_game_play_started {
+            self.is_game_play_started = true;
+            event_queue.add_event(Event::ActionDown(Action::Start));
+        }
+
+        for event in frame.events.iter() {
+            event_queue.add_event(*event)
+        }
+
+        self.clock.advance_by_ticks(frame.ticks);
+        self.frame_repeat_index += 1;
+        if self.frame_repeat_index >= frame.count {
+            self.frame_index += 1;
+            self.frame_repeat_index = 0;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_loop::{GameLoop, GameLoopResult};
+    use crate::graphics::tile_atlas::TileAtlas;
+    use crate::high_scores::HighScores;
+    use crate::key_bindings::KeyBindings;
+
+    fn game_loop<'a, 'b>(
+        world_data: WorldData,
+        player_data: PlayerData,
+        clock: Arc<dyn Clock>,
+    ) -> GameLoop<'a, 'b> {
+        GameLoop::new_with_clock(
+            world_data,
+            TileAtlas::from_ron(include_str!("../../assets_processed/tile_atlas.ron")).unwrap(),
+            HighScores::new(),
+            KeyBindings::default(),
+            clock,
+        )
+        .with_player_data(player_data)
+    }
+
+    /// Score, ticks animated & whether the game is over
+    fn outcome(game_loop: &GameLoop) -> (u64, u64, bool) {
+        let game_play = game_loop.game_play();
+        (
+            game_play.score(),
+            game_play.ticks_animated(),
+            game_play.is_over(),
+        )
+    }
+
+    #[test]
+    fn replay_plays_out_as_recorded() {
+        // Frames covering different number of ticks & some presses along
+        // the way. Jumps every now & then keep the game going for a while
+        let clock = ManualClock::new();
+        let player_data = PlayerData::new().with_lives(2);
+        let mut recorded = game_loop(
+            WorldData::new().with_rng_seed(7),
+            player_data,
+            Arc::new(clock.clone()),
+        );
+
+        recorded
+            .event_queue()
+            .add_event(Event::ActionDown(Action::Start));
+        for frame in 1..2_000 {
+            {
+                let mut event_queue = recorded.event_queue();
+                match frame % 50 {
+                    0 => event_queue.add_event(Event::ActionDown(Action::Jump)),
+                    20 => event_queue.add_event(Event::ActionUp(Action::Jump)),
+                    30 => event_queue.add_event(Event::ActionDown(Action::MoveRight)),
+                    35 => event_queue.add_event(Event::ActionUp(Action::MoveRight)),
+                    _ => {}
+                }
+            }
+
+            let game_loop_result = recorded.execute();
+            recorded.event_queue().reset();
+            clock.advance_by_ticks(1 + frame % 3);
+            if let GameLoopResult::GameOver = game_loop_result {
+                break;
+            }
+        }
+
+        // Play it back after it has been through being saved & loaded
+        let replay = Replay::from_ron(&recorded.replay().to_ron()).unwrap();
+        assert_eq!(replay.rng_seed(), 7);
+        assert_eq!(replay.lives(), 2);
+
+        let mut replay_player = ReplayPlayer::new(replay);
+        let mut replayed = game_loop(
+            replay_player.world_data(WorldData::new()),
+            replay_player.player_data(PlayerData::new()),
+            replay_player.clock(),
+        )
+        .with_difficulties(vec![replay_player.difficulty()]);
+
+        while replay_player.reset_and_populate(&mut replayed.event_queue()) {
+            if let GameLoopResult::GameOver = replayed.execute() {
+                break;
+            }
+        }
+
+        assert!(outcome(&recorded).0 > 0);
+        assert_eq!(outcome(&replayed), outcome(&recorded));
+    }
+
+    #[test]
+    fn replay_without_lives_is_turned_down() {
+        let replay = Replay::new(7, Difficulty::normal(), 0);
+        match Replay::from_ron(&replay.to_ron()) {
+            Err(ReplayError::NoLives) => {}
+            _ => panic!("Replay without lives should have been turned down"),
+        }
+    }
+}
@@ -1,5 +1,9 @@
 pub mod event_queue;
 pub mod game_play;
+pub mod game_rng;
+pub mod ground_laid_out;
 
 pub use event_queue::EventQueue;
 pub use game_play::GamePlay;
+pub use game_rng::GameRng;
+pub use ground_laid_out::GroundLaidOut;
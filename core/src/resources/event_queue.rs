@@ -19,6 +19,16 @@ impl EventQueue {
         self.queue.clear()
     }
 
+    /// Empties the queue while handing out the events in it
+    pub fn drain(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.queue.drain(..)
+    }
+
+    /// Keeps only the events `predicate` returns `true` for
+    pub fn retain(&mut self, predicate: impl FnMut(&Event) -> bool) {
+        self.queue.retain(predicate)
+    }
+
     pub fn add_event(&mut self, event: Event) {
         self.queue.push_back(event)
     }
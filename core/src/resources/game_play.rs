@@ -1,4 +1,5 @@
-use instant::Instant;
+use crate::clock::Clock;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Changing this number could affect how the simulation is run. For
@@ -7,6 +8,9 @@ use std::time::Duration;
 /// to be updated accordingly too
 pub const MILLISECONDS_IN_A_TICK: u8 = 16;
 
+/// Score goes up by one for every these many ticks the player survives
+const TICKS_IN_A_SCORE_POINT: u64 = 12;
+
 /// To track data about the ticks we have dealt or should deal with.
 ///
 /// What is a tick? => this is how granular our systems can get. To
@@ -16,6 +20,7 @@ pub const MILLISECONDS_IN_A_TICK: u8 = 16;
 pub struct GamePlay {
     /// Flags to control whether game play can take place or not
     is_started: bool,
+    is_paused: bool,
     is_over: bool,
 
     /// Number of ticks animated by our systems since the start of
@@ -28,19 +33,29 @@ pub struct GamePlay {
     /// Note: we don't enforce this in any way. This is a contract
     /// that has to held
     ticks_to_animate: u64,
-    last_ticks_to_animate_update_at: Instant,
+    last_ticks_to_animate_update_at: Duration,
+
+    /// Points picked up along the way (for eg., from coins). These are
+    /// on top of the points for the distance covered
+    collected_points: u64,
+
+    /// Ticks are derived from the time elapsed on this clock
+    clock: Arc<dyn Clock>,
 }
 
 impl GamePlay {
-    pub fn new() -> GamePlay {
+    pub fn new(clock: Arc<dyn Clock>) -> GamePlay {
         GamePlay {
             is_started: false,
+            is_paused: false,
             is_over: false,
             ticks_animated: 0,
             ticks_to_animate: 0,
+            collected_points: 0,
 
             // This will be overwritten when the play actually starts
-            last_ticks_to_animate_update_at: Instant::now(),
+            last_ticks_to_animate_update_at: clock.now(),
+            clock,
         }
     }
 
@@ -48,12 +63,16 @@ impl GamePlay {
         self.is_started
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
     pub fn is_over(&self) -> bool {
         self.is_over
     }
 
     pub fn is_allowed(&self) -> bool {
-        self.is_started && !self.is_over
+        self.is_started && !self.is_paused && !self.is_over
     }
 
     pub fn ticks_animated(&self) -> u64 {
@@ -64,6 +83,15 @@ impl GamePlay {
         self.ticks_to_animate
     }
 
+    /// Distance covered & points collected
+    pub fn score(&self) -> u64 {
+        self.ticks_animated / TICKS_IN_A_SCORE_POINT + self.collected_points
+    }
+
+    pub fn add_points(&mut self, points: u64) {
+        self.collected_points += points
+    }
+
     pub fn ticked(&self) -> bool {
         self.ticks_to_animate > 0
     }
@@ -74,7 +102,20 @@ impl GamePlay {
         // The play is only marked now as started. Start computing
         // the ticks relative to this time. Tried making this prop
         // an `Option` but that didn't make the code any easier!
-        self.last_ticks_to_animate_update_at = Instant::now();
+        self.last_ticks_to_animate_update_at = self.clock.now();
+    }
+
+    pub fn mark_paused(&mut self) {
+        self.is_paused = true
+    }
+
+    pub fn mark_resumed(&mut self) {
+        self.is_paused = false;
+
+        // Time spent paused shouldn't be animated. If we don't move this
+        // forward, all of that time would turn into ticks to animate in
+        // one big burst right after resuming
+        self.last_ticks_to_animate_update_at = self.clock.now();
     }
 
     pub fn mark_over(&mut self) {
@@ -92,10 +133,15 @@ impl GamePlay {
 
             // We want to make sure we advance our physics in deterministic steps
             // all the time to be hardware independent
-            let mut ms_elapsed = self.last_ticks_to_animate_update_at.elapsed().as_millis();
+            let mut ms_elapsed =
+                (self.clock.now() - self.last_ticks_to_animate_update_at).as_millis();
 
+            // A tick is due as soon as its worth of time has elapsed. With `>`,
+            // a clock moved forward by exactly n ticks (for eg., a `ManualClock`)
+            // would only be animated by n - 1 ticks. Since the leftover time is
+            // carried over, the wall-clock sees no difference in the long run
             let mut ticks_to_animate = 0u64;
-            while ms_elapsed > MILLISECONDS_IN_A_TICK.into() {
+            while ms_elapsed >= MILLISECONDS_IN_A_TICK.into() {
                 ticks_to_animate += 1;
                 ms_elapsed -= u128::from(MILLISECONDS_IN_A_TICK)
             }
@@ -108,3 +154,108 @@ impl GamePlay {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    fn started_game_play(clock: &ManualClock) -> GamePlay {
+        let mut game_play = GamePlay::new(Arc::new(clock.clone()));
+        game_play.mark_started();
+        game_play
+    }
+
+    #[test]
+    fn tick_is_due_once_its_worth_of_time_has_elapsed() {
+        let clock = ManualClock::new();
+        let mut game_play = started_game_play(&clock);
+
+        clock.advance(Duration::from_millis(u64::from(MILLISECONDS_IN_A_TICK) - 1));
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 0);
+
+        // Exactly a tick's worth
+        clock.advance(Duration::from_millis(1));
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 1);
+    }
+
+    #[test]
+    fn clock_moved_by_ticks_animates_as_many_ticks() {
+        let clock = ManualClock::new();
+        let mut game_play = started_game_play(&clock);
+
+        clock.advance_by_ticks(3);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 3);
+
+        // Nothing left over to carry into the next one
+        clock.advance_by_ticks(1);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_animated(), 3);
+        assert_eq!(game_play.ticks_to_animate(), 1);
+    }
+
+    #[test]
+    fn leftover_time_is_carried_over() {
+        let clock = ManualClock::new();
+        let mut game_play = started_game_play(&clock);
+
+        let half_a_tick = Duration::from_millis(u64::from(MILLISECONDS_IN_A_TICK / 2));
+        clock.advance_by_ticks(1);
+        clock.advance(half_a_tick);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 1);
+
+        clock.advance(half_a_tick);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 1);
+    }
+
+    #[test]
+    fn time_spent_paused_is_not_caught_up_on() {
+        let clock = ManualClock::new();
+        let mut game_play = started_game_play(&clock);
+
+        clock.advance_by_ticks(2);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 2);
+
+        game_play.mark_paused();
+        clock.advance_by_ticks(100);
+        game_play.update_if_allowed();
+        assert!(!game_play.is_allowed());
+        assert_eq!(game_play.ticks_animated(), 0);
+
+        // Right after resuming, nothing is due. Only the ticks that come
+        // after are animated
+        game_play.mark_resumed();
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_animated(), 2);
+        assert_eq!(game_play.ticks_to_animate(), 0);
+
+        clock.advance_by_ticks(1);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_animated(), 2);
+        assert_eq!(game_play.ticks_to_animate(), 1);
+    }
+
+    #[test]
+    fn time_before_start_is_not_animated() {
+        let clock = ManualClock::new();
+        let mut game_play = GamePlay::new(Arc::new(clock.clone()));
+
+        clock.advance_by_ticks(50);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 0);
+
+        game_play.mark_started();
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 0);
+
+        clock.advance_by_ticks(1);
+        game_play.update_if_allowed();
+        assert_eq!(game_play.ticks_to_animate(), 1);
+    }
+}
@@ -0,0 +1,34 @@
+use rand::distributions::uniform::SampleUniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Every random decision in game play should be drawn from here. Given
+/// the same seed & the same input, a game plays out exactly the same way
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> GameRng {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seed to use when the game doesn't ask for a particular one
+    pub fn random_seed() -> u64 {
+        rand::random()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Same as [rand::Rng::gen_range] => `low` is inclusive & `high` is
+    /// exclusive. Panics if `low >= high`
+    pub fn gen_range<T: SampleUniform>(&mut self, low: T, high: T) -> T {
+        self.rng.gen_range(low, high)
+    }
+}
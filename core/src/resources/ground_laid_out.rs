@@ -0,0 +1,8 @@
+/// Where the ground that has been laid out so far ends (check out
+/// [crate::systems::GroundSystem]). Whole stretches of terrain are laid out
+/// at once. So, this is usually past the right edge of the world. Nothing
+/// after it has been picked yet
+#[derive(Copy, Clone, Debug)]
+pub struct GroundLaidOut {
+    pub till: i32,
+}
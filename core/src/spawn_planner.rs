@@ -0,0 +1,708 @@
+//! Plans go through the very same per-tick steps the game does to move,
+//! animate & hit things (`MovementSystem::advance`, `AnimationSystem::animate`,
+//! `EnemySystem::advance` & `PlayerSystem::update`). These are shared instead
+//! of copied over so that plans stay true to the game. Speed ups that are
+//! due while a plan plays out are gone through too (check out [GroundSpeeds])
+
+use crate::components::input::data::Direction;
+use crate::components::input::InputControlled;
+use crate::components::player::data::Action;
+use crate::components::player::Player;
+use crate::components::{Animatable, Drawable, Enemy, Transform, Velocity};
+use crate::data::{GroundData, GroundSpeeds, PlayerData, WorldData};
+use crate::graphics::data::Tile;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::rect::Rect;
+use crate::surfaces::Surfaces;
+use crate::systems::{AnimationSystem, EnemySystem, MovementSystem, PlayerSystem, PlayerTick};
+use std::collections::HashSet;
+
+// Everything on screen is long gone by then. Just a safety net to make
+// sure planning comes to an end
+pub(crate) const MAX_TICKS_TO_PLAN: u64 = 2_000;
+
+// Planning runs in the middle of a frame. Searches that go on for longer
+// than this many steps (a tick of a move that is tried is a step) are given
+// up on, as if there was no way through
+const MAX_STEPS_TO_SEARCH: u32 = 50_000;
+
+// Wide enough to stand in for all the ground that is yet to be created
+const UNKNOWN_TERRAIN_WIDTH_IN_WC: u32 = 1_000_000;
+
+/// What should be engaged in a tick to stick to a plan
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PlannedInput {
+    pub jump: bool,
+    pub slide: bool,
+}
+
+/// Everything that stands between the player & getting through, as it was
+/// at the end of the tick before `tick`
+#[derive(Clone)]
+pub struct Snapshot {
+    tick: u64,
+    player: Player,
+    player_animatable: Animatable,
+    player_transform: Transform,
+    player_drawable: Drawable,
+    input_ctrl: InputControlled,
+    enemies: Vec<(Enemy, Animatable, Transform, Velocity, Drawable)>,
+    surfaces: Vec<Rect>,
+
+    // All the ground moves together. So, instead of moving every surface,
+    // this is moved & the surfaces are kept as far from it as they were
+    // from (0, 0). Check out [Transform::origin]
+    ground_origin: Transform,
+
+    // Ground from here on hasn't been created yet. Anything could come
+    // up later. Planning takes it to be flat for now
+    terrain_known_till: i32,
+
+    // Whatever is engaged right now is what the player went with for `tick`
+    is_input_given: bool,
+
+    // Only ways through that pick this up on the way count
+    pickup: Option<(Transform, Drawable)>,
+}
+
+impl Snapshot {
+    pub(crate) fn new<'a>(
+        tick: u64,
+        (player, player_animatable, player_transform, player_drawable, input_ctrl): (
+            &Player,
+            &Animatable,
+            &Transform,
+            &Drawable,
+            &InputControlled,
+        ),
+        enemies: impl Iterator<
+            Item = (
+                &'a Enemy,
+                &'a Animatable,
+                &'a Transform,
+                &'a Velocity,
+                &'a Drawable,
+            ),
+        >,
+        ground: impl Iterator<Item = (&'a Transform, &'a Drawable)>,
+        terrain_known_till: i32,
+    ) -> Snapshot {
+        let ground: Vec<_> = ground.collect();
+        Snapshot {
+            tick,
+            player: player.clone(),
+            player_animatable: *player_animatable,
+            player_transform: *player_transform,
+            player_drawable: *player_drawable,
+            input_ctrl: input_ctrl.clone(),
+            enemies: enemies
+                .map(|(enemy, animatable, transform, velocity, drawable)| {
+                    (*enemy, *animatable, *transform, *velocity, *drawable)
+                })
+                .collect(),
+            surfaces: Surfaces::bounds_of(ground.iter().copied()),
+            ground_origin: ground
+                .first()
+                .map(|(transform, _)| transform.origin())
+                .unwrap_or_default(),
+            terrain_known_till,
+            is_input_given: false,
+            pickup: None,
+        }
+    }
+
+    /// As if `enemy` (check out [crate::entities::EnemyEntity::build]) was spawned
+    pub(crate) fn with_enemy(
+        mut self,
+        enemy: (Enemy, Animatable, Transform, Velocity, Drawable),
+    ) -> Snapshot {
+        self.enemies.push(enemy);
+        self
+    }
+
+    /// As if a pickup (check out [crate::entities::PickupEntity::build]) was
+    /// dropped. There has to be a way through that picks it up on the way
+    pub(crate) fn with_pickup(mut self, pickup: (Transform, Drawable)) -> Snapshot {
+        self.pickup = Some(pickup);
+        self
+    }
+
+    /// As if ground with `surfaces` was added right after what is already known
+    pub(crate) fn with_terrain(mut self, surfaces: Vec<Rect>, terrain_known_till: i32) -> Snapshot {
+        self.surfaces.extend(surfaces);
+        self.terrain_known_till = terrain_known_till;
+        self
+    }
+
+    /// Input for the snapshot's tick has already come in (check out
+    /// [crate::systems::EventSystem]). So, it is stuck to instead of being
+    /// planned for
+    pub(crate) fn with_input_given(mut self) -> Snapshot {
+        self.is_input_given = true;
+        self
+    }
+
+    /// Ground & enemies are moved back by the last `ticks` they were moved
+    /// for (check out [MovementSystem]). For eg., when they have already been
+    /// moved for a few more ticks than the rest
+    pub(crate) fn with_movement_rewound(
+        mut self,
+        ground_data: &GroundData,
+        ticks: u64,
+    ) -> Snapshot {
+        self.ground_origin
+            .move_right(ground_data.speed_in_wc_per_tick, ticks);
+
+        let player_left = Some(self.player_transform.left());
+        for (_, _, transform, velocity, _) in self.enemies.iter_mut() {
+            for _ in 0..ticks {
+                let distance_to_player = MovementSystem::distance_to_player(player_left, transform);
+                transform.move_right(
+                    velocity.speed_in_wc_per_tick(ground_data, distance_to_player),
+                    1,
+                );
+            }
+        }
+
+        self
+    }
+}
+
+/// Works out if there is a way for the player to get through everything
+/// that is coming their way. Goes through the same code the game does to
+/// move the player & the enemies a tick at a time. So, a plan that works
+/// here works in the game too.
+///
+/// Player stays where they are (no moving left or right) & only the simplest
+/// moves are tried => running, full jumps, short hops & full slides. Some
+/// situations that can be got through with fancier moves may be called out
+/// as impossible. That's fine since this is used to turn down spawns
+pub struct SpawnPlanner {
+    world_data: WorldData,
+    tile_atlas: TileAtlas,
+    player_system: PlayerSystem,
+}
+
+impl SpawnPlanner {
+    pub fn new(world_data: WorldData, tile_atlas: TileAtlas) -> SpawnPlanner {
+        SpawnPlanner {
+            world_data,
+            tile_atlas: tile_atlas.clone(),
+            player_system: PlayerSystem::new(world_data, tile_atlas),
+        }
+    }
+
+    pub fn can_get_through(
+        &self,
+        snapshot: &Snapshot,
+        player_data: &PlayerData,
+        ground_speeds: &GroundSpeeds,
+    ) -> bool {
+        self.find_way_through(snapshot, player_data, ground_speeds)
+            .is_some()
+    }
+
+    /// Input for every tick starting at the snapshot's tick, till everything
+    /// that is known about has gone past the player. [None] if there is no
+    /// way through
+    pub fn find_way_through(
+        &self,
+        snapshot: &Snapshot,
+        player_data: &PlayerData,
+        ground_speeds: &GroundSpeeds,
+    ) -> Option<Vec<PlannedInput>> {
+        let obstacles = self.compute_obstacles(snapshot, ground_speeds);
+
+        let mut surfaces = snapshot.surfaces.clone();
+        surfaces.push(Rect::new(
+            snapshot.terrain_known_till,
+            self.world_data.world_surface_at(),
+            UNKNOWN_TERRAIN_WIDTH_IN_WC,
+            UNKNOWN_TERRAIN_WIDTH_IN_WC,
+        ));
+
+        let mut search = Search {
+            planner: self,
+            player_data,
+            start_tick: snapshot.tick,
+            given_input: if snapshot.is_input_given {
+                Some(PlannedInput {
+                    jump: snapshot.input_ctrl.up_engaged(),
+                    slide: snapshot.input_ctrl.down_engaged(),
+                })
+            } else {
+                None
+            },
+            surfaces,
+            obstacles,
+            pickup_drawable: snapshot.pickup.map(|(_, drawable)| drawable),
+            dead_ends: HashSet::new(),
+            steps_left: MAX_STEPS_TO_SEARCH,
+        };
+
+        let state = PlayerState {
+            player: snapshot.player.clone(),
+            animatable: snapshot.player_animatable,
+            transform: snapshot.player_transform,
+            drawable: snapshot.player_drawable,
+            input_ctrl: snapshot.input_ctrl.clone(),
+            has_picked_up: false,
+        };
+
+        // `up` can only be held on to through a jump that is already going
+        // on if it is engaged right now. Pressing it again mid-air would
+        // queue up another jump
+        let can_hold_jump = snapshot.input_ctrl.up_engaged();
+        search.run(state, can_hold_jump)
+    }
+
+    /// Hitboxes of the enemies (& where the ground & the pickup are) for every
+    /// tick till all of them (& all the known terrain) have gone past the player
+    fn compute_obstacles(&self, snapshot: &Snapshot, ground_speeds: &GroundSpeeds) -> Obstacles {
+        let player_left = snapshot.player_transform.left();
+        let mut enemies = snapshot.enemies.clone();
+        let mut ground_origin = snapshot.ground_origin;
+        let mut pickup = snapshot.pickup;
+        let mut obstacles = Obstacles {
+            hitboxes: Vec::new(),
+            ends: Vec::new(),
+            ground_lefts: Vec::new(),
+            pickup_transforms: Vec::new(),
+        };
+
+        for current_tick in snapshot.tick..snapshot.tick + MAX_TICKS_TO_PLAN {
+            let ground_data = ground_speeds.at(current_tick);
+            ground_origin.move_left(ground_data.speed_in_wc_per_tick, 1);
+
+            let is_terrain_past = snapshot.terrain_known_till + ground_origin.left() <= player_left;
+
+            let is_every_enemy_past = enemies.iter().all(|(_, _, transform, _, drawable)| {
+                drawable.world_bounds(transform).right() < player_left
+            });
+
+            let is_pickup_past = match pickup {
+                None => true,
+                Some((transform, drawable)) => {
+                    drawable.world_bounds(&transform).right() < player_left
+                }
+            };
+
+            if is_terrain_past && is_every_enemy_past && is_pickup_past {
+                break;
+            }
+
+            // Same as the game. Everything is moved first & enemies that move
+            // out are let go. Enemies are animated & get to do their thing
+            // after that
+            for (_, _, transform, velocity, _) in enemies.iter_mut() {
+                MovementSystem::advance(1, &ground_data, Some(player_left), velocity, transform);
+            }
+
+            enemies.retain(|(_, _, transform, _, drawable)| {
+                !MovementSystem::has_moved_out(&self.world_data, transform, drawable)
+            });
+
+            if let Some((transform, _)) = pickup.as_mut() {
+                MovementSystem::advance(
+                    1,
+                    &ground_data,
+                    Some(player_left),
+                    &Velocity::Ground,
+                    transform,
+                );
+
+                obstacles.pickup_transforms.push(*transform);
+            }
+
+            for (enemy, animatable, transform, _, drawable) in enemies.iter_mut() {
+                AnimationSystem::animate(
+                    &self.tile_atlas,
+                    current_tick,
+                    animatable,
+                    transform,
+                    drawable,
+                );
+
+                EnemySystem::advance(current_tick, enemy, transform, drawable);
+                obstacles
+                    .hitboxes
+                    .extend(drawable.world_hitboxes(transform, &self.tile_atlas));
+            }
+
+            obstacles.ends.push(obstacles.hitboxes.len());
+            obstacles.ground_lefts.push(ground_origin.left());
+        }
+
+        obstacles
+    }
+}
+
+/// Hitboxes of the enemies, tick after tick. All of them are kept in one
+/// buffer (instead of a `Vec` for every tick) as there are lots of ticks
+struct Obstacles {
+    hitboxes: Vec<Rect>,
+
+    // Where the hitboxes of each tick end in `hitboxes`
+    ends: Vec<usize>,
+
+    // Where the ground has moved to by the end of each tick. The speed it
+    // moves at may change along the way
+    ground_lefts: Vec<i32>,
+
+    // Where the pickup (if there is one) has moved to by the end of each tick
+    pickup_transforms: Vec<Transform>,
+}
+
+impl Obstacles {
+    fn at(&self, index: usize) -> &[Rect] {
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        &self.hitboxes[start..self.ends[index]]
+    }
+}
+
+#[derive(Clone)]
+struct PlayerState {
+    player: Player,
+    animatable: Animatable,
+    transform: Transform,
+    drawable: Drawable,
+    input_ctrl: InputControlled,
+    has_picked_up: bool,
+}
+
+/// A way to go on from a tick => what to engage & whether to hold on to
+/// `up` through the jump that follows (if any)
+#[derive(Copy, Clone)]
+struct Move {
+    input: PlannedInput,
+    hold_jump: bool,
+
+    // If the move was made at all. For eg., a jump right after another
+    // is the same as running. [None] => there is nothing to check
+    expected_action: Option<Action>,
+}
+
+impl Move {
+    const fn new(
+        jump: bool,
+        slide: bool,
+        hold_jump: bool,
+        expected_action: Option<Action>,
+    ) -> Move {
+        Move {
+            input: PlannedInput { jump, slide },
+            hold_jump,
+            expected_action,
+        }
+    }
+}
+
+// Keep running (which is what it comes down to most of the time), a full
+// jump, a short hop or a slide
+const MOVES_WHILE_RUNNING: [Move; 4] = [
+    Move::new(false, false, false, None),
+    Move::new(true, false, true, Some(Action::Jump)),
+    Move::new(true, false, false, Some(Action::Jump)),
+    Move::new(false, true, false, Some(Action::Slide)),
+];
+
+// Nothing to decide till a jump or a slide is over
+const MOVES_WHILE_HOLDING_JUMP: [Move; 1] = [Move::new(true, false, true, None)];
+const MOVES_WHILE_JUMPING: [Move; 1] = [Move::new(false, false, false, None)];
+const MOVES_WHILE_SLIDING: [Move; 1] = [Move::new(false, true, false, None)];
+
+// A jump that is already going on when planning starts can either be held
+// on to or let go of
+const MOVES_AT_START_OF_JUMP: [Move; 2] = [MOVES_WHILE_HOLDING_JUMP[0], MOVES_WHILE_JUMPING[0]];
+
+/// Where the search is at in a tick => the player as they were at the start
+/// of the tick & the moves that are left to be tried from there
+struct SearchFrame {
+    state: PlayerState,
+    moves: &'static [Move],
+    next_move_index: usize,
+
+    // Only ticks that have a choice to make are remembered as dead ends
+    key: Option<StateKey>,
+}
+
+/// Depth first search through the moves the player can make. Once it is known
+/// that there is no way through from a particular state, it isn't tried again.
+/// Searches can go a couple of thousand ticks deep. So, ticks are kept in a
+/// stack of their own instead of recursing
+struct Search<'a> {
+    planner: &'a SpawnPlanner,
+    player_data: &'a PlayerData,
+    start_tick: u64,
+    given_input: Option<PlannedInput>,
+    surfaces: Vec<Rect>,
+    obstacles: Obstacles,
+    pickup_drawable: Option<Drawable>,
+    dead_ends: HashSet<StateKey>,
+    steps_left: u32,
+}
+
+/// Only what could make a difference to what happens next
+#[derive(Eq, Hash, PartialEq)]
+struct StateKey {
+    tick: u64,
+    bounds: Rect,
+    tile: Tile,
+    frame_started_at_tick: u64,
+    jump_ends_to_wait_out: u64,
+    slide_ends_to_wait_out: u64,
+    jump_pressed_at: Option<u64>,
+    invulnerable_until: u64,
+
+    // Holding on to `up` after landing means that it has to be let go of
+    // before the next jump
+    was_up_engaged: bool,
+    is_up_engaged: bool,
+    is_down_engaged: bool,
+    has_picked_up: bool,
+}
+
+impl<'a> Search<'a> {
+    /// Input for every tick from the start till everything has gone past.
+    /// [None] if there is no way through (or if it is taking too long to
+    /// find one)
+    fn run(&mut self, state: PlayerState, can_hold_jump: bool) -> Option<Vec<PlannedInput>> {
+        if self.is_past_everything(self.start_tick) {
+            return if self.is_safe_at_the_end(&state) {
+                Some(Vec::new())
+            } else {
+                None
+            };
+        }
+
+        let moves: &'static [Move] = match state.player.current_action {
+            Action::Jump if can_hold_jump => &MOVES_AT_START_OF_JUMP,
+            _ => self.moves_from(&state, false),
+        };
+
+        let mut inputs = Vec::new();
+        let mut frames = vec![SearchFrame {
+            key: self.key_if_choosing(self.start_tick, &state),
+            state,
+            moves,
+            next_move_index: 0,
+        }];
+
+        while let Some(frame) = frames.last_mut() {
+            let current_tick = self.start_tick + inputs.len() as u64;
+            let next_move = frame.moves.get(frame.next_move_index).copied();
+            frame.next_move_index += 1;
+
+            // Every move from here has been tried. Back to the tick before
+            let next_move = match next_move {
+                Some(next_move) => next_move,
+                None => {
+                    if let Some(key) = frames.pop().and_then(|frame| frame.key) {
+                        self.dead_ends.insert(key);
+                    }
+
+                    inputs.pop();
+                    continue;
+                }
+            };
+
+            if self.steps_left == 0 {
+                return None;
+            }
+
+            self.steps_left -= 1;
+            let input = self.input_at(current_tick, next_move.input);
+            let mut next_state = frame.state.clone();
+            if !self.step(current_tick, &mut next_state, input) {
+                continue;
+            }
+
+            // Too soon after the last one. Same as running then
+            let is_move_made = match next_move.expected_action {
+                None => true,
+                Some(action) => next_state.player.current_action == action,
+            };
+
+            if !is_move_made {
+                continue;
+            }
+
+            // Everything has gone past by now. Only flat ground is left (as
+            // far as anyone knows) to land on
+            let next_tick = current_tick + 1;
+            if self.is_past_everything(next_tick) {
+                if self.is_safe_at_the_end(&next_state) {
+                    inputs.push(input);
+                    return Some(inputs);
+                }
+
+                continue;
+            }
+
+            // Been here before & there was no way through from here
+            let key = self.key_if_choosing(next_tick, &next_state);
+            if let Some(key) = &key {
+                if self.dead_ends.contains(key) {
+                    continue;
+                }
+            }
+
+            // Once let go of, `up` can't be held on to anymore. Pressing it
+            // again mid-air would queue up another jump instead
+            let hold_jump = next_move.hold_jump && input.jump;
+            inputs.push(input);
+            frames.push(SearchFrame {
+                moves: self.moves_from(&next_state, hold_jump),
+                state: next_state,
+                next_move_index: 0,
+                key,
+            });
+        }
+
+        None
+    }
+
+    fn moves_from(&self, state: &PlayerState, hold_jump: bool) -> &'static [Move] {
+        match state.player.current_action {
+            Action::Jump if hold_jump => &MOVES_WHILE_HOLDING_JUMP,
+            Action::Jump => &MOVES_WHILE_JUMPING,
+            Action::Slide => &MOVES_WHILE_SLIDING,
+            Action::Run | Action::Still => &MOVES_WHILE_RUNNING,
+        }
+    }
+
+    fn key_if_choosing(&self, current_tick: u64, state: &PlayerState) -> Option<StateKey> {
+        match state.player.current_action {
+            Action::Jump | Action::Slide => None,
+            Action::Run | Action::Still => Some(self.state_key(current_tick, state)),
+        }
+    }
+
+    fn is_past_everything(&self, current_tick: u64) -> bool {
+        current_tick >= self.start_tick + self.obstacles.ends.len() as u64
+    }
+
+    /// Unless the player is already on their way down a pit (or has left
+    /// the pickup behind)
+    fn is_safe_at_the_end(&self, state: &PlayerState) -> bool {
+        let bottom = state.drawable.world_bounds(&state.transform).bottom();
+        bottom <= self.planner.world_data.world_surface_at()
+            && (self.pickup_drawable.is_none() || state.has_picked_up)
+    }
+
+    /// `input` unless something else has already been engaged for `current_tick`
+    fn input_at(&self, current_tick: u64, input: PlannedInput) -> PlannedInput {
+        match self.given_input {
+            Some(given_input) if current_tick == self.start_tick => given_input,
+            _ => input,
+        }
+    }
+
+    /// Returns [false] if the player doesn't make it through the tick
+    fn step(&self, current_tick: u64, state: &mut PlayerState, input: PlannedInput) -> bool {
+        if input.jump {
+            state.input_ctrl.update_key_down(Direction::Up)
+        } else {
+            state.input_ctrl.update_key_up(Direction::Up)
+        }
+
+        if input.slide {
+            state.input_ctrl.update_key_down(Direction::Down)
+        } else {
+            state.input_ctrl.update_key_up(Direction::Down)
+        }
+
+        // Same as the player system. Falling into a pit is the end
+        if state.transform.top() >= self.planner.world_data.world_surface_at() {
+            return false;
+        }
+
+        let index = (current_tick - self.start_tick) as usize;
+        let surfaces = Surfaces {
+            bounds: &self.surfaces,
+            x_offset: self.obstacles.ground_lefts[index],
+        };
+
+        let tick = PlayerTick {
+            current_tick,
+            player_data: self.player_data,
+            surfaces: &surfaces,
+            input_ctrl: &state.input_ctrl,
+        };
+
+        self.planner.player_system.update(
+            &tick,
+            &mut state.animatable,
+            &mut state.transform,
+            &mut state.drawable,
+            &mut state.player,
+        );
+
+        AnimationSystem::animate(
+            &self.planner.tile_atlas,
+            current_tick,
+            &mut state.animatable,
+            &mut state.transform,
+            &mut state.drawable,
+        );
+
+        // Same as the collision system. Pickups are up for grabs even when
+        // hits don't count. Once it has gone past, there is no going back
+        // for it
+        if let Some(pickup_drawable) = &self.pickup_drawable {
+            if !state.has_picked_up {
+                let pickup_transform = &self.obstacles.pickup_transforms[index];
+                let pickup_hitboxes: Vec<Rect> = pickup_drawable
+                    .world_hitboxes(pickup_transform, &self.planner.tile_atlas)
+                    .collect();
+
+                state.has_picked_up = self.is_touching(state, &pickup_hitboxes);
+                if !state.has_picked_up
+                    && pickup_drawable.world_bounds(pickup_transform).right()
+                        < state.transform.left()
+                {
+                    return false;
+                }
+            }
+        }
+
+        // Hits while recovering from an earlier one don't count. Shields
+        // aren't counted on since they may not last till then
+        if state.player.is_invulnerable(current_tick) {
+            return true;
+        }
+
+        !self.is_touching(state, self.obstacles.at(index))
+    }
+
+    fn is_touching(&self, state: &PlayerState, others: &[Rect]) -> bool {
+        state
+            .drawable
+            .world_hitboxes(&state.transform, &self.planner.tile_atlas)
+            .any(|hitbox| others.iter().any(|other| Rect::intersects(&hitbox, other)))
+    }
+
+    fn state_key(&self, current_tick: u64, state: &PlayerState) -> StateKey {
+        let player = &state.player;
+        let ticks_since_jump = current_tick.saturating_sub(player.most_recent_max_jump_end_at);
+        let ticks_since_slide = current_tick.saturating_sub(player.most_recent_max_slide_end_at);
+        StateKey {
+            tick: current_tick,
+            bounds: state.drawable.world_bounds(&state.transform),
+            tile: state.drawable.tile_data.tile,
+            frame_started_at_tick: state.animatable.frame_started_at_tick,
+            jump_ends_to_wait_out: u64::from(self.player_data.ticks_between_consecutive_jumps)
+                .saturating_sub(ticks_since_jump),
+            slide_ends_to_wait_out: u64::from(self.player_data.ticks_between_consecutive_slides)
+                .saturating_sub(ticks_since_slide),
+            jump_pressed_at: player.jump_pressed_at.filter(|pressed_at| {
+                current_tick <= pressed_at + u64::from(self.player_data.ticks_in_jump_buffer)
+            }),
+            invulnerable_until: player.invulnerable_until.max(current_tick),
+            was_up_engaged: player.was_up_engaged,
+            is_up_engaged: state.input_ctrl.up_engaged(),
+            is_down_engaged: state.input_ctrl.down_engaged(),
+            has_picked_up: state.has_picked_up,
+        }
+    }
+}
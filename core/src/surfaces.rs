@@ -0,0 +1,38 @@
+use crate::components::{Drawable, Transform};
+use crate::graphics::data::{PlatformTile, Tile};
+use crate::rect::Rect;
+
+/// Tops of the ground & of the platforms. Ground keeps moving. So, these
+/// are moved by `x_offset` to line them up with a particular tick
+pub(crate) struct Surfaces<'a> {
+    pub bounds: &'a [Rect],
+    pub x_offset: i32,
+}
+
+impl<'a> Surfaces<'a> {
+    /// Only the grassy tops of the ground tiles can be stood on
+    pub fn bounds_of<'b>(ground: impl Iterator<Item = (&'b Transform, &'b Drawable)>) -> Vec<Rect> {
+        ground
+            .filter(|(_, drawable)| {
+                drawable.tile_data.tile
+                    == Tile::Platform {
+                        tile: PlatformTile::GrassyGround,
+                    }
+            })
+            .map(|(transform, drawable)| drawable.world_bounds(transform))
+            .collect()
+    }
+
+    /// Top of the highest surface under `bounds` that isn't above its bottom
+    pub fn top_under(&self, bounds: &Rect) -> Option<i32> {
+        self.bounds
+            .iter()
+            .filter(|surface| {
+                surface.left() + self.x_offset < bounds.right()
+                    && surface.right() + self.x_offset > bounds.left()
+                    && surface.top() >= bounds.bottom()
+            })
+            .map(|surface| surface.top())
+            .min()
+    }
+}
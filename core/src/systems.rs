@@ -1,3 +1,4 @@
+mod animation_system;
 mod cloud_system;
 mod collision_system;
 mod enemy_system;
@@ -5,9 +6,13 @@ mod event_system;
 mod game_play_tick_updater;
 mod game_speed_updater;
 mod ground_system;
+mod life_system;
+mod movement_system;
+mod pickup_system;
 mod player_system;
 mod score_system;
 
+pub use animation_system::AnimationSystem;
 pub use cloud_system::CloudSystem;
 pub use collision_system::CollisionSystem;
 pub use enemy_system::EnemySystem;
@@ -15,5 +20,9 @@ pub use event_system::EventSystem;
 pub use game_play_tick_updater::GamePlayTickUpdater;
 pub use game_speed_updater::GameSpeedUpdater;
 pub use ground_system::GroundSystem;
+pub use life_system::LifeSystem;
+pub use movement_system::MovementSystem;
+pub use pickup_system::PickupSystem;
 pub use player_system::PlayerSystem;
+pub(crate) use player_system::PlayerTick;
 pub use score_system::ScoreSystem;
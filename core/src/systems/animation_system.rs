@@ -0,0 +1,117 @@
+use crate::components::{Animatable, Drawable, Transform};
+use crate::entities::{EnemyEntity, PlayerEntity};
+use crate::graphics::data::Tile;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::resources::GamePlay;
+use specs::join::Join;
+use specs::shred::ResourceId;
+use specs::World;
+use specs::{ReadExpect, System, SystemData, WriteStorage};
+
+/// Plays whatever clip each [Animatable] has been asked to play. Frames
+/// move on once they have been shown for long enough
+pub struct AnimationSystem {
+    tile_atlas: TileAtlas,
+}
+
+impl AnimationSystem {
+    pub fn new(tile_atlas: TileAtlas) -> AnimationSystem {
+        AnimationSystem { tile_atlas }
+    }
+
+    /// Animates by a tick
+    pub(crate) fn animate(
+        tile_atlas: &TileAtlas,
+        current_tick: u64,
+        animatable: &mut Animatable,
+        transform: &mut Transform,
+        drawable: &mut Drawable,
+    ) {
+        let frame = animatable.clip.frame(animatable.frame_index);
+        let ticks_in_frame = (f32::from(frame.ticks) * animatable.ticks_multiplier) as u64;
+        if animatable.frame_started_at_tick + ticks_in_frame <= current_tick {
+            if let Some(index) = animatable.clip.next_frame_index(animatable.frame_index) {
+                animatable.frame_index = index;
+                animatable.frame_started_at_tick = current_tick;
+            }
+        }
+
+        AnimationSystem::show_current_frame(tile_atlas, animatable, transform, drawable);
+    }
+
+    /// Redraws `drawable` if it isn't already showing the current frame (for
+    /// eg., when a new clip has just started playing)
+    pub(crate) fn show_current_frame(
+        tile_atlas: &TileAtlas,
+        animatable: &Animatable,
+        transform: &mut Transform,
+        drawable: &mut Drawable,
+    ) {
+        let tile = animatable.current_tile();
+        if drawable.tile_data.tile == tile {
+            return;
+        }
+
+        // Player stays on whatever they are standing on & enemies (since they
+        // come from the right) keep their front where it was
+        let old_drawable = *drawable;
+        let keeps_right = match tile {
+            Tile::Character { tile } => {
+                *drawable = PlayerEntity::build_drawable(tile_atlas, tile);
+                false
+            }
+
+            Tile::Enemy { tile } => {
+                *drawable = EnemyEntity::build_drawable(tile_atlas, tile);
+                true
+            }
+
+            _ => panic!("No idea how to animate {:?}", tile),
+        };
+
+        let x_offset = if keeps_right {
+            old_drawable.width_in_wc as i32 - drawable.width_in_wc as i32
+        } else {
+            0
+        };
+
+        transform.offset(
+            x_offset,
+            old_drawable.height_in_wc as i32 - drawable.height_in_wc as i32,
+        )
+    }
+}
+
+#[derive(SystemData)]
+pub struct AnimationSystemData<'a> {
+    game_play: ReadExpect<'a, GamePlay>,
+    animatables_storage: WriteStorage<'a, Animatable>,
+    transforms_storage: WriteStorage<'a, Transform>,
+    drawables_storage: WriteStorage<'a, Drawable>,
+}
+
+impl<'a> System<'a> for AnimationSystem {
+    type SystemData = AnimationSystemData<'a>;
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        let start_tick = data.game_play.ticks_animated();
+        let end_tick = start_tick + data.game_play.ticks_to_animate();
+        for (animatable, transform, drawable) in (
+            &mut data.animatables_storage,
+            &mut data.transforms_storage,
+            &mut data.drawables_storage,
+        )
+            .join()
+        {
+            for current_tick in start_tick..end_tick {
+                AnimationSystem::animate(
+                    &self.tile_atlas,
+                    current_tick,
+                    animatable,
+                    transform,
+                    drawable,
+                )
+            }
+        }
+    }
+}
@@ -1,21 +1,60 @@
+use crate::components::pickup::data::Kind;
 use crate::components::player::Player;
-use crate::components::{Drawable, Enemy};
-
+use crate::components::{Drawable, Enemy, Pickup, Transform};
+use crate::data::{PickupData, PlayerData};
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::rect::Rect;
 use crate::resources::GamePlay;
 use specs::join::Join;
 use specs::shred::ResourceId;
-use specs::{ReadExpect, System};
+use specs::{Entities, ReadExpect, System, WriteExpect};
 use specs::{ReadStorage, SystemData};
 use specs::{World, WriteStorage};
 
-pub struct CollisionSystem;
+pub struct CollisionSystem {
+    tile_atlas: TileAtlas,
+}
+
+impl CollisionSystem {
+    pub fn new(tile_atlas: TileAtlas) -> CollisionSystem {
+        CollisionSystem { tile_atlas }
+    }
+
+    /// Tiles have quite a bit of transparent space around them. Only the
+    /// hitboxes count to avoid getting hit by thin air
+    fn are_touching(
+        &self,
+        (player_transform, player_drawable): (&Transform, &Drawable),
+        (other_transform, other_drawable): (&Transform, &Drawable),
+    ) -> bool {
+        // No point in looking closer at far away things
+        if !Rect::intersects(
+            &player_drawable.world_bounds(player_transform),
+            &other_drawable.world_bounds(other_transform),
+        ) {
+            return false;
+        }
+
+        player_drawable
+            .world_hitboxes(player_transform, &self.tile_atlas)
+            .any(|player_hitbox| {
+                other_drawable
+                    .world_hitboxes(other_transform, &self.tile_atlas)
+                    .any(|other_hitbox| Rect::intersects(&player_hitbox, &other_hitbox))
+            })
+    }
+}
 
 #[derive(SystemData)]
 pub struct CollisionSystemData<'a> {
-    game_play: ReadExpect<'a, GamePlay>,
+    entities: Entities<'a>,
+    game_play: WriteExpect<'a, GamePlay>,
+    pickup_data: ReadExpect<'a, PickupData>,
+    player_data: ReadExpect<'a, PlayerData>,
     enemies_storage: ReadStorage<'a, Enemy>,
+    pickups_storage: ReadStorage<'a, Pickup>,
     players_storage: WriteStorage<'a, Player>,
+    transforms_storage: ReadStorage<'a, Transform>,
     drawables_storage: ReadStorage<'a, Drawable>,
 }
 
@@ -24,13 +63,65 @@ impl<'a> System<'a> for CollisionSystem {
 
     fn run(&mut self, mut data: Self::SystemData) {
         if data.game_play.ticked() {
-            for (player_drawable, mut player) in
-                (&data.drawables_storage, &mut data.players_storage).join()
+            let last_tick = data.game_play.ticks_animated() + data.game_play.ticks_to_animate() - 1;
+            for (player_transform, player_drawable, player) in (
+                &data.transforms_storage,
+                &data.drawables_storage,
+                &mut data.players_storage,
+            )
+                .join()
             {
-                for (enemy_drawable, _) in (&data.drawables_storage, &data.enemies_storage).join() {
-                    if Rect::intersects(&player_drawable.world_bounds, &enemy_drawable.world_bounds)
-                    {
-                        player.is_hit = true;
+                let player_placed = (player_transform, player_drawable);
+                // Pickups are up for grabs even when hits don't count
+                for (entity, pickup_transform, pickup_drawable, pickup) in (
+                    &data.entities,
+                    &data.transforms_storage,
+                    &data.drawables_storage,
+                    &data.pickups_storage,
+                )
+                    .join()
+                {
+                    if self.are_touching(player_placed, (pickup_transform, pickup_drawable)) {
+                        match pickup.kind {
+                            Kind::Coin => {
+                                data.game_play.add_points(data.pickup_data.points_in_coin)
+                            }
+                            Kind::Shield => player.has_shield = true,
+                            Kind::Magnet => {
+                                player.magnet_until =
+                                    last_tick + u64::from(data.pickup_data.ticks_in_magnet)
+                            }
+                        }
+
+                        data.entities
+                            .delete(entity)
+                            .expect("Pickup entity couldn't be deleted");
+                    }
+                }
+
+                // Still recovering from the last hit
+                if player.is_invulnerable(last_tick) {
+                    continue;
+                }
+
+                for (enemy_transform, enemy_drawable, _) in (
+                    &data.transforms_storage,
+                    &data.drawables_storage,
+                    &data.enemies_storage,
+                )
+                    .join()
+                {
+                    if self.are_touching(player_placed, (enemy_transform, enemy_drawable)) {
+                        // Shield takes the hit. Give the player some time to
+                        // get away from the enemy that broke it
+                        if player.has_shield {
+                            player.has_shield = false;
+                            player.invulnerable_until =
+                                last_tick + u64::from(data.player_data.ticks_in_invulnerability);
+                        } else {
+                            player.is_hit = true;
+                        }
+
                         return;
                     }
                 }
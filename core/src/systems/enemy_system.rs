@@ -1,88 +1,62 @@
-use crate::components::enemy::data::Position;
+use crate::components::input::InputControlled;
+use crate::components::player::Player;
 use crate::components::Enemy;
-use crate::components::{Animatable, Drawable};
-use crate::data::enemy_data::EnemyData;
-use crate::data::{PlayerData, WorldData};
+use crate::components::{Animatable, Drawable, Ground, Transform, Velocity};
+use crate::data::enemy_data::{EnemyData, EnemyKind};
+use crate::data::{GroundSpeeds, PlayerData, WorldData};
 use crate::entities::EnemyEntity;
-use crate::graphics::data;
 use crate::graphics::data::EnemyTile;
-use crate::resources::GamePlay;
-use rand::Rng;
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::resources::{GamePlay, GameRng, GroundLaidOut};
+use crate::spawn_planner::{Snapshot, SpawnPlanner};
 use specs::join::Join;
 use specs::shred::ResourceId;
 use specs::World;
-use specs::{Entities, Entity, SystemData};
-use specs::{ReadExpect, System, WriteStorage};
+use specs::{Entities, SystemData};
+use specs::{ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
 pub struct EnemySystem {
     world_data: WorldData,
+    tile_atlas: TileAtlas,
+    spawn_planner: SpawnPlanner,
     enemy_wave_started_at_tick: u64,
     enemies_spawned_in_current_wave: u8,
     most_recent_enemy_spawned_at_tick: u64,
 }
 
 impl EnemySystem {
-    pub fn new(world_data: WorldData) -> EnemySystem {
+    pub fn new(world_data: WorldData, tile_atlas: TileAtlas) -> EnemySystem {
         EnemySystem {
             world_data,
+            spawn_planner: SpawnPlanner::new(world_data, tile_atlas.clone()),
+            tile_atlas,
             enemy_wave_started_at_tick: 0,
             enemies_spawned_in_current_wave: 0,
             most_recent_enemy_spawned_at_tick: 0,
         }
     }
 
-    fn move_or_remove(
-        &self,
+    /// Has an enemy go up & down as its behaviour asks of it for a tick.
+    /// Moving to the left (however fast its behaviour wants it to go) &
+    /// animating are left to the movement & animation systems
+    pub(crate) fn advance(
         current_tick: u64,
-        entities: &Entities,
-        entity: Entity,
         enemy: &Enemy,
-        animatable: &mut Animatable,
-        drawable: &mut Drawable,
+        transform: &mut Transform,
+        drawable: &Drawable,
     ) {
-        // As soon as an enemy moves out, let it go
-        if drawable.world_bounds.right() <= self.world_data.bounds().left() {
-            entities
-                .delete(entity)
-                .expect("Enemy entity couldn't be deleted");
-        } else {
-            // For every tick, the enemy should move at least a bit
-            drawable
-                .world_bounds
-                .offset(-i32::from(enemy.speed_in_wc_per_tick), 0);
-
-            // Each step should last for a few ticks
-            let move_to_next_animation_frame = animatable.current_step_started_at_tick
-                + u64::from(enemy.ticks_in_movement)
-                <= current_tick;
-
-            if move_to_next_animation_frame {
-                animatable.current_step_started_at_tick = current_tick;
-                if let data::Tile::Enemy { tile } = drawable.tile_data.tile {
-                    let next_tile = match tile {
-                        EnemyTile::BatFly1 => EnemyTile::BatFly2,
-                        EnemyTile::BatFly2 => EnemyTile::BatFly1,
-                        EnemyTile::BeeFly1 => EnemyTile::BeeFly2,
-                        EnemyTile::BeeFly2 => EnemyTile::BeeFly1,
-                        EnemyTile::BugRun1 => EnemyTile::BugRun2,
-                        EnemyTile::BugRun2 => EnemyTile::BugRun1,
-                        EnemyTile::MouseRun1 => EnemyTile::MouseRun2,
-                        EnemyTile::MouseRun2 => EnemyTile::MouseRun1,
-                        EnemyTile::SpiderRun1 => EnemyTile::SpiderRun2,
-                        EnemyTile::SpiderRun2 => EnemyTile::SpiderRun1,
-                    };
-
-                    *drawable = EnemyEntity::build_drawable_with_right_bottom(
-                        next_tile,
-                        drawable.world_bounds.right(),
-                        drawable.world_bounds.bottom(),
-                    )
-                }
-            }
-        }
+        let ticks_alive = current_tick.saturating_sub(enemy.spawned_at_tick);
+        let bottom = enemy.base_world_bottom - enemy.behaviour.height_in_wc(ticks_alive);
+        transform.offset(0, bottom - drawable.world_bounds(transform).bottom());
     }
 
-    fn should_spawn_enemy(&mut self, current_tick: u64, enemy_data: &EnemyData) -> bool {
+    /// Doesn't count the enemy as spawned. Check out [EnemySystem::spawned]
+    fn should_spawn_enemy(
+        &mut self,
+        current_tick: u64,
+        enemy_data: &EnemyData,
+        rng: &mut GameRng,
+    ) -> bool {
         // Start new wave if required
         if current_tick - self.enemy_wave_started_at_tick > enemy_data.enemy_wave_ticks_count.into()
         {
@@ -96,7 +70,7 @@ impl EnemySystem {
                 .min(enemy_data.enemy_count_in_wave);
 
         let ticks_since_last_enemy = current_tick - self.most_recent_enemy_spawned_at_tick;
-        if enemies_remaining_in_wave <= 0
+        if enemies_remaining_in_wave == 0
             || ticks_since_last_enemy < u64::from(enemy_data.min_ticks_between_enemies)
         {
             false
@@ -106,45 +80,84 @@ impl EnemySystem {
                 - current_tick;
 
             // Would panic if low == high. Hence `max(1)` for upper bound
-            let spawn_enemy = rand::thread_rng().gen_range(
+            rng.gen_range(
                 0,
                 (ticks_remaining_in_wave / enemies_remaining_in_wave as u64).max(1),
-            ) == 0;
+            ) == 0
+        }
+    }
+
+    /// Enemies can still be turned down after it is decided that one should
+    /// be spawned (check out [EnemySystem::can_get_through]). Only those that
+    /// make it into the world count
+    fn spawned(&mut self, current_tick: u64) {
+        self.enemies_spawned_in_current_wave += 1;
+        self.most_recent_enemy_spawned_at_tick = current_tick;
+    }
+
+    /// Everything has been moved for the frame by now. So, planning starts
+    /// from the tick after
+    fn can_get_through(
+        &self,
+        next_tick: u64,
+        enemy: (Enemy, Animatable, Transform, Velocity, Drawable),
+        data: &EnemySystemData,
+    ) -> bool {
+        let player = (
+            &data.players_storage,
+            &data.animatables_storage,
+            &data.transforms_storage,
+            &data.drawables_storage,
+            &data.input_controlled_storage,
+        )
+            .join()
+            .next();
 
-            if spawn_enemy {
-                self.enemies_spawned_in_current_wave += 1;
-                self.most_recent_enemy_spawned_at_tick = current_tick;
+        match player {
+            Some(player) => {
+                let snapshot = Snapshot::new(
+                    next_tick,
+                    player,
+                    (
+                        &data.enemies_storage,
+                        &data.animatables_storage,
+                        &data.transforms_storage,
+                        &data.velocities_storage,
+                        &data.drawables_storage,
+                    )
+                        .join(),
+                    (
+                        &data.grounds_storage,
+                        &data.transforms_storage,
+                        &data.drawables_storage,
+                    )
+                        .join()
+                        .map(|(_, transform, drawable)| (transform, drawable)),
+                    data.ground_laid_out.till,
+                )
+                .with_enemy(enemy);
+
+                self.spawn_planner.can_get_through(
+                    &snapshot,
+                    &data.player_data,
+                    &data.ground_speeds,
+                )
             }
 
-            spawn_enemy
+            // Nobody to get through anything
+            None => true,
         }
     }
 
-    fn get_random_enemy_tile(
-        can_create_low_enemy: bool,
-        can_create_mid_enemy: bool,
-        can_create_high_enemy: bool,
-    ) -> EnemyTile {
-        loop {
-            let tile = match rand::thread_rng().gen_range(1, 6) {
-                1 => EnemyTile::BatFly1,
-                2 => EnemyTile::BeeFly1,
-                3 => EnemyTile::BugRun1,
-                4 => EnemyTile::MouseRun1,
-                _ => EnemyTile::SpiderRun1,
-            };
-
-            // Look at the call site for reasons behind why we have these flags
-            // to control which enemy we spawn
-            let can_create_such_enemy = match EnemyEntity::get_enemy_position(tile) {
-                Position::Low => can_create_low_enemy,
-                Position::Mid => can_create_mid_enemy,
-                Position::High => can_create_high_enemy,
-            };
-
-            if can_create_such_enemy {
-                return tile;
-            }
+    /// Only from the kinds that are allowed right now. Make sure there is
+    /// at least one
+    fn get_random_enemy_tile(enemy_kinds: &[EnemyKind], rng: &mut GameRng) -> EnemyTile {
+        match enemy_kinds[rng.gen_range(0, enemy_kinds.len() as i32) as usize] {
+            EnemyKind::Bat => EnemyTile::BatFly1,
+            EnemyKind::Bee => EnemyTile::BeeFly1,
+            EnemyKind::Bug => EnemyTile::BugRun1,
+            EnemyKind::Mouse => EnemyTile::MouseRun1,
+            EnemyKind::Spider => EnemyTile::SpiderRun1,
         }
     }
 }
@@ -154,72 +167,125 @@ pub struct EnemySystemData<'a> {
     entities: Entities<'a>,
     enemy_data: ReadExpect<'a, EnemyData>,
     player_data: ReadExpect<'a, PlayerData>,
+    ground_speeds: ReadExpect<'a, GroundSpeeds>,
+    ground_laid_out: ReadExpect<'a, GroundLaidOut>,
+    players_storage: ReadStorage<'a, Player>,
+    grounds_storage: ReadStorage<'a, Ground>,
+    input_controlled_storage: ReadStorage<'a, InputControlled>,
     animatables_storage: WriteStorage<'a, Animatable>,
     enemies_storage: WriteStorage<'a, Enemy>,
     drawables_storage: WriteStorage<'a, Drawable>,
+    transforms_storage: WriteStorage<'a, Transform>,
+    velocities_storage: WriteStorage<'a, Velocity>,
     game_play: ReadExpect<'a, GamePlay>,
+    rng: WriteExpect<'a, GameRng>,
 }
 
 impl<'a> System<'a> for EnemySystem {
     type SystemData = EnemySystemData<'a>;
 
     fn run(&mut self, mut data: Self::SystemData) {
-        let mut has_low_enemies = false;
-        let mut has_mid_enemies = false;
-        let mut has_high_enemies = false;
-
-        // animate/remove existing enemies
-        for (enemy, entity, mut animatable, mut drawable) in (
-            &data.enemies_storage,
-            &data.entities,
-            &mut data.animatables_storage,
-            &mut data.drawables_storage,
-        )
-            .join()
-        {
-            match enemy.position {
-                Position::Low => has_low_enemies = true,
-                Position::Mid => has_mid_enemies = true,
-                Position::High => has_high_enemies = true,
-            };
-
-            let start_tick = data.game_play.ticks_animated();
-            let end_tick = start_tick + data.game_play.ticks_to_animate();
-            for current_tick in start_tick..end_tick {
-                self.move_or_remove(
-                    current_tick,
-                    &data.entities,
-                    entity,
-                    &enemy,
-                    &mut animatable,
-                    &mut drawable,
-                )
+        // Existing enemies have already been moved (& removed if required) &
+        // animated by the movement & animation systems. How high up they are
+        // only depends on how long they have been around
+        let start_tick = data.game_play.ticks_animated();
+        let end_tick = start_tick + data.game_play.ticks_to_animate();
+        if data.game_play.ticked() {
+            for (enemy, transform, drawable) in (
+                &data.enemies_storage,
+                &mut data.transforms_storage,
+                &data.drawables_storage,
+            )
+                .join()
+            {
+                EnemySystem::advance(end_tick - 1, enemy, transform, drawable)
             }
         }
 
-        // Create new enemies if possible & required
-        if self.should_spawn_enemy(data.game_play.ticks_animated(), &data.enemy_data) {
-            // Why do we use these flags? => To make sure that at any given
-            // time, enemies are present only in 2 height positions. Otherwise,
-            // there could be situation where the user can't slide or jump
-            // through them. I think that even just allowing 2 enemies isn't going
-            // to solve this problem but should reduce the possibility quite a bit
-            let enemy_tile = EnemySystem::get_random_enemy_tile(
-                !has_mid_enemies || !has_high_enemies,
-                !has_low_enemies || !has_high_enemies,
-                !has_low_enemies || !has_mid_enemies,
-            );
-
-            EnemyEntity::create(
+        // Create new enemies if possible & required. Some stages of some
+        // difficulties may not allow any
+        if !data.enemy_data.enemy_kinds.is_empty()
+            && self.should_spawn_enemy(start_tick, &data.enemy_data, &mut data.rng)
+        {
+            let enemy = EnemyEntity::build(
                 &data.enemy_data,
                 &data.player_data,
                 &self.world_data,
-                enemy_tile,
-                &data.entities,
-                &mut data.animatables_storage,
-                &mut data.drawables_storage,
-                &mut data.enemies_storage,
-            )
+                &self.tile_atlas,
+                EnemySystem::get_random_enemy_tile(&data.enemy_data.enemy_kinds, &mut data.rng),
+                start_tick,
+            );
+
+            // Only spawn if the player can still get through everything
+            // that's coming along with this new enemy
+            if self.can_get_through(end_tick, enemy, &data) {
+                EnemyEntity::create(
+                    enemy,
+                    &data.entities,
+                    &mut data.animatables_storage,
+                    &mut data.drawables_storage,
+                    &mut data.enemies_storage,
+                    &mut data.transforms_storage,
+                    &mut data.velocities_storage,
+                );
+
+                self.spawned(start_tick)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::GroundData;
+
+    const WAVE_TICKS: u16 = 100;
+
+    fn enemy_system() -> EnemySystem {
+        EnemySystem::new(
+            WorldData::new(),
+            TileAtlas::from_ron(include_str!("../../../assets_processed/tile_atlas.ron")).unwrap(),
+        )
+    }
+
+    fn enemy_data(enemy_count_in_wave: u8) -> EnemyData {
+        EnemyData {
+            min_ticks_between_enemies: 0,
+            enemy_wave_ticks_count: WAVE_TICKS,
+            enemy_count_in_wave,
+            ..EnemyData::new(WorldData::new(), GroundData::new(1.0), None)
+        }
+    }
+
+    /// Ticks (of the first wave) at which it is decided that an enemy should
+    /// be spawned. Only those for which `is_spawned` says so are spawned
+    fn spawn_ticks(enemy_system: &mut EnemySystem, is_spawned: impl Fn(u64) -> bool) -> Vec<u64> {
+        let enemy_data = enemy_data(2);
+        let mut rng = GameRng::new(7);
+        let mut ticks = Vec::new();
+        for current_tick in 1..=u64::from(WAVE_TICKS) {
+            if enemy_system.should_spawn_enemy(current_tick, &enemy_data, &mut rng) {
+                ticks.push(current_tick);
+                if is_spawned(current_tick) {
+                    enemy_system.spawned(current_tick)
+                }
+            }
+        }
+
+        ticks
+    }
+
+    #[test]
+    fn wave_ends_once_its_enemies_are_spawned() {
+        let ticks = spawn_ticks(&mut enemy_system(), |_| true);
+        assert_eq!(ticks.len(), 2);
+    }
+
+    #[test]
+    fn turned_down_enemies_do_not_use_up_the_wave() {
+        // None of them make it into the world. The wave keeps asking for more
+        let ticks = spawn_ticks(&mut enemy_system(), |_| false);
+        assert!(ticks.len() > 2);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::components::input::data::Direction;
 use crate::components::input::InputControlled;
-use crate::input::{Event, Keycode};
+use crate::input::{Action, Event};
 use crate::resources::EventQueue;
 use specs::join::Join;
 use specs::shred::ResourceId;
@@ -27,18 +27,18 @@ impl<'a> System<'a> for EventSystem {
         for input_cntl in (&mut data.input_controlled_storage).join() {
             for event in data.event_queue.iter() {
                 match event {
-                    Event::KeyDown(keycode) => match keycode {
-                        Keycode::Up => input_cntl.update_key_down(Direction::Up),
-                        Keycode::Down => input_cntl.update_key_down(Direction::Down),
-                        Keycode::Left => input_cntl.update_key_down(Direction::Left),
-                        Keycode::Right => input_cntl.update_key_down(Direction::Right),
+                    Event::ActionDown(action) => match action {
+                        Action::Jump => input_cntl.update_key_down(Direction::Up),
+                        Action::Slide => input_cntl.update_key_down(Direction::Down),
+                        Action::MoveLeft => input_cntl.update_key_down(Direction::Left),
+                        Action::MoveRight => input_cntl.update_key_down(Direction::Right),
                         _ => {}
                     },
-                    Event::KeyUp(keycode) => match keycode {
-                        Keycode::Up => input_cntl.update_key_up(Direction::Up),
-                        Keycode::Down => input_cntl.update_key_up(Direction::Down),
-                        Keycode::Left => input_cntl.update_key_up(Direction::Left),
-                        Keycode::Right => input_cntl.update_key_up(Direction::Right),
+                    Event::ActionUp(action) => match action {
+                        Action::Jump => input_cntl.update_key_up(Direction::Up),
+                        Action::Slide => input_cntl.update_key_up(Direction::Down),
+                        Action::MoveLeft => input_cntl.update_key_up(Direction::Left),
+                        Action::MoveRight => input_cntl.update_key_up(Direction::Right),
                         _ => {}
                     },
                     _ => {}
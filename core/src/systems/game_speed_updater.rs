@@ -1,39 +1,116 @@
+use crate::data::difficulty::{Difficulty, Progress};
 use crate::data::enemy_data::EnemyData;
-use crate::data::{GroundData, PlayerData, WorldData};
-use crate::resources::{game_play, GamePlay};
+use crate::data::{GroundData, GroundSpeeds, PickupData, PlayerData, WorldData};
+use crate::resources::GamePlay;
+use crate::spawn_planner;
 use specs::shred::ResourceId;
 use specs::SystemData;
 use specs::World;
 use specs::WriteExpect;
 use specs::{ReadExpect, System};
 
-// Speed up once every 10 seconds
-const SPEED_UP_MULTIPLIER: f32 = 1.1;
-const SPEED_UP_INTERVAL_IN_SECONDS: u8 = 10;
-const SPEED_UP_TICK_INTERVAL: u16 =
-    (SPEED_UP_INTERVAL_IN_SECONDS as u16 * 1000) / (game_play::MILLISECONDS_IN_A_TICK as u16);
-
+/// Makes the game harder as it goes on, as laid out by the [Difficulty]
+/// being played. Also lets everyone know when the ground is going to speed
+/// up next (check out [GroundSpeeds])
+#[derive(Clone)]
 pub struct GameSpeedUpdater {
     last_speed_up_at_tick: u64,
+    last_speed_up_at_distance_in_wc: u64,
     last_multiplier: f32,
+    last_stage_index: Option<usize>,
+
+    // Speeds needn't be whole world coordinates per tick. So, fractions of
+    // a world coordinate are counted too
+    distance_in_wc: f64,
     world_data: WorldData,
+    player_data: PlayerData,
+    difficulty: Difficulty,
 }
 
 impl GameSpeedUpdater {
-    pub fn new(world_data: WorldData) -> Self {
+    pub fn new(world_data: WorldData, player_data: PlayerData, difficulty: Difficulty) -> Self {
         Self {
             last_speed_up_at_tick: 0,
-            last_multiplier: 1.0,
+            last_speed_up_at_distance_in_wc: 0,
+            last_multiplier: difficulty.starting_speed_multiplier,
+            last_stage_index: None,
+            distance_in_wc: 0.0,
             world_data,
+            player_data,
+            difficulty,
         }
     }
+
+    fn should_speed_up(&self, tick_animated: u64) -> bool {
+        // Progress is counted from the last speed up
+        let is_due = match self.difficulty.speed_up.every {
+            Progress::Seconds(_) => self
+                .difficulty
+                .speed_up
+                .every
+                .is_reached(tick_animated - self.last_speed_up_at_tick, 0),
+            Progress::Distance(_) => self.difficulty.speed_up.every.is_reached(
+                0,
+                self.distance_in_wc as u64 - self.last_speed_up_at_distance_in_wc,
+            ),
+        };
+
+        is_due && self.last_multiplier < self.difficulty.max_speed_multiplier
+    }
+
+    /// Counts `ticks` (from `tick_animated` on) worth of moving at
+    /// `speed_in_wc_per_tick`. Returns [true] if it is time to speed up
+    fn progress(&mut self, tick_animated: u64, ticks: u64, speed_in_wc_per_tick: f32) -> bool {
+        self.distance_in_wc += f64::from(speed_in_wc_per_tick) * ticks as f64;
+        if !self.should_speed_up(tick_animated) {
+            return false;
+        }
+
+        self.last_multiplier = (self.last_multiplier * self.difficulty.speed_up.multiplier)
+            .min(self.difficulty.max_speed_multiplier);
+        self.last_speed_up_at_tick = tick_animated;
+        self.last_speed_up_at_distance_in_wc = self.distance_in_wc as u64;
+        true
+    }
+
+    /// Ground speeds from `next_tick` on, as they would be if every frame to
+    /// come animated `ticks_per_frame` ticks. Speed ups are only ever looked
+    /// into once a frame (same as [GameSpeedUpdater::run]). Only as far ahead
+    /// as plans go
+    fn forecast(
+        &self,
+        next_tick: u64,
+        ticks_per_frame: u64,
+        ground_data: GroundData,
+    ) -> GroundSpeeds {
+        let mut updater = self.clone();
+        let mut ground_speeds = GroundSpeeds::steady(ground_data);
+        let mut speed_in_wc_per_tick = ground_data.speed_in_wc_per_tick;
+        let ticks_per_frame = ticks_per_frame.max(1);
+        let last_tick = next_tick + spawn_planner::MAX_TICKS_TO_PLAN;
+        for tick in (next_tick..last_tick).step_by(ticks_per_frame as usize) {
+            if updater.last_multiplier >= updater.difficulty.max_speed_multiplier {
+                break;
+            }
+
+            if updater.progress(tick, ticks_per_frame, speed_in_wc_per_tick) {
+                let ground_data = GroundData::new(updater.last_multiplier);
+                speed_in_wc_per_tick = ground_data.speed_in_wc_per_tick;
+                ground_speeds = ground_speeds.with_change(tick, ground_data);
+            }
+        }
+
+        ground_speeds
+    }
 }
 
 #[derive(SystemData)]
 pub struct SpeedSystemData<'a> {
     game_play: ReadExpect<'a, GamePlay>,
     ground_data: WriteExpect<'a, GroundData>,
+    ground_speeds: WriteExpect<'a, GroundSpeeds>,
     enemy_data: WriteExpect<'a, EnemyData>,
+    pickup_data: WriteExpect<'a, PickupData>,
     player_data: WriteExpect<'a, PlayerData>,
 }
 
@@ -42,19 +119,76 @@ impl<'a> System<'a> for GameSpeedUpdater {
 
     fn run(&mut self, mut data: Self::SystemData) {
         let tick_animated = data.game_play.ticks_animated();
-        if self.last_speed_up_at_tick + u64::from(SPEED_UP_TICK_INTERVAL) <= tick_animated {
-            let multiplier = self.last_multiplier * SPEED_UP_MULTIPLIER;
-            let ground_data = GroundData::new(multiplier);
-            let enemy_data = EnemyData::new(self.world_data, ground_data);
-            let player_data = PlayerData::new();
+        let ticks_to_animate = data.game_play.ticks_to_animate();
+        let is_sped_up = self.progress(
+            tick_animated,
+            ticks_to_animate,
+            data.ground_data.speed_in_wc_per_tick,
+        );
+
+        let stage_index = self
+            .difficulty
+            .stage_index_at(tick_animated, self.distance_in_wc as u64);
+        if is_sped_up || stage_index != self.last_stage_index {
+            let ground_data = GroundData::new(self.last_multiplier);
+            let stage = stage_index.map(|index| &self.difficulty.stages[index]);
+            let enemy_data = EnemyData::new(self.world_data, ground_data, stage);
+            let pickup_data = PickupData::new(self.world_data, ground_data);
+            let player_data = self.player_data;
 
             *data.ground_data = ground_data;
             *data.enemy_data = enemy_data;
+            *data.pickup_data = pickup_data;
             *data.player_data = player_data;
 
             // Update local data for next run
-            self.last_multiplier = multiplier;
-            self.last_speed_up_at_tick = tick_animated;
+            self.last_stage_index = stage_index;
+        }
+
+        *data.ground_speeds = self.forecast(
+            tick_animated + ticks_to_animate,
+            ticks_to_animate,
+            *data.ground_data,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::difficulty::SpeedUp;
+
+    /// Speeds up by 10% every 101 world coordinates. The ground starts off
+    /// at 2 world coordinates per tick
+    fn game_speed_updater() -> GameSpeedUpdater {
+        let difficulty = Difficulty {
+            speed_up: SpeedUp {
+                multiplier: 1.1,
+                every: Progress::Distance(101),
+            },
+            ..Difficulty::normal()
+        };
+
+        GameSpeedUpdater::new(WorldData::new(), PlayerData::new(), difficulty)
+    }
+
+    #[test]
+    fn forecast_speeds_up_on_the_frame_it_would_happen() {
+        // Speeding up is due once 51 ticks (102 world coordinates) have been
+        // counted. At 3 ticks a frame, those are counted by the frame that
+        // starts at tick 48 & that whole frame is sped up
+        let ground_data = GroundData::new(1.0);
+        for &(ticks_per_frame, speed_up_at_tick) in &[(1, 50), (3, 48)] {
+            let ground_speeds = game_speed_updater().forecast(0, ticks_per_frame, ground_data);
+            assert_eq!(
+                ground_speeds.at(speed_up_at_tick - 1).speed_in_wc_per_tick,
+                ground_data.speed_in_wc_per_tick
+            );
+
+            assert_eq!(
+                ground_speeds.at(speed_up_at_tick).speed_in_wc_per_tick,
+                GroundData::new(1.1).speed_in_wc_per_tick
+            );
         }
     }
 }
@@ -1,98 +1,231 @@
-use crate::components::Drawable;
-use crate::components::Ground;
-use crate::data::{GroundData, WorldData};
-use crate::entities::GroundEntity;
-use crate::resources::GamePlay;
+use crate::components::ground::data::Terrain;
+use crate::components::input::InputControlled;
+use crate::components::player::Player;
+use crate::components::{Animatable, Enemy, Transform};
+use crate::data::{GroundData, GroundSpeeds, PlayerData, WorldData};
+use crate::entities::{GroundEntity, GroundStorages};
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::resources::{GamePlay, GameRng, GroundLaidOut};
+use crate::spawn_planner::{Snapshot, SpawnPlanner};
 use specs::join::Join;
 use specs::shred::ResourceId;
+use specs::SystemData;
 use specs::World;
-use specs::{Entities, Entity, SystemData};
-use specs::{ReadExpect, System, WriteStorage};
+use specs::{ReadExpect, ReadStorage, System, WriteExpect};
+
+// Pits are just wide enough to need a jump. Flat stretches between
+// anything else give the player some time to get ready
+const COLUMNS_IN_PIT: u8 = 2;
+const MIN_COLUMNS_IN_RAISED: u8 = 3;
+const MAX_COLUMNS_IN_RAISED: u8 = 5;
+const MIN_COLUMNS_IN_FLAT: u8 = 4;
+const MAX_COLUMNS_IN_FLAT: u8 = 8;
 
 pub struct GroundSystem {
     world_data: WorldData,
+    tile_atlas: TileAtlas,
+    spawn_planner: SpawnPlanner,
+    next_column: Option<Transform>,
+    current_terrain: Terrain,
+    columns_remaining_in_terrain: u8,
 }
 
 impl GroundSystem {
-    pub fn new(world_data: WorldData) -> GroundSystem {
-        GroundSystem { world_data }
+    pub fn new(world_data: WorldData, tile_atlas: TileAtlas) -> GroundSystem {
+        GroundSystem {
+            world_data,
+            spawn_planner: SpawnPlanner::new(world_data, tile_atlas.clone()),
+            tile_atlas,
+            next_column: None,
+            current_terrain: Terrain::Flat,
+            columns_remaining_in_terrain: MIN_COLUMNS_IN_FLAT,
+        }
+    }
+
+    /// There is always some flat ground after a pit or a platform
+    fn pick_next_terrain(current_terrain: Terrain, rng: &mut GameRng) -> (Terrain, u8) {
+        match current_terrain {
+            Terrain::Pit | Terrain::Raised => (
+                Terrain::Flat,
+                rng.gen_range(MIN_COLUMNS_IN_FLAT, MAX_COLUMNS_IN_FLAT + 1),
+            ),
+            Terrain::Flat => match rng.gen_range(0, 4) {
+                0 => (Terrain::Pit, COLUMNS_IN_PIT),
+                1 => (
+                    Terrain::Raised,
+                    rng.gen_range(MIN_COLUMNS_IN_RAISED, MAX_COLUMNS_IN_RAISED + 1),
+                ),
+                _ => (
+                    Terrain::Flat,
+                    rng.gen_range(MIN_COLUMNS_IN_FLAT, MAX_COLUMNS_IN_FLAT + 1),
+                ),
+            },
+        }
     }
 
-    fn move_or_remove(
+    /// Whether the player can still get through everything that's on its way
+    /// if `columns` of `terrain` were to start at `column_left`. Everything has
+    /// been moved (check out `MovementSystem`) for the whole frame but nothing
+    /// else has been done yet. Hence, it is all moved back to where it was at
+    /// the end of the last frame. Input for the frame has come in already
+    /// though & can't be taken back
+    fn can_get_through(
         &self,
-        entities: &Entities,
-        entity: Entity,
-        ground_data: GroundData,
-        drawable: &mut Drawable,
-    ) {
-        // Every tile needs to be moved to the left by a few world coordinates. Even
-        // if a tile is outside the world bounds, keep moving it. This way everyone
-        // will stick to the same grid even when creating a new tile based on the
-        // current tiles. This will make sure there aren't any surprises in case any
-        // computation is based on the min number
-        drawable
-            .world_bounds
-            .offset(-i32::from(ground_data.speed_in_wc_per_tick), 0);
-
-        // If ground tile is outside the world bounds! Remove. The delete won't get
-        // into effect until a call to `World#maintain`
-        if drawable.world_bounds.right() < self.world_data.bounds().left() {
-            entities
-                .delete(entity)
-                .expect("Ground entity couldn't be deleted");
+        terrain: Terrain,
+        columns: u8,
+        column_left: i32,
+        data: &GroundSystemData,
+    ) -> bool {
+        let player = (
+            &data.players_storage,
+            &data.animatables_storage,
+            &data.ground_storages.transforms_storage,
+            &data.ground_storages.drawables_storage,
+            &data.input_controlled_storage,
+        )
+            .join()
+            .next();
+
+        match player {
+            None => true,
+            Some(player) => {
+                let mut surfaces = Vec::new();
+                let mut next_column_left = column_left;
+                for _ in 0..columns {
+                    surfaces.extend(GroundEntity::column_surfaces(
+                        next_column_left,
+                        terrain,
+                        &self.world_data,
+                    ));
+
+                    next_column_left += GroundEntity::column_width();
+                }
+
+                let snapshot = Snapshot::new(
+                    data.game_play.ticks_animated(),
+                    player,
+                    (
+                        &data.enemies_storage,
+                        &data.animatables_storage,
+                        &data.ground_storages.transforms_storage,
+                        &data.ground_storages.velocities_storage,
+                        &data.ground_storages.drawables_storage,
+                    )
+                        .join(),
+                    (
+                        &data.ground_storages.grounds_storage,
+                        &data.ground_storages.transforms_storage,
+                        &data.ground_storages.drawables_storage,
+                    )
+                        .join()
+                        .map(|(_, transform, drawable)| (transform, drawable)),
+                    column_left,
+                )
+                .with_terrain(surfaces, next_column_left)
+                .with_movement_rewound(&data.ground_data, data.game_play.ticks_to_animate())
+                .with_input_given();
+
+                self.spawn_planner.can_get_through(
+                    &snapshot,
+                    &data.player_data,
+                    &data.ground_speeds,
+                )
+            }
         }
     }
 }
 
 #[derive(SystemData)]
 pub struct GroundSystemData<'a> {
-    entities: Entities<'a>,
     game_play: ReadExpect<'a, GamePlay>,
     ground_data: ReadExpect<'a, GroundData>,
-    grounds_storage: WriteStorage<'a, Ground>,
-    drawables_storage: WriteStorage<'a, Drawable>,
+    ground_speeds: ReadExpect<'a, GroundSpeeds>,
+    ground_laid_out: WriteExpect<'a, GroundLaidOut>,
+    player_data: ReadExpect<'a, PlayerData>,
+    animatables_storage: ReadStorage<'a, Animatable>,
+    enemies_storage: ReadStorage<'a, Enemy>,
+    ground_storages: GroundStorages<'a>,
+    input_controlled_storage: ReadStorage<'a, InputControlled>,
+    players_storage: ReadStorage<'a, Player>,
+    rng: WriteExpect<'a, GameRng>,
 }
 
 impl<'a> System<'a> for GroundSystem {
     type SystemData = GroundSystemData<'a>;
 
     fn run(&mut self, mut data: Self::SystemData) {
-        let mut max_right: Option<i32> = None;
-
-        for (entity, _, mut drawable) in (
-            &data.entities,
-            &data.grounds_storage,
-            &mut data.drawables_storage,
+        // Tiles have been moved for all the ticks in this frame by now. Otherwise
+        // the rightmost one could be an old, wrong one (in case of animating 2
+        // ticks at a time)
+        let rightmost = (
+            &data.ground_storages.grounds_storage,
+            &data.ground_storages.transforms_storage,
         )
             .join()
-        {
-            for _ in 0..data.game_play.ticks_to_animate() {
-                self.move_or_remove(&data.entities, entity, *data.ground_data, &mut drawable);
+            .map(|(_, transform)| *transform)
+            .max_by_key(|transform| transform.left());
+
+        // Pits don't have any tiles. So, after the first run, where the next column
+        // goes is tracked here instead of being worked out from the tiles. It moves
+        // along with the tiles to stay in step with them
+        let next_column = match self.next_column {
+            None => rightmost.map(|mut column| {
+                column.offset(
+                    GroundEntity::column_width(),
+                    self.world_data.world_surface_at() - column.top(),
+                );
+
+                column
+            }),
+            Some(mut column) => {
+                column.move_left(
+                    data.ground_data.speed_in_wc_per_tick,
+                    data.game_play.ticks_to_animate(),
+                );
+
+                Some(column)
             }
+        };
 
-            // Track data to figure out ground tiles to create. Make sure to do this
-            // after all moves for a tile is done to prevent gaps between tiles. Otherwise
-            // max_right could have an old, wrong value (in case of animating 2 ticks at
-            // a time)
-            let max_right_candidate = drawable.world_bounds.right();
-            max_right = match max_right {
-                None => Some(max_right_candidate),
-                Some(x) => Some(x.max(max_right_candidate)),
-            };
-        }
+        // May be there were no ticks to animate! Safely get the next column &
+        // then add more ground tiles if required. A stretch of terrain is laid
+        // out all at once (even if it goes past the right edge of the world).
+        // Otherwise, plans would take what is left of it to be flat ground
+        if let Some(mut next_column) = next_column {
+            while next_column.left() < self.world_data.bounds().right()
+                || self.columns_remaining_in_terrain > 0
+            {
+                if self.columns_remaining_in_terrain == 0 {
+                    let (terrain, columns) =
+                        GroundSystem::pick_next_terrain(self.current_terrain, &mut data.rng);
+
+                    // Pits & platforms could leave the player with no way around
+                    // the enemies that are already on their way. Stick to flat
+                    // ground in that case
+                    let is_terrain_allowed = terrain == Terrain::Flat
+                        || self.can_get_through(terrain, columns, next_column.left(), &data);
 
-        // May be there were no ticks to animate! Safely get max_right & then add
-        // more ground tiles if required
-        if let Some(max_right) = max_right {
-            if max_right < self.world_data.bounds().right() {
-                GroundEntity::create_all_world_tiles_start_at_world_x(
-                    max_right,
+                    if is_terrain_allowed {
+                        self.current_terrain = terrain;
+                        self.columns_remaining_in_terrain = columns;
+                    } else {
+                        self.current_terrain = Terrain::Flat;
+                        self.columns_remaining_in_terrain = MIN_COLUMNS_IN_FLAT;
+                    }
+                }
+
+                self.columns_remaining_in_terrain -= 1;
+                next_column = GroundEntity::create_column_at(
+                    next_column,
+                    self.current_terrain,
                     &self.world_data,
-                    &data.entities,
-                    &mut data.drawables_storage,
-                    &mut data.grounds_storage,
+                    &self.tile_atlas,
+                    &mut data.ground_storages,
                 );
             }
+
+            self.next_column = Some(next_column);
+            data.ground_laid_out.till = next_column.left();
         }
     }
 }
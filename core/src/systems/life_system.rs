@@ -0,0 +1,31 @@
+use crate::components::player::Player;
+use crate::components::Life;
+use specs::join::Join;
+use specs::shred::ResourceId;
+use specs::{Entities, ReadStorage, System, SystemData, World};
+
+/// Takes away life icons as the player loses lives
+pub struct LifeSystem;
+
+#[derive(SystemData)]
+pub struct LifeSystemData<'a> {
+    entities: Entities<'a>,
+    lives_storage: ReadStorage<'a, Life>,
+    players_storage: ReadStorage<'a, Player>,
+}
+
+impl<'a> System<'a> for LifeSystem {
+    type SystemData = LifeSystemData<'a>;
+
+    fn run(&mut self, data: Self::SystemData) {
+        for player in data.players_storage.join() {
+            for (entity, life) in (&data.entities, &data.lives_storage).join() {
+                if life.index >= player.lives {
+                    data.entities
+                        .delete(entity)
+                        .expect("Life icon couldn't be removed");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::components::player::Player;
+use crate::components::{Drawable, Transform, Velocity};
+use crate::data::{GroundData, WorldData};
+use crate::resources::GamePlay;
+use specs::join::Join;
+use specs::shred::ResourceId;
+use specs::World;
+use specs::{Entities, ReadExpect, ReadStorage, System, SystemData, WriteStorage};
+
+/// Moves everything that has a [Velocity] to the left & lets go of whatever
+/// has moved out of the world. Anything else that needs to be done to these
+/// (for eg., animating them) is up to the systems that care about them
+pub struct MovementSystem {
+    world_data: WorldData,
+}
+
+impl MovementSystem {
+    pub fn new(world_data: WorldData) -> MovementSystem {
+        MovementSystem { world_data }
+    }
+
+    /// Moves by `ticks` worth of `velocity`
+    pub(crate) fn advance(
+        ticks: u64,
+        ground_data: &GroundData,
+        player_left: Option<i32>,
+        velocity: &Velocity,
+        transform: &mut Transform,
+    ) {
+        match velocity {
+            // How fast depends on where the player is. It is worked out for
+            // every tick
+            Velocity::Dash { .. } => {
+                for _ in 0..ticks {
+                    let distance_to_player =
+                        MovementSystem::distance_to_player(player_left, transform);
+                    transform.move_left(
+                        velocity.speed_in_wc_per_tick(ground_data, distance_to_player),
+                        1,
+                    )
+                }
+            }
+
+            _ => transform.move_left(velocity.speed_in_wc_per_tick(ground_data, None), ticks),
+        }
+    }
+
+    /// How far ahead of `transform` the player is. [None] if there isn't a
+    /// player or if they have already been passed
+    pub(crate) fn distance_to_player(
+        player_left: Option<i32>,
+        transform: &Transform,
+    ) -> Option<i32> {
+        player_left
+            .map(|left| transform.left() - left)
+            .filter(|&distance| distance >= 0)
+    }
+
+    /// As soon as something moves out, it can be let go
+    pub(crate) fn has_moved_out(
+        world_data: &WorldData,
+        transform: &Transform,
+        drawable: &Drawable,
+    ) -> bool {
+        drawable.world_bounds(transform).right() <= world_data.bounds().left()
+    }
+}
+
+#[derive(SystemData)]
+pub struct MovementSystemData<'a> {
+    entities: Entities<'a>,
+    game_play: ReadExpect<'a, GamePlay>,
+    ground_data: ReadExpect<'a, GroundData>,
+    players_storage: ReadStorage<'a, Player>,
+    velocities_storage: ReadStorage<'a, Velocity>,
+    transforms_storage: WriteStorage<'a, Transform>,
+    drawables_storage: ReadStorage<'a, Drawable>,
+}
+
+impl<'a> System<'a> for MovementSystem {
+    type SystemData = MovementSystemData<'a>;
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        // Some enemies keep an eye on the player
+        let player_left = (&data.players_storage, &data.transforms_storage)
+            .join()
+            .map(|(_, transform)| transform.left())
+            .next();
+
+        for (entity, velocity, transform, drawable) in (
+            &data.entities,
+            &data.velocities_storage,
+            &mut data.transforms_storage,
+            &data.drawables_storage,
+        )
+            .join()
+        {
+            MovementSystem::advance(
+                data.game_play.ticks_to_animate(),
+                &data.ground_data,
+                player_left,
+                velocity,
+                transform,
+            );
+
+            // The delete won't get into effect until a call to `World#maintain`
+            if MovementSystem::has_moved_out(&self.world_data, transform, drawable) {
+                data.entities
+                    .delete(entity)
+                    .expect("Moving entity couldn't be deleted");
+            }
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use crate::components::input::InputControlled;
+use crate::components::pickup::data::Kind;
+use crate::components::player::Player;
+use crate::components::{Animatable, Drawable, Enemy, Ground, ShieldBubble, Transform};
+use crate::data::{GroundSpeeds, PickupData, PlayerData, WorldData};
+use crate::entities::{PickupEntity, PickupStorages};
+use crate::graphics::tile_atlas::TileAtlas;
+use crate::rect::Rect;
+use crate::resources::{GamePlay, GameRng, GroundLaidOut};
+use crate::spawn_planner::{Snapshot, SpawnPlanner};
+use specs::join::Join;
+use specs::shred::ResourceId;
+use specs::World;
+use specs::{Entities, SystemData};
+use specs::{ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+// Pickups shouldn't be dropped right on top of an enemy that just came in
+const SPAWN_CLEARANCE_IN_WC: i32 = 64;
+
+pub struct PickupSystem {
+    world_data: WorldData,
+    tile_atlas: TileAtlas,
+    spawn_planner: SpawnPlanner,
+    pickup_wave_started_at_tick: u64,
+    pickups_spawned_in_current_wave: u8,
+    most_recent_pickup_spawned_at_tick: u64,
+}
+
+impl PickupSystem {
+    pub fn new(world_data: WorldData, tile_atlas: TileAtlas) -> PickupSystem {
+        PickupSystem {
+            world_data,
+            spawn_planner: SpawnPlanner::new(world_data, tile_atlas.clone()),
+            tile_atlas,
+            pickup_wave_started_at_tick: 0,
+            pickups_spawned_in_current_wave: 0,
+            most_recent_pickup_spawned_at_tick: 0,
+        }
+    }
+
+    /// Close enough coins fly towards the player
+    fn pull_towards(
+        pickup_data: &PickupData,
+        (target_x, target_y): (i32, i32),
+        transform: &mut Transform,
+        drawable: &Drawable,
+    ) {
+        let bounds = drawable.world_bounds(transform);
+        let x_distance = (target_x - (bounds.left() + (bounds.width() / 2) as i32)) as f32;
+        let y_distance = (target_y - (bounds.top() + (bounds.height() / 2) as i32)) as f32;
+        let distance = (x_distance.powf(2.0) + y_distance.powf(2.0)).sqrt();
+        if distance > 0.0 && distance <= f32::from(pickup_data.magnet_range_in_wc) {
+            // Moving by whole world coordinates would throw the direction off
+            // (& drop any step that is less than one) at most angles
+            let step = f32::from(pickup_data.magnet_speed_in_wc_per_tick).min(distance);
+            transform.move_by(x_distance * step / distance, y_distance * step / distance)
+        }
+    }
+
+    /// Doesn't count the pickup as spawned. Check out [PickupSystem::spawned]
+    fn should_spawn_pickup(
+        &mut self,
+        current_tick: u64,
+        pickup_data: &PickupData,
+        rng: &mut GameRng,
+    ) -> bool {
+        // Start new wave if required
+        if current_tick - self.pickup_wave_started_at_tick
+            > pickup_data.pickup_wave_ticks_count.into()
+        {
+            self.pickup_wave_started_at_tick = current_tick;
+            self.pickups_spawned_in_current_wave = 0;
+        }
+
+        let pickups_remaining_in_wave = pickup_data.pickup_count_in_wave
+            - self
+                .pickups_spawned_in_current_wave
+                .min(pickup_data.pickup_count_in_wave);
+
+        let ticks_since_last_pickup = current_tick - self.most_recent_pickup_spawned_at_tick;
+        if pickups_remaining_in_wave == 0
+            || ticks_since_last_pickup < u64::from(pickup_data.min_ticks_between_pickups)
+        {
+            false
+        } else {
+            let ticks_remaining_in_wave: u64 = (self.pickup_wave_started_at_tick
+                + pickup_data.pickup_wave_ticks_count as u64)
+                - current_tick;
+
+            // Would panic if low == high. Hence `max(1)` for upper bound
+            rng.gen_range(
+                0,
+                (ticks_remaining_in_wave / pickups_remaining_in_wave as u64).max(1),
+            ) == 0
+        }
+    }
+
+    /// Pickups can still be held back after it is decided that one should be
+    /// spawned (check out [PickupSystem::can_pick_up]). Only those that make
+    /// it into the world count
+    fn spawned(&mut self, current_tick: u64) {
+        self.pickups_spawned_in_current_wave += 1;
+        self.most_recent_pickup_spawned_at_tick = current_tick;
+    }
+
+    /// Whether the player can pick up `pickup` & still get through everything
+    /// that is coming their way. Everything has been moved for the frame by
+    /// now. So, planning starts from the tick after
+    fn can_pick_up(
+        &self,
+        next_tick: u64,
+        pickup: (Transform, Drawable),
+        data: &PickupSystemData,
+    ) -> bool {
+        let storages = &data.pickup_storages;
+        let player = (
+            &data.players_storage,
+            &data.animatables_storage,
+            &storages.transforms_storage,
+            &storages.drawables_storage,
+            &data.input_controlled_storage,
+        )
+            .join()
+            .next();
+
+        match player {
+            Some(player) => {
+                let snapshot = Snapshot::new(
+                    next_tick,
+                    player,
+                    (
+                        &data.enemies_storage,
+                        &data.animatables_storage,
+                        &storages.transforms_storage,
+                        &storages.velocities_storage,
+                        &storages.drawables_storage,
+                    )
+                        .join(),
+                    (
+                        &data.grounds_storage,
+                        &storages.transforms_storage,
+                        &storages.drawables_storage,
+                    )
+                        .join()
+                        .map(|(_, transform, drawable)| (transform, drawable)),
+                    data.ground_laid_out.till,
+                )
+                .with_pickup(pickup);
+
+                self.spawn_planner.can_get_through(
+                    &snapshot,
+                    &data.player_data,
+                    &data.ground_speeds,
+                )
+            }
+
+            // Nobody to pick anything up
+            None => false,
+        }
+    }
+
+    /// Coins most of the time. Power ups are rare
+    fn get_random_kind(rng: &mut GameRng) -> Kind {
+        match rng.gen_range(0, 10) {
+            0 => Kind::Shield,
+            1 => Kind::Magnet,
+            _ => Kind::Coin,
+        }
+    }
+
+    /// Some pickups can be run into & some need a jump
+    fn get_random_height(player_data: &PlayerData, rng: &mut GameRng) -> i32 {
+        let max_jump_height = i32::from(player_data.max_jump_height_in_wc);
+        match rng.gen_range(0, 3) {
+            0 => 0,
+            1 => max_jump_height / 3,
+            _ => max_jump_height * 2 / 3,
+        }
+    }
+
+    fn update_shield_bubble(
+        &self,
+        entities: &Entities,
+        player: Option<(Rect, bool)>,
+        drawables_storage: &mut WriteStorage<Drawable>,
+        shield_bubbles_storage: &mut WriteStorage<ShieldBubble>,
+        transforms_storage: &mut WriteStorage<Transform>,
+    ) {
+        let mut has_bubble = false;
+        for (entity, _, transform, drawable) in (
+            entities,
+            &*shield_bubbles_storage,
+            &mut *transforms_storage,
+            &mut *drawables_storage,
+        )
+            .join()
+        {
+            match player {
+                Some((player_bounds, true)) => {
+                    has_bubble = true;
+                    let (bubble_transform, bubble_drawable) =
+                        PickupEntity::build_shield_bubble(&self.tile_atlas, &player_bounds);
+                    *transform = bubble_transform;
+                    *drawable = bubble_drawable
+                }
+                _ => entities
+                    .delete(entity)
+                    .expect("Shield bubble couldn't be deleted"),
+            }
+        }
+
+        if let Some((player_bounds, true)) = player {
+            if !has_bubble {
+                PickupEntity::create_shield_bubble(
+                    &self.tile_atlas,
+                    &player_bounds,
+                    entities,
+                    drawables_storage,
+                    shield_bubbles_storage,
+                    transforms_storage,
+                )
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct PickupSystemData<'a> {
+    pickup_data: ReadExpect<'a, PickupData>,
+    player_data: ReadExpect<'a, PlayerData>,
+    ground_speeds: ReadExpect<'a, GroundSpeeds>,
+    ground_laid_out: ReadExpect<'a, GroundLaidOut>,
+    animatables_storage: ReadStorage<'a, Animatable>,
+    enemies_storage: ReadStorage<'a, Enemy>,
+    grounds_storage: ReadStorage<'a, Ground>,
+    input_controlled_storage: ReadStorage<'a, InputControlled>,
+    players_storage: ReadStorage<'a, Player>,
+    shield_bubbles_storage: WriteStorage<'a, ShieldBubble>,
+    pickup_storages: PickupStorages<'a>,
+    game_play: ReadExpect<'a, GamePlay>,
+    rng: WriteExpect<'a, GameRng>,
+}
+
+impl<'a> System<'a> for PickupSystem {
+    type SystemData = PickupSystemData<'a>;
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        let start_tick = data.game_play.ticks_animated();
+        let end_tick = start_tick + data.game_play.ticks_to_animate();
+
+        // Find out what the player is up to
+        let mut player_state = None;
+        let mut magnet_until = 0;
+        let storages = &mut data.pickup_storages;
+        for (player, transform, drawable) in (
+            &data.players_storage,
+            &storages.transforms_storage,
+            &storages.drawables_storage,
+        )
+            .join()
+        {
+            player_state = Some((drawable.world_bounds(transform), player.has_shield));
+            magnet_until = player.magnet_until;
+        }
+
+        // Existing pickups are moved (& removed) by the movement system. Coins
+        // may still have to be pulled in
+        for (pickup, transform, drawable) in (
+            &storages.pickups_storage,
+            &mut storages.transforms_storage,
+            &storages.drawables_storage,
+        )
+            .join()
+        {
+            for current_tick in start_tick..end_tick {
+                match player_state {
+                    Some((player_bounds, _))
+                        if pickup.kind == Kind::Coin && current_tick < magnet_until =>
+                    {
+                        PickupSystem::pull_towards(
+                            &data.pickup_data,
+                            (
+                                player_bounds.left() + (player_bounds.width() / 2) as i32,
+                                player_bounds.top() + (player_bounds.height() / 2) as i32,
+                            ),
+                            transform,
+                            drawable,
+                        )
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.update_shield_bubble(
+            &storages.entities,
+            player_state,
+            &mut storages.drawables_storage,
+            &mut data.shield_bubbles_storage,
+            &mut storages.transforms_storage,
+        );
+
+        // Create new pickups if possible & required
+        let current_tick = data.game_play.ticks_animated();
+        if self.should_spawn_pickup(current_tick, &data.pickup_data, &mut data.rng) {
+            let kind = PickupSystem::get_random_kind(&mut data.rng);
+            let height = PickupSystem::get_random_height(&data.player_data, &mut data.rng);
+            let is_spawn_area_clear = (
+                &data.enemies_storage,
+                &data.pickup_storages.transforms_storage,
+                &data.pickup_storages.drawables_storage,
+            )
+                .join()
+                .all(|(_, transform, drawable)| {
+                    drawable.world_bounds(transform).right()
+                        < self.world_data.bounds().right() - SPAWN_CLEARANCE_IN_WC
+                });
+
+            // Pickups that can only be got to by running into something (or
+            // by falling into a pit) are left out. So is every pickup that
+            // can't be got to at all
+            let pickup = PickupEntity::build(&self.world_data, &self.tile_atlas, kind, height);
+            if is_spawn_area_clear && self.can_pick_up(end_tick, pickup, &data) {
+                PickupEntity::create(kind, pickup, &mut data.pickup_storages);
+                self.spawned(current_tick)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::GroundData;
+    use crate::graphics::data::{PlatformTile, Tile};
+    use crate::render::Layer;
+
+    #[test]
+    fn coins_are_pulled_in_at_an_angle() {
+        // Coin is a lot further away sideways than it is up. Less than a
+        // world coordinate a tick is left for going down
+        let tile_atlas =
+            TileAtlas::from_ron(include_str!("../../../assets_processed/tile_atlas.ron")).unwrap();
+        let pickup_data = PickupData::new(WorldData::new(), GroundData::new(1.0));
+        let drawable = Drawable {
+            tile_data: tile_atlas.tile_data(Tile::Platform {
+                tile: PlatformTile::Coin,
+            }),
+            width_in_wc: 10,
+            height_in_wc: 10,
+            layer: Layer::Pickups,
+        };
+
+        let mut transform = Transform::new(200, 0);
+        for _ in 0..10 {
+            PickupSystem::pull_towards(&pickup_data, (5, 25), &mut transform, &drawable);
+        }
+
+        // 10 ticks at 8 world coordinates a tick, give or take rounding
+        let x_distance = (200 - transform.left()) as f32;
+        let y_distance = transform.top() as f32;
+        let distance = (x_distance.powf(2.0) + y_distance.powf(2.0)).sqrt();
+        assert!(y_distance >= 7.0);
+        assert!((distance - 80.0).abs() <= 1.5, "distance: {}", distance);
+    }
+}
@@ -2,26 +2,40 @@ use crate::components::input::InputControlled;
 use crate::components::player::data as player_data;
 use crate::components::player::data::Action;
 use crate::components::player::Player;
-use crate::components::{Animatable, Drawable};
-use crate::data::{PlayerData, WorldData};
-use crate::entities::PlayerEntity;
-use crate::graphics::data::{CharacterTile, Tile};
+use crate::components::{Animatable, Drawable, Ground, Hidden, Transform};
+use crate::data::{GroundData, PlayerData, WorldData};
+use crate::graphics::tile_atlas::TileAtlas;
 use crate::jump_physics::JumpPhysics;
+use crate::rect::Rect;
 use crate::resources::GamePlay;
+use crate::surfaces::Surfaces;
+use crate::systems::AnimationSystem;
 use specs::join::Join;
 use specs::shred::ResourceId;
-use specs::{ReadExpect, System, WriteStorage};
+use specs::{Entities, ReadExpect, System, WriteStorage};
 use specs::{ReadStorage, SystemData};
 use specs::{World, WriteExpect};
 use std::convert::TryFrom;
 
+/// Whatever the player goes by in a tick. Stays the same all through the tick
+pub(crate) struct PlayerTick<'a> {
+    pub current_tick: u64,
+    pub player_data: &'a PlayerData,
+    pub surfaces: &'a Surfaces<'a>,
+    pub input_ctrl: &'a InputControlled,
+}
+
 pub struct PlayerSystem {
     world_data: WorldData,
+    tile_atlas: TileAtlas,
 }
 
 impl PlayerSystem {
-    pub fn new(world_data: WorldData) -> PlayerSystem {
-        PlayerSystem { world_data }
+    pub fn new(world_data: WorldData, tile_atlas: TileAtlas) -> PlayerSystem {
+        PlayerSystem {
+            world_data,
+            tile_atlas,
+        }
     }
 
     fn input_to_vertical_action(
@@ -39,6 +53,14 @@ impl PlayerSystem {
             }
         }
 
+        // A press that came in a little too early (for eg., just before landing)
+        // still counts. No need to wait between jumps for these
+        if input_action.is_none()
+            && PlayerSystem::is_jump_buffered(current_tick, player_data, player)
+        {
+            input_action = Some(Action::Jump)
+        }
+
         if input_action.is_none() && input_ctrl.down_engaged() && !input_ctrl.up_engaged() {
             let ticks_since_last_slide = current_tick - player.most_recent_max_slide_end_at;
             if ticks_since_last_slide >= u64::from(player_data.ticks_between_consecutive_slides) {
@@ -49,10 +71,39 @@ impl PlayerSystem {
         input_action
     }
 
+    fn is_jump_buffered(current_tick: u64, player_data: &PlayerData, player: &Player) -> bool {
+        match player.jump_pressed_at {
+            None => false,
+            Some(pressed_at) => {
+                current_tick <= pressed_at + u64::from(player_data.ticks_in_jump_buffer)
+            }
+        }
+    }
+
+    fn is_in_coyote_time(current_tick: u64, player_data: &PlayerData, player: &Player) -> bool {
+        match player.fall_started_at {
+            None => false,
+            Some(fall_started_at) => {
+                current_tick <= fall_started_at + u64::from(player_data.ticks_in_coyote_time)
+            }
+        }
+    }
+
+    fn is_blinked_out(current_tick: u64, player_data: &PlayerData, player: &Player) -> bool {
+        if player.lives == 0 || !player.is_invulnerable(current_tick) {
+            return false;
+        }
+
+        let ticks_left = player.invulnerable_until - current_tick;
+        let blink_count = ticks_left / u64::from(player_data.ticks_in_invulnerability_blink.max(1));
+        blink_count % 2 == 1
+    }
+
     fn input_to_bounded_x_offset(
         &self,
         player_data: &PlayerData,
-        drawable: &mut Drawable,
+        transform: &Transform,
+        drawable: &Drawable,
         input_ctrl: &InputControlled,
     ) -> i32 {
         let x_offset = if input_ctrl.right_engaged() && !input_ctrl.left_engaged() {
@@ -63,112 +114,112 @@ impl PlayerSystem {
             0 // Player will run at place which will keep the player keep up with ground
         };
 
-        let new_x = drawable.world_bounds.x() + x_offset;
-        let new_x = new_x.max(self.world_data.bounds().left()).min(
-            self.world_data.bounds().right()
-                - i32::try_from(drawable.world_bounds.width()).unwrap(),
-        );
+        let new_x = transform.left() + x_offset;
+        let new_x = new_x
+            .max(self.world_data.bounds().left())
+            .min(self.world_data.bounds().right() - i32::try_from(drawable.width_in_wc).unwrap());
 
-        new_x - drawable.world_bounds.x()
+        new_x - transform.left()
     }
 
-    fn update(
-        &mut self,
-        current_tick: u64,
-        player_data: &PlayerData,
+    /// Moves & animates the player by a tick
+    pub(crate) fn update(
+        &self,
+        tick: &PlayerTick,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
-        input_ctrl: &InputControlled,
         player: &mut Player,
     ) {
-        let tile = drawable.tile_data.tile;
-        let current_step_started_at_tick = animatable.current_step_started_at_tick;
+        // Keep track of presses to buffer jumps
+        if tick.input_ctrl.up_engaged() && !player.was_up_engaged {
+            player.jump_pressed_at = Some(tick.current_tick);
+        }
+
+        player.was_up_engaged = tick.input_ctrl.up_engaged();
 
         // Move the player horizontally in the screen if wished for
-        let bounded_x_offset = self.input_to_bounded_x_offset(player_data, drawable, input_ctrl);
-        drawable.world_bounds.offset(bounded_x_offset, 0);
+        let bounded_x_offset =
+            self.input_to_bounded_x_offset(tick.player_data, transform, drawable, tick.input_ctrl);
+        transform.offset(bounded_x_offset, 0);
+
+        // Ran off the edge of a platform or into a pit
+        let is_on_the_ground =
+            player.current_action == Action::Run || player.current_action == Action::Slide;
+        let bounds = drawable.world_bounds(transform);
+        if is_on_the_ground && tick.surfaces.top_under(&bounds) != Some(bounds.bottom()) {
+            self.start_fall(tick, animatable, &bounds, player)
+        }
 
         // Move (if required) & animate with appropriate action
         match player.current_action {
+            // Dropped down without jumping a moment ago. There is still time
+            // to jump
+            Action::Jump
+                if PlayerSystem::is_in_coyote_time(tick.current_tick, tick.player_data, player)
+                    && PlayerSystem::is_jump_buffered(
+                        tick.current_tick,
+                        tick.player_data,
+                        player,
+                    ) =>
+            {
+                self.start_jump(tick, animatable, transform, drawable, player)
+            }
+
             // Already an uninterruptible input based animation is going on. Transfer
             // the control over to that to either carry on the animation or to finish
             // it. We don't worry about new inputs at this point
-            Action::Jump => self.continue_jump_or_start_running(
-                current_tick,
-                player_data,
-                animatable,
-                drawable,
-                input_ctrl,
-                player,
-            ),
+            Action::Jump => {
+                self.continue_jump_or_start_running(tick, animatable, transform, drawable, player)
+            }
 
             // Another uninterruptible animation
             Action::Slide => self.continue_slide_or_start_running(
-                current_tick,
-                current_step_started_at_tick,
-                player_data,
+                tick,
+                animatable.clip_started_at_tick,
                 animatable,
+                transform,
                 drawable,
                 player,
-                input_ctrl,
             ),
 
             // Probably the game just started. Start running
-            Action::Still => self.start_run(current_tick, animatable, drawable, player),
+            Action::Still => self.start_run(tick, animatable, transform, drawable, player),
 
             // No input based animation going on. Gotta check if we should start one now
             Action::Run => {
                 let new_action = PlayerSystem::input_to_vertical_action(
-                    current_tick,
-                    player_data,
+                    tick.current_tick,
+                    tick.player_data,
                     player,
-                    input_ctrl,
+                    tick.input_ctrl,
                 );
 
                 match new_action {
                     // Some new input based action to start
                     Some(action) => match action {
-                        Action::Jump => self.start_jump(
-                            current_tick,
-                            player_data,
-                            animatable,
-                            drawable,
-                            input_ctrl,
-                            player,
-                        ),
+                        Action::Jump => {
+                            self.start_jump(tick, animatable, transform, drawable, player)
+                        }
 
-                        Action::Slide => self.start_slide(
-                            current_tick,
-                            current_tick,
-                            player_data,
-                            animatable,
-                            drawable,
-                            player,
-                            input_ctrl,
-                        ),
+                        Action::Slide => {
+                            self.start_slide(tick, animatable, transform, drawable, player)
+                        }
 
-                        Action::Still | Action::Run => self.continue_run(
-                            current_tick,
-                            current_step_started_at_tick,
+                        Action::Still | Action::Run => PlayerSystem::continue_run(
                             bounded_x_offset,
-                            player_data,
+                            tick.player_data,
                             animatable,
-                            drawable,
                             player,
-                            tile,
                         ),
                     },
 
                     // Nothing else to do! Just continue running
-                    None => self.continue_run(
-                        current_tick,
-                        current_step_started_at_tick,
+                    None => PlayerSystem::continue_run(
                         bounded_x_offset,
-                        player_data,
+                        tick.player_data,
                         animatable,
-                        drawable,
                         player,
-                        tile,
                     ),
                 }
             }
@@ -177,217 +228,181 @@ impl PlayerSystem {
 
     fn start_slide(
         &self,
-        current_tick: u64,
-        slide_started_at_tick: u64,
-        player_data: &PlayerData,
+        tick: &PlayerTick,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
         player: &mut Player,
-        input_ctrl: &InputControlled,
     ) {
-        animatable.current_step_started_at_tick = current_tick;
         player.current_action = Action::Slide;
-        self.update_drawable_for_slide_tile(
-            current_tick,
-            slide_started_at_tick,
-            player_data,
-            drawable,
-            input_ctrl,
-        );
+        animatable.play(tick.player_data.slide_clip, tick.current_tick);
+        AnimationSystem::show_current_frame(&self.tile_atlas, animatable, transform, drawable);
     }
 
     fn continue_slide_or_start_running(
         &self,
-        current_tick: u64,
+        tick: &PlayerTick,
         slide_started_at_tick: u64,
-        player_data: &PlayerData,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
         player: &mut Player,
-        input_ctrl: &InputControlled,
     ) {
-        let continue_slide = self.update_drawable_for_slide_tile(
-            current_tick,
-            slide_started_at_tick,
-            player_data,
-            drawable,
-            input_ctrl,
-        );
+        let enough_ticks_passed_in_slide =
+            tick.current_tick >= slide_started_at_tick + u64::from(tick.player_data.ticks_in_slide);
 
-        if !continue_slide {
-            player.most_recent_max_slide_end_at = current_tick;
-            self.start_run(current_tick, animatable, drawable, player)
+        // Slide can go on only for so long & also `down` should be engaged
+        if !tick.input_ctrl.down_engaged() || enough_ticks_passed_in_slide {
+            player.most_recent_max_slide_end_at = tick.current_tick;
+            self.start_run(tick, animatable, transform, drawable, player)
         }
     }
 
     fn start_jump(
-        &mut self,
-        current_tick: u64,
-        player_data: &PlayerData,
+        &self,
+        tick: &PlayerTick,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
-        input_ctrl: &InputControlled,
         player: &mut Player,
     ) {
-        animatable.current_step_started_at_tick = current_tick;
+        // Could be jumping from mid-air during coyote time
+        let height = self.world_data.world_surface_at() - drawable.world_bounds(transform).bottom();
+
         player.current_action = Action::Jump;
-        player.jump_physics = Some(JumpPhysics::from_ground(
-            current_tick,
-            player_data.ticks_in_max_jump,
-            player_data.max_jump_height_in_wc,
+        player.jump_pressed_at = None;
+        player.fall_started_at = None;
+        player.jump_physics = Some(JumpPhysics::from_height(
+            tick.current_tick,
+            height,
+            tick.player_data,
         ));
+        animatable.play(tick.player_data.jump_clip, tick.current_tick);
+        self.update_drawable_for_jump(tick, animatable, transform, drawable, player);
+    }
 
-        self.update_drawable_for_jump_tile(current_tick, player_data, drawable, input_ctrl, player);
+    fn start_fall(
+        &self,
+        tick: &PlayerTick,
+        animatable: &mut Animatable,
+        bounds: &Rect,
+        player: &mut Player,
+    ) {
+        let height = self.world_data.world_surface_at() - bounds.bottom();
+
+        player.current_action = Action::Jump;
+        player.fall_started_at = Some(tick.current_tick);
+        player.jump_physics = Some(JumpPhysics::fall(
+            tick.current_tick,
+            height,
+            tick.player_data,
+        ));
+        animatable.play(tick.player_data.jump_clip, tick.current_tick);
     }
 
     fn continue_jump_or_start_running(
-        &mut self,
-        current_tick: u64,
-        player_data: &PlayerData,
+        &self,
+        tick: &PlayerTick,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
-        input_ctrl: &InputControlled,
         player: &mut Player,
     ) {
-        let still_jumping = self.update_drawable_for_jump_tile(
-            current_tick,
-            player_data,
-            drawable,
-            input_ctrl,
-            player,
-        );
+        let still_jumping =
+            self.update_drawable_for_jump(tick, animatable, transform, drawable, player);
         if !still_jumping {
-            player.most_recent_max_jump_end_at = current_tick;
-            self.start_run(current_tick, animatable, drawable, player)
+            player.most_recent_max_jump_end_at = tick.current_tick;
+            self.start_run(tick, animatable, transform, drawable, player)
         }
     }
 
     fn start_run(
         &self,
-        current_tick: u64,
+        tick: &PlayerTick,
         animatable: &mut Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
         player: &mut Player,
     ) {
-        animatable.current_step_started_at_tick = current_tick;
         player.current_action = Action::Run;
-        self.update_drawable_for_run_tile(drawable, CharacterTile::Run1);
+        animatable.play(tick.player_data.run_clip, tick.current_tick);
+        AnimationSystem::show_current_frame(&self.tile_atlas, animatable, transform, drawable);
     }
 
     fn continue_run(
-        &self,
-        current_tick: u64,
-        run_started_at_tick: u64,
         x_offset: i32,
         player_data: &PlayerData,
         animatable: &mut Animatable,
-        drawable: &mut Drawable,
         player: &mut Player,
-        current_tile: Tile,
     ) {
-        let ticks_in_run_step: u64 = if x_offset == 0 {
-            u64::from(player_data.ticks_in_run_step)
+        // Legs move faster when running ahead & slower when holding back
+        animatable.ticks_multiplier = if x_offset == 0 {
+            1.0
+        } else if x_offset < 0 {
+            player_data.ticks_multiplier_for_slower_running
         } else {
-            (f32::from(player_data.ticks_in_run_step)
-                * if x_offset < 0 {
-                    player_data.ticks_multiplier_for_slower_running
-                } else {
-                    player_data.ticks_multiplier_for_faster_running
-                }) as u64
+            player_data.ticks_multiplier_for_faster_running
         };
 
-        if run_started_at_tick + ticks_in_run_step <= current_tick {
-            animatable.current_step_started_at_tick = current_tick;
-            self.update_drawable_for_run_tile(
-                drawable,
-                match current_tile {
-                    Tile::Character { tile } => match tile {
-                        CharacterTile::Run1 => CharacterTile::Run2,
-                        CharacterTile::Run2 => CharacterTile::Run3,
-                        CharacterTile::Run3 => CharacterTile::Run1,
-                        _ => CharacterTile::Run1, //  Fallback
-                    },
-                    _ => CharacterTile::Run1, //  Fallback
-                },
-            )
-        }
-
         player.current_action = Action::Run;
     }
 
-    fn update_drawable_for_run_tile(&self, drawable: &mut Drawable, tile: CharacterTile) {
-        *drawable = PlayerEntity::build_drawable_with_left_bottom(
-            tile,
-            drawable.world_bounds.left(),
-            self.world_data.world_surface_at(),
-        );
-    }
-
-    /// returns [true] if player is still going through the slide
-    fn update_drawable_for_slide_tile(
-        &self,
-        current_tick: u64,
-        slide_started_at_tick: u64,
-        player_data: &PlayerData,
-        drawable: &mut Drawable,
-        input_ctrl: &InputControlled,
-    ) -> bool {
-        *drawable = PlayerEntity::build_drawable_with_left_bottom(
-            CharacterTile::Slide,
-            drawable.world_bounds.left(),
-            self.world_data.world_surface_at(),
-        );
-
-        let enough_ticks_passed_in_slide =
-            current_tick >= slide_started_at_tick + u64::from(player_data.ticks_in_slide);
-
-        // Slide can go on only for so long & also `down` should be engaged
-        input_ctrl.down_engaged() && !enough_ticks_passed_in_slide
-    }
-
     /// returns [true] if player is still going through the jump
-    fn update_drawable_for_jump_tile(
-        &mut self,
-        current_tick: u64,
-        player_data: &PlayerData,
+    fn update_drawable_for_jump(
+        &self,
+        tick: &PlayerTick,
+        animatable: &Animatable,
+        transform: &mut Transform,
         drawable: &mut Drawable,
-        input_ctrl: &InputControlled,
         player: &mut Player,
     ) -> bool {
         let jump_physics = match player.jump_physics.take() {
             Some(physics) => physics,
-            None => JumpPhysics::from_ground(
-                current_tick,
-                player_data.ticks_in_max_jump,
-                player_data.max_jump_height_in_wc,
-            ),
+            None => JumpPhysics::from_ground(tick.current_tick, tick.player_data),
         };
 
-        let height = jump_physics.compute_height(current_tick);
-        let world_surface = self.world_data.world_surface_at();
-        let new_y = (world_surface - height).min(world_surface);
+        let old_bounds = drawable.world_bounds(transform);
+        let height = jump_physics.compute_height(tick.current_tick);
+        let new_y = self.world_data.world_surface_at() - height;
+
+        // Coming down on to something to stand on brings the jump to an end. Only
+        // the surfaces that were below the player before this tick count. This way,
+        // platforms can be jumped on to from below
+        let landed_at = if new_y > old_bounds.bottom() {
+            tick.surfaces
+                .top_under(&old_bounds)
+                .filter(|&top| top <= new_y)
+        } else {
+            None
+        };
 
-        *drawable = PlayerEntity::build_drawable_with_left_bottom(
-            CharacterTile::Jump,
-            drawable.world_bounds.left(),
-            new_y,
-        );
+        // Jump may have just started. What was being shown before is what
+        // counts for landing in that case
+        AnimationSystem::show_current_frame(&self.tile_atlas, animatable, transform, drawable);
+        let bottom = landed_at.unwrap_or(new_y);
+        transform.offset(0, bottom - drawable.world_bounds(transform).bottom());
 
-        player.jump_physics =
-            Some(jump_physics.update_gravity_if_required(current_tick, height, input_ctrl));
+        player.jump_physics = Some(jump_physics.update_gravity_if_required(
+            tick.current_tick,
+            height,
+            tick.input_ctrl,
+        ));
 
-        // If the player is at the same height as the ground, jump animation
-        // has come to an end
-        (new_y != world_surface) || height >= 0
+        landed_at.is_none()
     }
 }
 
 #[derive(SystemData)]
 pub struct PlayerSystemData<'a> {
+    entities: Entities<'a>,
     game_play: WriteExpect<'a, GamePlay>,
+    ground_data: ReadExpect<'a, GroundData>,
     player_data: ReadExpect<'a, PlayerData>,
     animatable_storage: WriteStorage<'a, Animatable>,
     drawables_storage: WriteStorage<'a, Drawable>,
+    grounds_storage: ReadStorage<'a, Ground>,
+    transforms_storage: WriteStorage<'a, Transform>,
+    hidden_storage: WriteStorage<'a, Hidden>,
     players_storage: WriteStorage<'a, Player>,
     input_controlled_storage: ReadStorage<'a, InputControlled>,
 }
@@ -396,8 +411,26 @@ impl<'a> System<'a> for PlayerSystem {
     type SystemData = PlayerSystemData<'a>;
 
     fn run(&mut self, mut data: Self::SystemData) {
-        for (mut animatable, mut drawable, input_controlled, mut player) in (
+        let surface_bounds = Surfaces::bounds_of(
+            (
+                &data.grounds_storage,
+                &data.transforms_storage,
+                &data.drawables_storage,
+            )
+                .join()
+                .map(|(_, transform, drawable)| (transform, drawable)),
+        );
+
+        let ground_origin = (&data.grounds_storage, &data.transforms_storage)
+            .join()
+            .map(|(_, transform)| transform.origin())
+            .next()
+            .unwrap_or_default();
+
+        for (entity, animatable, transform, drawable, input_controlled, player) in (
+            &data.entities,
             &mut data.animatable_storage,
+            &mut data.transforms_storage,
             &mut data.drawables_storage,
             &data.input_controlled_storage,
             &mut data.players_storage,
@@ -407,25 +440,248 @@ impl<'a> System<'a> for PlayerSystem {
             let start_tick = data.game_play.ticks_animated();
             let end_tick = start_tick + data.game_play.ticks_to_animate();
             for current_tick in start_tick..end_tick {
+                // Ground has already been moved for all the ticks in this frame
+                let mut origin = ground_origin;
+                origin.move_right(
+                    data.ground_data.speed_in_wc_per_tick,
+                    end_tick - 1 - current_tick,
+                );
+
+                let surfaces = Surfaces {
+                    bounds: &surface_bounds,
+                    x_offset: origin.left(),
+                };
+
+                // Fell into a pit. No coming back from that
+                if transform.top() >= self.world_data.world_surface_at() {
+                    player.lives = 0;
+                }
+
+                // A hit costs a life & buys some time to get out of the way
                 if player.is_hit {
-                    *drawable = PlayerEntity::build_drawable_with_left_bottom(
-                        CharacterTile::Hit,
-                        drawable.world_bounds.left(),
-                        drawable.world_bounds.bottom(),
+                    player.is_hit = false;
+                    player.lives = player.lives.saturating_sub(1);
+                    player.invulnerable_until =
+                        current_tick + u64::from(data.player_data.ticks_in_invulnerability);
+                }
+
+                if player.lives == 0 {
+                    animatable.play(data.player_data.hit_clip, current_tick);
+                    AnimationSystem::show_current_frame(
+                        &self.tile_atlas,
+                        animatable,
+                        transform,
+                        drawable,
                     );
 
                     data.game_play.mark_over()
                 } else {
-                    self.update(
+                    let tick = PlayerTick {
                         current_tick,
-                        &data.player_data,
-                        &mut animatable,
-                        &mut drawable,
-                        &input_controlled,
-                        &mut player,
-                    )
+                        player_data: &data.player_data,
+                        surfaces: &surfaces,
+                        input_ctrl: input_controlled,
+                    };
+
+                    self.update(&tick, animatable, transform, drawable, player)
                 }
             }
+
+            // Blink while hits don't count
+            let is_hidden = PlayerSystem::is_blinked_out(end_tick, &data.player_data, player);
+            if is_hidden {
+                data.hidden_storage
+                    .insert(entity, Hidden)
+                    .expect("Player couldn't be hidden");
+            } else {
+                data.hidden_storage.remove(entity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::input::data::Direction;
+    use crate::entities::PlayerEntity;
+    use crate::graphics::data::CharacterTile;
+
+    /// Runs the player tick by tick without the rest of the world
+    struct Runner {
+        player_system: PlayerSystem,
+        world_data: WorldData,
+        player_data: PlayerData,
+        current_tick: u64,
+        animatable: Animatable,
+        transform: Transform,
+        drawable: Drawable,
+        player: Player,
+        input_ctrl: InputControlled,
+    }
+
+    impl Runner {
+        /// Already running on the ground
+        fn new() -> Runner {
+            let world_data = WorldData::new();
+            let player_data = PlayerData::new();
+            let tile_atlas =
+                TileAtlas::from_ron(include_str!("../../../assets_processed/tile_atlas.ron"))
+                    .unwrap();
+
+            let drawable = PlayerEntity::build_drawable(&tile_atlas, CharacterTile::Still);
+            let mut runner = Runner {
+                player_system: PlayerSystem::new(world_data, tile_atlas),
+                world_data,
+                player_data,
+                current_tick: 0,
+                animatable: Animatable::new(player_data.still_clip),
+                transform: Transform::new(
+                    0,
+                    world_data.world_surface_at() - i32::try_from(drawable.height_in_wc).unwrap(),
+                ),
+                drawable,
+                player: Player::new(1),
+                input_ctrl: InputControlled::new(),
+            };
+
+            runner.tick(true);
+            assert_eq!(runner.player.current_action, Action::Run);
+            runner
+        }
+
+        /// Either over ground all along or over nothing at all
+        fn tick(&mut self, is_over_ground: bool) {
+            let ground = Rect::new(-1_000, self.world_data.world_surface_at(), 2_000, 50);
+            let bounds = if is_over_ground { vec![ground] } else { vec![] };
+            let tick = PlayerTick {
+                current_tick: self.current_tick,
+                player_data: &self.player_data,
+                surfaces: &Surfaces {
+                    bounds: &bounds,
+                    x_offset: 0,
+                },
+                input_ctrl: &self.input_ctrl,
+            };
+
+            self.player_system.update(
+                &tick,
+                &mut self.animatable,
+                &mut self.transform,
+                &mut self.drawable,
+                &mut self.player,
+            );
+
+            self.current_tick += 1;
+        }
+
+        /// Up is engaged for just a tick
+        fn tap_up(&mut self, is_over_ground: bool) {
+            self.input_ctrl.update_key_down(Direction::Up);
+            self.tick(is_over_ground);
+            self.input_ctrl.update_key_up(Direction::Up);
+        }
+
+        fn bottom(&self) -> i32 {
+            self.drawable.world_bounds(&self.transform).bottom()
+        }
+
+        /// Up is engaged for a while & then let go of. Up is tapped again
+        /// `ticks_before_landing` ticks before the jump would have come to
+        /// an end if it wasn't
+        fn jump_and_tap_up_before_landing(&mut self, ticks_before_landing: u64) {
+            let mut held_through = Runner::new();
+            held_through.hold_up_in_jump();
+            let ticks_till_landing = held_through.tick_till_landed() + 1;
+
+            self.hold_up_in_jump();
+            for _ in 1..ticks_till_landing - ticks_before_landing {
+                self.tick(true);
+            }
+
+            self.tap_up(true);
+        }
+
+        fn hold_up_in_jump(&mut self) {
+            self.input_ctrl.update_key_down(Direction::Up);
+            for _ in 0..self.player_data.ticks_in_max_jump / 2 {
+                self.tick(true);
+            }
+
+            self.input_ctrl.update_key_up(Direction::Up);
+        }
+
+        /// Ticks till the player is back on the ground. Returns the number of
+        /// ticks it took
+        fn tick_till_landed(&mut self) -> u64 {
+            let started_at = self.current_tick;
+            while self.player.current_action == Action::Jump {
+                assert!(self.current_tick - started_at < 1_000, "never landed");
+                self.tick(true)
+            }
+
+            self.current_tick - started_at
+        }
+    }
+
+    #[test]
+    fn jump_is_allowed_a_little_after_running_off_the_ground() {
+        let mut runner = Runner::new();
+        let surface = runner.world_data.world_surface_at();
+        for _ in 0..runner.player_data.ticks_in_coyote_time {
+            runner.tick(false);
+        }
+
+        // Already falling but it isn't too late to jump
+        assert!(runner.player.fall_started_at.is_some());
+        assert!(runner.bottom() > surface);
+
+        let bottom_when_jumped = runner.bottom();
+        runner.input_ctrl.update_key_down(Direction::Up);
+        runner.tick(false);
+        assert_eq!(runner.player.fall_started_at, None);
+        for _ in 0..5 {
+            runner.tick(false);
+        }
+
+        assert!(runner.bottom() < bottom_when_jumped);
+    }
+
+    #[test]
+    fn jump_is_not_allowed_once_coyote_time_is_over() {
+        let mut runner = Runner::new();
+        let surface = runner.world_data.world_surface_at();
+        for _ in 0..=runner.player_data.ticks_in_coyote_time {
+            runner.tick(false);
+        }
+
+        runner.tap_up(false);
+        assert!(runner.player.fall_started_at.is_some());
+        for _ in 0..10 {
+            runner.tick(false);
+            assert!(runner.bottom() > surface);
+        }
+    }
+
+    #[test]
+    fn jump_pressed_just_before_landing_is_not_lost() {
+        let ticks_before_landing = u64::from(PlayerData::new().ticks_in_jump_buffer) / 2;
+        let mut runner = Runner::new();
+        runner.jump_and_tap_up_before_landing(ticks_before_landing);
+        runner.tick_till_landed();
+        runner.tick(true);
+        assert_eq!(runner.player.current_action, Action::Jump);
+    }
+
+    #[test]
+    fn jump_pressed_long_before_landing_is_lost() {
+        let ticks_before_landing = u64::from(PlayerData::new().ticks_in_jump_buffer) * 2;
+        let mut runner = Runner::new();
+        runner.jump_and_tap_up_before_landing(ticks_before_landing);
+        runner.tick_till_landed();
+        for _ in 0..10 {
+            runner.tick(true);
+            assert_eq!(runner.player.current_action, Action::Run);
         }
     }
 }
@@ -0,0 +1,26 @@
+use crate::storage;
+use log::warn;
+use runner_core::data::difficulty::Difficulty;
+
+const STORAGE_NAME: &str = "difficulties";
+
+/// Reads the difficulties players have come up with for themselves => from
+/// `.runner_difficulties.ron` in the home directory on desktop & from local
+/// storage on the web. Check out [Difficulty] for what goes in them
+pub struct DifficultyStore;
+
+impl DifficultyStore {
+    /// The presets followed by custom ones (if there are any). Custom ones
+    /// are ignored if they can't be understood or make no sense
+    pub fn load() -> Vec<Difficulty> {
+        let mut difficulties = Difficulty::presets();
+        if let Some(text) = storage::read(STORAGE_NAME) {
+            match Difficulty::list_from_ron(&text) {
+                Ok(custom) => difficulties.extend(custom),
+                Err(error) => warn!("Ignoring custom difficulties. {}", error),
+            }
+        }
+
+        difficulties
+    }
+}
@@ -4,6 +4,7 @@ use log::info;
 pub struct Fps {
     frame_count_in_window: u32,
     window_start: Instant,
+    fps: u32,
 }
 
 impl Fps {
@@ -11,10 +12,15 @@ impl Fps {
         Fps {
             frame_count_in_window: 0,
             window_start: Instant::now(),
+            fps: 0,
         }
     }
 
-    #[cfg(debug_assertions)]
+    /// FPS over the most recent window of (a little more than) a second
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
     pub fn track(&mut self) {
         self.frame_count_in_window += 1;
         let now = Instant::now();
@@ -22,15 +28,15 @@ impl Fps {
 
         if window_elapsed_in_seconds > 1.0 {
             let fps_in_window = self.frame_count_in_window as f32 / window_elapsed_in_seconds;
-            info!("FPS: {}", fps_in_window);
+            self.fps = fps_in_window.round() as u32;
+
+            // Logging is only for when debugging is requested
+            if cfg!(debug_assertions) {
+                info!("FPS: {}", fps_in_window);
+            }
 
             self.frame_count_in_window = 0;
             self.window_start = now;
         }
     }
-
-    #[cfg(not(debug_assertions))]
-    pub fn track(&mut self) {
-        // It is a no-op when no debugging is requested
-    }
 }
@@ -0,0 +1,58 @@
+use crate::storage;
+use log::warn;
+use runner_core::high_scores::HighScores;
+
+const STORAGE_NAME: &str = "high_scores";
+
+/// Keeps high scores around between sessions => in a file on desktop & in
+/// local storage on the web
+pub struct HighScoreStore;
+
+impl HighScoreStore {
+    /// Starts afresh if there is nothing saved yet or if whatever was saved
+    /// can't be understood. Losing high scores isn't worth crashing over
+    pub fn load() -> HighScores {
+        HighScoreStore::from_saved(storage::read(STORAGE_NAME))
+    }
+
+    pub fn save(high_scores: &HighScores) {
+        storage::write(STORAGE_NAME, &high_scores.to_ron())
+    }
+
+    fn from_saved(saved: Option<String>) -> HighScores {
+        match saved {
+            None => HighScores::new(),
+            Some(text) => HighScores::from_ron(&text).unwrap_or_else(|error| {
+                warn!("Ignoring saved high scores. Couldn't parse: {}", error);
+                HighScores::new()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_saved_starts_afresh() {
+        assert!(HighScoreStore::from_saved(None).scores(1).is_empty());
+    }
+
+    #[test]
+    fn garbage_starts_afresh() {
+        let high_scores = HighScoreStore::from_saved(Some("(scores: [1, 2".to_string()));
+        assert!(high_scores.scores(1).is_empty());
+    }
+
+    #[test]
+    fn saved_scores_are_loaded_back() {
+        let mut high_scores = HighScores::new();
+        high_scores.add(20, 1);
+        high_scores.add(30, 3);
+
+        let high_scores = HighScoreStore::from_saved(Some(high_scores.to_ron()));
+        assert_eq!(high_scores.scores(1), &[20]);
+        assert_eq!(high_scores.scores(3), &[30]);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::storage;
+use log::warn;
+use runner_core::key_bindings::KeyBindings;
+
+const STORAGE_NAME: &str = "key_bindings";
+
+/// Reads the key bindings players have set up for themselves => from
+/// `.runner_key_bindings.ron` in the home directory on desktop & from
+/// local storage on the web
+pub struct KeyBindingsStore;
+
+impl KeyBindingsStore {
+    /// Falls back to the default bindings if there is nothing set up or if
+    /// whatever was set up can't be understood
+    pub fn load() -> KeyBindings {
+        match storage::read(STORAGE_NAME) {
+            None => KeyBindings::default(),
+            Some(text) => KeyBindings::from_ron(&text).unwrap_or_else(|error| {
+                warn!("Ignoring key bindings. Couldn't parse: {}", error);
+                KeyBindings::default()
+            }),
+        }
+    }
+}
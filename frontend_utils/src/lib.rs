@@ -1,3 +1,12 @@
+mod difficulty_store;
 mod fps;
+mod high_score_store;
+mod key_bindings_store;
+mod lives_store;
+mod storage;
 
+pub use difficulty_store::DifficultyStore;
 pub use fps::Fps;
+pub use high_score_store::HighScoreStore;
+pub use key_bindings_store::KeyBindingsStore;
+pub use lives_store::LivesStore;
@@ -0,0 +1,26 @@
+use crate::storage;
+use log::warn;
+
+const STORAGE_NAME: &str = "lives";
+
+/// Reads how many lives players want to play with => from `.runner_lives.ron`
+/// in the home directory on desktop & from local storage on the web. Just a
+/// number (like `3`) goes in there. Games end with the first hit otherwise
+pub struct LivesStore;
+
+impl LivesStore {
+    /// 1 if nothing is set up or if whatever was set up isn't a number of
+    /// lives the game can be played with
+    pub fn load() -> u8 {
+        match storage::read(STORAGE_NAME) {
+            None => 1,
+            Some(text) => match text.trim().parse::<u8>() {
+                Ok(lives) if lives > 0 => lives,
+                _ => {
+                    warn!("Ignoring lives. Expected 1 to 255, found: {}", text.trim());
+                    1
+                }
+            },
+        }
+    }
+}
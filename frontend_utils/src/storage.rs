@@ -0,0 +1,116 @@
+//! Somewhere to keep things between sessions => in a file in the user's
+//! home directory on desktop & in local storage on the web. Everything
+//! is stored under a `name`, which ends up as `.runner_<name>.ron` on
+//! desktop & `runner_<name>` on the web
+
+#[cfg(not(feature = "web"))]
+pub(crate) use desktop::{read, write};
+
+#[cfg(feature = "web")]
+pub(crate) use web::{read, write};
+
+#[cfg(not(feature = "web"))]
+mod desktop {
+    use log::warn;
+    use std::env;
+    use std::path::{Path, PathBuf};
+
+    pub(crate) fn read(name: &str) -> Option<String> {
+        read_in(&home_dir(), name)
+    }
+
+    pub(crate) fn write(name: &str, text: &str) {
+        write_in(&home_dir(), name, text)
+    }
+
+    fn read_in(dir: &Path, name: &str) -> Option<String> {
+        std::fs::read_to_string(file_path(dir, name)).ok()
+    }
+
+    fn write_in(dir: &Path, name: &str, text: &str) {
+        let file_path = file_path(dir, name);
+        if let Err(error) = std::fs::write(&file_path, text) {
+            warn!("Couldn't save {} to {:?}: {}", name, file_path, error)
+        }
+    }
+
+    /// The user's home directory if we can find it. Else, the directory
+    /// the game is run from
+    fn home_dir() -> PathBuf {
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+            .unwrap_or_default()
+    }
+
+    fn file_path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!(".runner_{}.ron", name))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Tests run in parallel. So, each of them gets an empty directory
+        /// of its own
+        fn test_dir(test_name: &str) -> PathBuf {
+            let dir = env::temp_dir().join(format!("runner_storage_{}", test_name));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).expect("Couldn't create test directory");
+            dir
+        }
+
+        #[test]
+        fn nothing_is_read_before_it_is_written() {
+            assert_eq!(read_in(&test_dir("nothing_written"), "scores"), None);
+        }
+
+        #[test]
+        fn written_text_is_read_back() {
+            let dir = test_dir("read_back");
+            write_in(&dir, "scores", "(scores: [3, 2, 1])");
+            write_in(&dir, "lives", "3");
+
+            assert_eq!(
+                read_in(&dir, "scores"),
+                Some("(scores: [3, 2, 1])".to_string())
+            );
+
+            assert_eq!(read_in(&dir, "lives"), Some("3".to_string()));
+            assert!(dir.join(".runner_scores.ron").is_file());
+        }
+
+        #[test]
+        fn writing_again_replaces_what_was_there() {
+            let dir = test_dir("write_again");
+            write_in(&dir, "lives", "3");
+            write_in(&dir, "lives", "5");
+            assert_eq!(read_in(&dir, "lives"), Some("5".to_string()));
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use log::warn;
+
+    pub(crate) fn read(name: &str) -> Option<String> {
+        stdweb::web::window()
+            .local_storage()
+            .get(&storage_key(name))
+    }
+
+    pub(crate) fn write(name: &str, text: &str) {
+        if stdweb::web::window()
+            .local_storage()
+            .insert(&storage_key(name), text)
+            .is_err()
+        {
+            warn!("Couldn't save {} to local storage", name)
+        }
+    }
+
+    fn storage_key(name: &str) -> String {
+        format!("runner_{}", name)
+    }
+}
@@ -0,0 +1,131 @@
+use runner_core::game_loop::GameLoop;
+use runner_core::graphics::data::Tile;
+use runner_core::input::{Action, Event};
+use runner_core::resources::EventQueue;
+use runner_core::spawn_planner::PlannedInput;
+use specs::join::Join;
+
+/// Plays the game by itself instead of following a script. Sticks to a plan
+/// (check out [GameLoop::plan_way_through]) & comes up with a new one every
+/// time something new shows up (or the game speeds up). If no new plan can
+/// be found, it sticks to the old one (if there is anything left of it) &
+/// hopes for the best
+pub struct Autopilot {
+    plan: Vec<PlannedInput>,
+    plan_starts_at_tick: u64,
+    newest_enemy_left: Option<i32>,
+    ground_laid_out_till: i32,
+    ground_speed_in_wc_per_tick: f32,
+    engaged: PlannedInput,
+    is_first_populate: bool,
+}
+
+impl Autopilot {
+    pub fn new() -> Autopilot {
+        Autopilot {
+            plan: Vec::new(),
+            plan_starts_at_tick: 0,
+            newest_enemy_left: None,
+            ground_laid_out_till: i32::MIN,
+            ground_speed_in_wc_per_tick: 0.0,
+            engaged: PlannedInput::default(),
+            is_first_populate: true,
+        }
+    }
+
+    /// Adds the events required to stick to the plan at `current_tick`
+    pub fn reset_and_populate(&mut self, current_tick: u64, game_loop: &mut GameLoop) {
+        // Laid out ground only ever moves left, unless more of it is laid out
+        let newest_enemy_left = Autopilot::newest_enemy_left(game_loop);
+        let ground_laid_out_till = game_loop.ground_laid_out().till;
+        let has_something_shown_up = newest_enemy_left > self.newest_enemy_left
+            || ground_laid_out_till > self.ground_laid_out_till;
+
+        let ground_speed_in_wc_per_tick = game_loop.ground_data().speed_in_wc_per_tick;
+        let has_sped_up = ground_speed_in_wc_per_tick != self.ground_speed_in_wc_per_tick;
+
+        self.newest_enemy_left = newest_enemy_left;
+        self.ground_laid_out_till = ground_laid_out_till;
+        self.ground_speed_in_wc_per_tick = ground_speed_in_wc_per_tick;
+        let plan_index = current_tick.saturating_sub(self.plan_starts_at_tick) as usize;
+        if has_something_shown_up || has_sped_up || plan_index >= self.plan.len() {
+            match game_loop.plan_way_through() {
+                Some(plan) => {
+                    self.plan_starts_at_tick = current_tick;
+                    self.plan = plan;
+                }
+
+                // Planning only ever sees so far ahead. What was good before
+                // may still be good
+                None if plan_index < self.plan.len() => {}
+                None => {
+                    self.plan_starts_at_tick = current_tick;
+                    self.plan = Vec::new();
+                }
+            }
+        }
+
+        let input = self
+            .plan
+            .get(current_tick.saturating_sub(self.plan_starts_at_tick) as usize)
+            .copied()
+            .unwrap_or_default();
+
+        let mut event_queue = game_loop.event_queue();
+        event_queue.reset();
+
+        // Nobody is around to read the instructions. Start right away
+        if self.is_first_populate {
+            self.is_first_populate = false;
+            event_queue.add_event(Event::ActionDown(Action::Start));
+        }
+
+        // Only changes are passed on. Same as someone pressing & letting go
+        // of keys
+        Autopilot::add_event_if_changed(
+            &mut event_queue,
+            Action::Jump,
+            self.engaged.jump,
+            input.jump,
+        );
+
+        Autopilot::add_event_if_changed(
+            &mut event_queue,
+            Action::Slide,
+            self.engaged.slide,
+            input.slide,
+        );
+
+        self.engaged = input;
+    }
+
+    /// Enemies show up on the right & only ever move left. So, the left of
+    /// the rightmost one going up means that a new one has shown up. Counting
+    /// them won't do since one usually shows up right when another goes away
+    fn newest_enemy_left(game_loop: &GameLoop) -> Option<i32> {
+        (
+            &game_loop.transforms_storage(),
+            &game_loop.drawables_storage(),
+        )
+            .join()
+            .filter(|(_, drawable)| match drawable.tile_data.tile {
+                Tile::Enemy { .. } => true,
+                _ => false,
+            })
+            .map(|(transform, _)| transform.left())
+            .max()
+    }
+
+    fn add_event_if_changed(
+        event_queue: &mut EventQueue,
+        action: Action,
+        was_engaged: bool,
+        is_engaged: bool,
+    ) {
+        match (was_engaged, is_engaged) {
+            (false, true) => event_queue.add_event(Event::ActionDown(action)),
+            (true, false) => event_queue.add_event(Event::ActionUp(action)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use runner_core::input::{Action, Event, Keycode};
+use runner_core::resources::EventQueue;
+
+/// Instead of polling a window for events, this replays events from a
+/// script. Each line in the script is of the form `<tick> <down|up> <key>`.
+/// Keys are named as in [Keycode] (case doesn't matter) & are turned into
+/// actions with the default key bindings. Blank lines & lines starting with
+/// `#` are ignored. For eg.,
+///
+/// ```text
+/// # Jump over the first enemy
+/// 120 down up
+/// 150 up up
+/// ```
+pub struct InputManager {
+    scripted_events: Vec<(u64, Event)>,
+    next_event_index: usize,
+    is_first_populate: bool,
+}
+
+impl InputManager {
+    pub fn new() -> InputManager {
+        InputManager::from_script("")
+    }
+
+    pub fn from_script(script: &str) -> InputManager {
+        let mut scripted_events: Vec<(u64, Event)> = script
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('#')
+            })
+            .map(|(index, line)| InputManager::parse_line(index + 1, line))
+            .collect();
+
+        // Stable sort. So events scheduled for the same tick stay in the
+        // order they were written in
+        scripted_events.sort_by_key(|(tick, _)| *tick);
+
+        InputManager {
+            scripted_events,
+            next_event_index: 0,
+            is_first_populate: true,
+        }
+    }
+
+    /// Adds all the events that are due at or before `current_tick`
+    pub fn reset_and_populate(&mut self, current_tick: u64, event_queue: &mut EventQueue) {
+        event_queue.reset();
+
+        // Nobody is around to read the instructions. Start right away
+        if self.is_first_populate {
+            self.is_first_populate = false;
+            event_queue.add_event(Event::ActionDown(Action::Start));
+        }
+
+        while let Some((tick, event)) = self.scripted_events.get(self.next_event_index) {
+            if *tick > current_tick {
+                break;
+            }
+
+            event_queue.add_event(*event);
+            self.next_event_index += 1;
+        }
+    }
+
+    fn parse_line(line_number: usize, line: &str) -> (u64, Event) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 3 {
+            panic!(
+                "Script line {}: expected `<tick> <down|up> <key>`, found: {}",
+                line_number, line
+            )
+        }
+
+        let tick = parts[0]
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Script line {}: invalid tick: {}", line_number, parts[0]));
+
+        let keycode = *Keycode::ALL
+            .iter()
+            .find(|keycode| format!("{:?}", keycode).eq_ignore_ascii_case(parts[2]))
+            .unwrap_or_else(|| panic!("Script line {}: unknown key: {}", line_number, parts[2]));
+
+        let event = match parts[1] {
+            "down" => Event::KeyDown(keycode),
+            "up" => Event::KeyUp(keycode),
+            action => panic!("Script line {}: unknown action: {}", line_number, action),
+        };
+
+        (tick, event)
+    }
+}
@@ -0,0 +1,329 @@
+use crate::autopilot::Autopilot;
+use crate::input_manager::InputManager;
+use runner_core::clock::ManualClock;
+use runner_core::data::difficulty::Difficulty;
+use runner_core::data::{PlayerData, WorldData};
+use runner_core::game_loop::{GameLoop, GameLoopResult};
+use runner_core::graphics::tile_atlas::TileAtlas;
+use runner_core::high_scores::HighScores;
+use runner_core::key_bindings::KeyBindings;
+use runner_core::replay::{Replay, ReplayPlayer};
+use std::env;
+use std::fs;
+use std::process;
+use std::sync::Arc;
+
+mod autopilot;
+mod input_manager;
+
+/// A little over 5 minutes of game play. A run is stopped after these
+/// many ticks even if the game isn't over yet
+const DEFAULT_MAX_TICKS: u64 = 20_000;
+
+const USAGE: &str = "Usage: runner_headless_frontend [--script <file>] [--max-ticks <n>] \
+                     [--seed <n>] [--record <file>] [--replay <file>] [--fuzz <seed count>] \
+                     [--difficulty <easy|normal|hard|file>] [--lives <n>]";
+
+struct Args {
+    script: Option<String>,
+    max_ticks: Option<u64>,
+    rng_seed: Option<u64>,
+    record: Option<String>,
+    replay: Option<String>,
+    fuzz: Option<u64>,
+    difficulty: Difficulty,
+    lives: u8,
+}
+
+struct Outcome {
+    rng_seed: u64,
+    is_game_over: bool,
+    score: u64,
+    ticks_animated: u64,
+}
+
+pub fn main() {
+    let args = parse_args();
+    if let Some(seed_count) = args.fuzz {
+        fuzz(
+            seed_count,
+            &args.difficulty,
+            args.max_ticks.unwrap_or(DEFAULT_MAX_TICKS),
+        );
+        return;
+    }
+
+    let game_loop = match &args.replay {
+        Some(path) => replay_game_loop(
+            Replay::from_ron(&read_file(path))
+                .unwrap_or_else(|error| panic!("Couldn't read replay: {}; error: {}", path, error)),
+        ),
+
+        None => {
+            let mut input_manager = match &args.script {
+                None => InputManager::new(),
+                Some(path) => InputManager::from_script(&read_file(path)),
+            };
+
+            let world_data = match args.rng_seed {
+                None => WorldData::new(),
+                Some(rng_seed) => WorldData::new().with_rng_seed(rng_seed),
+            };
+
+            run_game_loop(
+                world_data,
+                PlayerData::new().with_lives(args.lives),
+                &args.difficulty,
+                |current_tick, game_loop| {
+                    input_manager.reset_and_populate(current_tick, &mut game_loop.event_queue())
+                },
+                args.max_ticks.unwrap_or(DEFAULT_MAX_TICKS),
+            )
+        }
+    };
+
+    if let Some(path) = &args.record {
+        fs::write(path, game_loop.replay().to_ron())
+            .unwrap_or_else(|_| panic!("Couldn't write replay: {}", path));
+    }
+
+    let outcome = build_outcome(&game_loop);
+    println!("rng seed: {}", outcome.rng_seed);
+    println!("game over: {}", outcome.is_game_over);
+    println!("score: {}", outcome.score);
+    println!("ticks: {}", outcome.ticks_animated);
+}
+
+fn parse_args() -> Args {
+    let mut args = Args {
+        script: None,
+        max_ticks: None,
+        rng_seed: None,
+        record: None,
+        replay: None,
+        fuzz: None,
+        difficulty: Difficulty::default(),
+        lives: 1,
+    };
+
+    let mut raw_args = env::args().skip(1);
+    while let Some(flag) = raw_args.next() {
+        let value = raw_args
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {}\n{}", flag, USAGE));
+
+        match flag.as_str() {
+            "--script" => args.script = Some(value),
+            "--record" => args.record = Some(value),
+            "--replay" => args.replay = Some(value),
+            "--max-ticks" => {
+                args.max_ticks = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid max ticks: {}", value)),
+                )
+            }
+            "--fuzz" => {
+                args.fuzz = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid seed count: {}", value)),
+                )
+            }
+            "--difficulty" => args.difficulty = read_difficulty(&value),
+            "--lives" => {
+                args.lives = match value.parse() {
+                    Ok(lives) if lives > 0 => lives,
+                    _ => panic!("Invalid lives: {}", value),
+                }
+            }
+            "--seed" => {
+                args.rng_seed = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid rng seed: {}", value)),
+                )
+            }
+            _ => panic!("Unknown argument: {}\n{}", flag, USAGE),
+        }
+    }
+
+    args
+}
+
+fn read_file(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|_| panic!("Couldn't read file: {}", path))
+}
+
+/// One of the presets if there is one by that name. Else, a custom one
+/// from the file at `name_or_path`
+fn read_difficulty(name_or_path: &str) -> Difficulty {
+    Difficulty::presets()
+        .into_iter()
+        .find(|difficulty| difficulty.name == name_or_path)
+        .unwrap_or_else(|| {
+            Difficulty::from_ron(&read_file(name_or_path)).unwrap_or_else(|error| {
+                panic!(
+                    "Couldn't read difficulty: {}; error: {}",
+                    name_or_path, error
+                )
+            })
+        })
+}
+
+/// Nothing is drawn here. But tiles decide the size of everything in the
+/// world & so, how a game plays out
+fn load_tile_atlas() -> TileAtlas {
+    TileAtlas::from_ron(include_str!("../../assets_processed/tile_atlas.ron"))
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+fn build_outcome(game_loop: &GameLoop) -> Outcome {
+    let game_play = game_loop.game_play();
+    Outcome {
+        rng_seed: game_loop.rng_seed(),
+        is_game_over: game_play.is_over(),
+        score: game_play.score(),
+        ticks_animated: game_play.ticks_animated(),
+    }
+}
+
+/// Lets the autopilot play a game for every seed in `0..seed_count`. Since
+/// nothing should be spawned unless there is a way through, the autopilot
+/// should never lose. Exits with an error if it does for any seed
+fn fuzz(seed_count: u64, difficulty: &Difficulty, max_ticks: u64) {
+    let lost_seeds: Vec<u64> = (0..seed_count)
+        .map(|rng_seed| autopilot_outcome(rng_seed, difficulty, max_ticks))
+        .filter(|outcome| outcome.is_game_over)
+        .map(|outcome| {
+            println!(
+                "rng seed: {} lost at tick: {}",
+                outcome.rng_seed, outcome.ticks_animated
+            );
+
+            outcome.rng_seed
+        })
+        .collect();
+
+    println!("seeds: {}", seed_count);
+    println!("lost: {}", lost_seeds.len());
+    if !lost_seeds.is_empty() {
+        process::exit(1)
+    }
+}
+
+fn autopilot_outcome(rng_seed: u64, difficulty: &Difficulty, max_ticks: u64) -> Outcome {
+    let mut autopilot = Autopilot::new();
+    let game_loop = run_game_loop(
+        WorldData::new().with_rng_seed(rng_seed),
+        PlayerData::new(),
+        difficulty,
+        |current_tick, game_loop| autopilot.reset_and_populate(current_tick, game_loop),
+        max_ticks,
+    );
+
+    build_outcome(&game_loop)
+}
+
+/// `populate` is called with the tick that is going to be animated next &
+/// should add the events for it
+fn run_game_loop<'a, 'b>(
+    world_data: WorldData,
+    player_data: PlayerData,
+    difficulty: &Difficulty,
+    mut populate: impl FnMut(u64, &mut GameLoop<'a, 'b>),
+    max_ticks: u64,
+) -> GameLoop<'a, 'b> {
+    // No one is watching. So, instead of waiting on the wall-clock, move
+    // time forward by exactly one tick for every loop
+    let clock = ManualClock::new();
+    let mut game_loop = GameLoop::new_with_clock(
+        world_data,
+        load_tile_atlas(),
+        HighScores::new(),
+        KeyBindings::default(),
+        Arc::new(clock.clone()),
+    )
+    .with_player_data(player_data)
+    .with_difficulties(vec![difficulty.clone()]);
+
+    loop {
+        // The tick that is going to be animated next. Separate scope as to
+        // drop `game_play` which will let us borrow GameLoop again
+        let current_tick = {
+            let game_play = game_loop.game_play();
+            game_play.ticks_animated() + game_play.ticks_to_animate()
+        };
+
+        populate(current_tick, &mut game_loop);
+
+        // Execute loop once & let the next tick be due
+        let game_loop_result = game_loop.execute();
+        clock.advance_by_ticks(1);
+
+        // Ticks don't move while paused. Since the script is driven by
+        // ticks, nothing in it could ever resume a paused game
+        let should_stop = match game_loop_result {
+            GameLoopResult::Continue => {
+                let game_play = game_loop.game_play();
+                game_play.is_paused() || game_play.ticks_animated() >= max_ticks
+            }
+            GameLoopResult::GameOver | GameLoopResult::Quit => true,
+        };
+
+        if should_stop {
+            return game_loop;
+        }
+    }
+}
+
+/// Plays the replay frame by frame till either the frames run out or the
+/// game comes to an end
+fn replay_game_loop<'a, 'b>(replay: Replay) -> GameLoop<'a, 'b> {
+    let mut replay_player = ReplayPlayer::new(replay);
+    let mut game_loop = GameLoop::new_with_clock(
+        replay_player.world_data(WorldData::new()),
+        load_tile_atlas(),
+        HighScores::new(),
+        KeyBindings::default(),
+        replay_player.clock(),
+    )
+    .with_player_data(replay_player.player_data(PlayerData::new()))
+    .with_difficulties(vec![replay_player.difficulty()]);
+
+    loop {
+        let has_frame = replay_player.reset_and_populate(&mut game_loop.event_queue());
+        if !has_frame {
+            return game_loop;
+        }
+
+        match game_loop.execute() {
+            GameLoopResult::Continue => {}
+            GameLoopResult::GameOver | GameLoopResult::Quit => return game_loop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autopilot_gets_through_the_start_of_runs() {
+        // Long enough for a few pits & enemies. `--fuzz` is there for full
+        // runs (which go through every speed up there is) & lots more seeds
+        let max_ticks = 3_000;
+        for difficulty in Difficulty::presets() {
+            for rng_seed in 0..2 {
+                let outcome = autopilot_outcome(rng_seed, &difficulty, max_ticks);
+                assert!(
+                    !outcome.is_game_over,
+                    "{} with rng seed: {} lost at tick: {}",
+                    difficulty.name, rng_seed, outcome.ticks_animated
+                );
+
+                assert_eq!(outcome.ticks_animated, max_ticks);
+            }
+        }
+    }
+}
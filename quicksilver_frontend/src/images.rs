@@ -1,11 +1,24 @@
 use quicksilver::graphics::Image;
 use quicksilver::Graphics;
+use runner_core::graphics::data::TileSheet;
+use runner_core::graphics::tile_atlas::TileAtlas;
 use rust_embed::RustEmbed;
+use std::str;
 
 #[derive(RustEmbed)]
 #[folder = "../assets_processed/"]
 struct Asset;
 
+const TILE_ATLAS_FILENAME: &str = "tile_atlas.ron";
+
+pub fn load_tile_atlas() -> TileAtlas {
+    let bytes = Asset::get(TILE_ATLAS_FILENAME).unwrap();
+    let text = str::from_utf8(&bytes)
+        .unwrap_or_else(|_| panic!("Couldn't read file: {}", TILE_ATLAS_FILENAME));
+
+    TileAtlas::from_ron(text).unwrap_or_else(|error| panic!("{}", error))
+}
+
 pub struct Images {
     pub cloud_image: Image,
     pub character_image: Image,
@@ -17,15 +30,16 @@ pub struct Images {
 }
 
 impl Images {
-    pub fn load_from_files(graphics: &Graphics) -> Images {
+    pub fn load_from_files(tile_atlas: &TileAtlas, graphics: &Graphics) -> Images {
+        let load = |tile_sheet| Images::load_from_file(tile_atlas.image_file(tile_sheet), graphics);
         Images {
-            cloud_image: Images::load_from_file("cloud_tile_sheet.png", graphics),
-            character_image: Images::load_from_file("character_tile_sheet.png", graphics),
-            enemy_image: Images::load_from_file("enemy_tile_sheet.png", graphics),
-            icon_image: Images::load_from_file("icon_tile_sheet.png", graphics),
-            letter_image: Images::load_from_file("letter_tile_sheet.png", graphics),
-            platform_image: Images::load_from_file("platform_tile_sheet.png", graphics),
-            number_image: Images::load_from_file("number_tile_sheet.png", graphics),
+            cloud_image: load(TileSheet::Cloud),
+            character_image: load(TileSheet::Character),
+            enemy_image: load(TileSheet::Enemy),
+            icon_image: load(TileSheet::Icon),
+            letter_image: load(TileSheet::Letter),
+            platform_image: load(TileSheet::Platform),
+            number_image: load(TileSheet::Number),
         }
     }
 
@@ -1,44 +1,148 @@
+use quicksilver::geom::Vector;
 use quicksilver::input::Event as QsEvent;
-use quicksilver::input::Key;
+use quicksilver::input::{Key, MouseButton};
 use quicksilver::Input;
-use runner_core::input::{Event, Keycode};
+use runner_core::data::WorldData;
+use runner_core::input::{Event, Keycode, Pointer, PointerId};
 use runner_core::resources::EventQueue;
 
 pub struct InputManager {
+    world_data: WorldData,
     input: Input,
+    pointer_location: Vector,
+    is_pointer_down: bool,
 }
 
 impl InputManager {
-    pub fn new(input: Input) -> InputManager {
-        InputManager { input }
+    pub fn new(world_data: WorldData, input: Input) -> InputManager {
+        InputManager {
+            world_data,
+            input,
+            pointer_location: Vector::ZERO,
+            is_pointer_down: false,
+        }
     }
 
+    /// Keys & pointers are passed on as is. It is up to the core to figure
+    /// out which actions they are meant for
     pub async fn reset_and_populate(&mut self, event_queue: &mut EventQueue) {
         event_queue.reset();
         while let Some(event) = self.input.next_event().await {
-            if let QsEvent::KeyboardInput(keyboard_event) = event {
-                if keyboard_event.is_down() {
-                    match keyboard_event.key() {
-                        Key::Escape => event_queue.add_event(Event::KeyDown(Keycode::Escape)),
-                        Key::Space => event_queue.add_event(Event::KeyDown(Keycode::Space)),
-                        Key::Up => event_queue.add_event(Event::KeyDown(Keycode::Up)),
-                        Key::Down => event_queue.add_event(Event::KeyDown(Keycode::Down)),
-                        Key::Left => event_queue.add_event(Event::KeyDown(Keycode::Left)),
-                        Key::Right => event_queue.add_event(Event::KeyDown(Keycode::Right)),
-                        _ => {}
+            match event {
+                QsEvent::FocusChanged(focus_event) => {
+                    if !focus_event.is_focused() {
+                        event_queue.add_event(Event::FocusLost)
+                    }
+                }
+                QsEvent::PointerMoved(pointer_event) => {
+                    self.pointer_location = pointer_event.location();
+                    if self.is_pointer_down {
+                        event_queue.add_event(Event::PointerMove(self.pointer()))
                     }
-                } else {
-                    match keyboard_event.key() {
-                        Key::Escape => event_queue.add_event(Event::KeyUp(Keycode::Escape)),
-                        Key::Space => event_queue.add_event(Event::KeyUp(Keycode::Space)),
-                        Key::Up => event_queue.add_event(Event::KeyUp(Keycode::Up)),
-                        Key::Down => event_queue.add_event(Event::KeyUp(Keycode::Down)),
-                        Key::Left => event_queue.add_event(Event::KeyUp(Keycode::Left)),
-                        Key::Right => event_queue.add_event(Event::KeyUp(Keycode::Right)),
-                        _ => {}
+                }
+                QsEvent::PointerInput(pointer_event) => {
+                    if pointer_event.button() == MouseButton::Left {
+                        self.is_pointer_down = pointer_event.is_down();
+                        if pointer_event.is_down() {
+                            event_queue.add_event(Event::PointerDown(self.pointer()))
+                        } else {
+                            event_queue.add_event(Event::PointerUp(self.pointer()))
+                        }
+                    }
+                }
+                QsEvent::KeyboardInput(keyboard_event) => {
+                    if let Some(keycode) = to_keycode(keyboard_event.key()) {
+                        if keyboard_event.is_down() {
+                            event_queue.add_event(Event::KeyDown(keycode))
+                        } else {
+                            event_queue.add_event(Event::KeyUp(keycode))
+                        }
                     }
                 }
+                _ => {}
             }
         }
     }
+
+    /// The window is exactly as big as the world. So, pointer locations
+    /// (which are from the top left of the window) just need to be moved.
+    /// Pointer presses don't carry a location. Hence, the last known location
+    /// is used for them. Only the one pointer is tracked (as the mouse)
+    fn pointer(&self) -> Pointer {
+        let world_bounds = self.world_data.bounds();
+        Pointer {
+            id: PointerId::Mouse,
+            x: world_bounds.left() + self.pointer_location.x as i32,
+            y: world_bounds.top() + self.pointer_location.y as i32,
+        }
+    }
+}
+
+fn to_keycode(key: Key) -> Option<Keycode> {
+    let keycode = match key {
+        Key::Up => Keycode::Up,
+        Key::Down => Keycode::Down,
+        Key::Left => Keycode::Left,
+        Key::Right => Keycode::Right,
+        Key::Escape => Keycode::Escape,
+        Key::Space => Keycode::Space,
+        Key::Return => Keycode::Return,
+        Key::Back => Keycode::Backspace,
+        Key::Tab => Keycode::Tab,
+        Key::LShift => Keycode::LShift,
+        Key::RShift => Keycode::RShift,
+        Key::LControl => Keycode::LCtrl,
+        Key::RControl => Keycode::RCtrl,
+        Key::A => Keycode::A,
+        Key::B => Keycode::B,
+        Key::C => Keycode::C,
+        Key::D => Keycode::D,
+        Key::E => Keycode::E,
+        Key::F => Keycode::F,
+        Key::G => Keycode::G,
+        Key::H => Keycode::H,
+        Key::I => Keycode::I,
+        Key::J => Keycode::J,
+        Key::K => Keycode::K,
+        Key::L => Keycode::L,
+        Key::M => Keycode::M,
+        Key::N => Keycode::N,
+        Key::O => Keycode::O,
+        Key::P => Keycode::P,
+        Key::Q => Keycode::Q,
+        Key::R => Keycode::R,
+        Key::S => Keycode::S,
+        Key::T => Keycode::T,
+        Key::U => Keycode::U,
+        Key::V => Keycode::V,
+        Key::W => Keycode::W,
+        Key::X => Keycode::X,
+        Key::Y => Keycode::Y,
+        Key::Z => Keycode::Z,
+        Key::Key0 => Keycode::Num0,
+        Key::Key1 => Keycode::Num1,
+        Key::Key2 => Keycode::Num2,
+        Key::Key3 => Keycode::Num3,
+        Key::Key4 => Keycode::Num4,
+        Key::Key5 => Keycode::Num5,
+        Key::Key6 => Keycode::Num6,
+        Key::Key7 => Keycode::Num7,
+        Key::Key8 => Keycode::Num8,
+        Key::Key9 => Keycode::Num9,
+        Key::F1 => Keycode::F1,
+        Key::F2 => Keycode::F2,
+        Key::F3 => Keycode::F3,
+        Key::F4 => Keycode::F4,
+        Key::F5 => Keycode::F5,
+        Key::F6 => Keycode::F6,
+        Key::F7 => Keycode::F7,
+        Key::F8 => Keycode::F8,
+        Key::F9 => Keycode::F9,
+        Key::F10 => Keycode::F10,
+        Key::F11 => Keycode::F11,
+        Key::F12 => Keycode::F12,
+        _ => return None,
+    };
+
+    Some(keycode)
 }
@@ -3,9 +3,10 @@ use crate::input_manager::InputManager;
 use crate::renderer::Renderer;
 use quicksilver::geom::Vector;
 use quicksilver::{Graphics, Input, Result, Settings, Window};
-use runner_core::data::WorldData;
+use runner_core::data::{PlayerData, WorldData};
 use runner_core::game_loop::{GameLoop, GameLoopResult};
-use runner_frontend_utils::Fps;
+use runner_core::render;
+use runner_frontend_utils::{DifficultyStore, Fps, HighScoreStore, KeyBindingsStore, LivesStore};
 
 mod color;
 mod images;
@@ -37,12 +38,20 @@ async fn app(window: Window, mut graphics: Graphics, input: Input) -> Result<()>
     setup_splash_screen(world_data, &mut graphics, &window);
 
     // Time to load some resources
-    let images = Images::load_from_files(&graphics);
+    let tile_atlas = images::load_tile_atlas();
+    let images = Images::load_from_files(&tile_atlas, &graphics);
 
     // Build everything required for the loop
-    let mut game_loop = GameLoop::new(world_data);
+    let mut game_loop = GameLoop::new(
+        world_data,
+        tile_atlas,
+        HighScoreStore::load(),
+        KeyBindingsStore::load(),
+    )
+    .with_player_data(PlayerData::new().with_lives(LivesStore::load()))
+    .with_difficulties(DifficultyStore::load());
     let mut renderer = Renderer::new(world_data, window, graphics, images);
-    let mut input_manager = InputManager::new(input);
+    let mut input_manager = InputManager::new(world_data, input);
     let mut fps = Fps::new();
 
     // Note: unlike other front-ends, we are not using any `FrameLimiter`s
@@ -65,12 +74,20 @@ async fn app(window: Window, mut graphics: Graphics, input: Input) -> Result<()>
         // Execute loop once
         match game_loop.execute() {
             GameLoopResult::Continue => {}
+            GameLoopResult::GameOver => HighScoreStore::save(game_loop.high_scores()),
             GameLoopResult::Quit => break 'running,
         }
 
         // Display whatever we have
-        renderer.draw(game_loop.drawables_storage());
         fps.track();
+        render::draw_frame(
+            &mut renderer,
+            &world_data,
+            game_loop.transforms_storage(),
+            game_loop.drawables_storage(),
+            game_loop.hidden_storage(),
+            &game_loop.debug_overlay(fps.fps()),
+        );
     }
 
     Ok(())
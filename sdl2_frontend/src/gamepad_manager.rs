@@ -0,0 +1,182 @@
+use log::{info, warn};
+use runner_core::input::{Action, Event};
+use runner_core::resources::EventQueue;
+use sdl2::controller::{Axis, Button, GameController};
+use sdl2::event::Event as SdlEvent;
+use sdl2::GameControllerSubsystem;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// Fraction of the full tilt the stick has to be pushed by before it counts
+pub const DEFAULT_DEADZONE: f32 = 0.3;
+
+/// Something on a controller that can be held down
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+enum Input {
+    Button(Button),
+    StickLeft,
+    StickRight,
+    StickUp,
+    StickDown,
+}
+
+impl Input {
+    fn actions(self) -> &'static [Action] {
+        match self {
+            Input::Button(Button::A) => &[Action::Jump, Action::Start],
+            Input::Button(Button::B) => &[Action::Slide],
+            Input::Button(Button::Start) => &[Action::Start, Action::Pause],
+            Input::Button(Button::DPadUp) | Input::StickUp => &[Action::Jump],
+            Input::Button(Button::DPadDown) | Input::StickDown => &[Action::Slide],
+            Input::Button(Button::DPadLeft) | Input::StickLeft => &[Action::MoveLeft],
+            Input::Button(Button::DPadRight) | Input::StickRight => &[Action::MoveRight],
+            Input::Button(_) => &[],
+        }
+    }
+}
+
+/// Turns game controller events into the same actions the keyboard is bound
+/// to => D-pad & left stick to move, jump & slide, A to jump (or start) &
+/// start to start (or pause). Controllers can come & go while playing
+pub struct GamepadManager {
+    subsystem: GameControllerSubsystem,
+    deadzone: i16,
+    controllers: Vec<GameController>,
+
+    // Inputs held down along with the ID of the controller they are on (as
+    // events have it). An action is down as long as any of its inputs are
+    // held down
+    held_inputs: HashSet<(u32, Input)>,
+}
+
+impl GamepadManager {
+    /// `deadzone` is a fraction of the full tilt (0 to 1)
+    pub fn new(subsystem: GameControllerSubsystem, deadzone: f32) -> GamepadManager {
+        GamepadManager {
+            subsystem,
+            deadzone: (deadzone.clamp(0.0, 1.0) * f32::from(i16::MAX)) as i16,
+            controllers: Vec::new(),
+            held_inputs: HashSet::new(),
+        }
+    }
+
+    /// Controllers that are connected at start up show up as being added
+    /// too. So, there is no need to look for them separately
+    pub fn handle_event(&mut self, event: &SdlEvent, event_queue: &mut EventQueue) {
+        match *event {
+            SdlEvent::ControllerDeviceAdded { which, .. } => self.add_controller(which),
+            SdlEvent::ControllerDeviceRemoved { which, .. } => {
+                self.remove_controller(which, event_queue)
+            }
+            SdlEvent::ControllerButtonDown { which, button, .. } => {
+                self.hold(which, Input::Button(button), event_queue)
+            }
+            SdlEvent::ControllerButtonUp { which, button, .. } => {
+                self.release(which, Input::Button(button), event_queue)
+            }
+            SdlEvent::ControllerAxisMotion {
+                which, axis, value, ..
+            } => match axis {
+                Axis::LeftX => self.update_stick(
+                    which,
+                    Input::StickLeft,
+                    Input::StickRight,
+                    value,
+                    event_queue,
+                ),
+                Axis::LeftY => {
+                    self.update_stick(which, Input::StickUp, Input::StickDown, value, event_queue)
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn add_controller(&mut self, joystick_index: u32) {
+        if !self.subsystem.is_game_controller(joystick_index) {
+            return;
+        }
+
+        match self.subsystem.open(joystick_index) {
+            Ok(controller) => {
+                info!("Controller connected: {}", controller.name());
+                self.controllers.push(controller)
+            }
+            Err(error) => warn!("Couldn't open controller {}: {}", joystick_index, error),
+        }
+    }
+
+    fn remove_controller(&mut self, instance_id: u32, event_queue: &mut EventQueue) {
+        // Let go of everything that was held on this controller. Otherwise,
+        // the runner could be stuck sliding
+        let held_inputs: Vec<Input> = self
+            .held_inputs
+            .iter()
+            .filter(|(id, _)| *id == instance_id)
+            .map(|(_, input)| *input)
+            .collect();
+
+        for input in held_inputs {
+            self.release(instance_id, input, event_queue)
+        }
+
+        // Controllers hand out their IDs as `i32` even though events use `u32`
+        self.controllers
+            .retain(|controller| u32::try_from(controller.instance_id()) != Ok(instance_id));
+
+        info!("Controller disconnected: {}", instance_id)
+    }
+
+    /// Axis values are negative towards left/up & positive towards right/down
+    fn update_stick(
+        &mut self,
+        instance_id: u32,
+        negative: Input,
+        positive: Input,
+        value: i16,
+        event_queue: &mut EventQueue,
+    ) {
+        // Release before holding. This way, flicking the stick from one side
+        // to the other can't leave both sides held down
+        if value >= -self.deadzone {
+            self.release(instance_id, negative, event_queue)
+        }
+
+        if value <= self.deadzone {
+            self.release(instance_id, positive, event_queue)
+        }
+
+        if value < -self.deadzone {
+            self.hold(instance_id, negative, event_queue)
+        } else if value > self.deadzone {
+            self.hold(instance_id, positive, event_queue)
+        }
+    }
+
+    fn hold(&mut self, instance_id: u32, input: Input, event_queue: &mut EventQueue) {
+        if self.held_inputs.insert((instance_id, input)) {
+            for action in input.actions() {
+                if !self.is_held_elsewhere(*action, instance_id, input) {
+                    event_queue.add_event(Event::ActionDown(*action))
+                }
+            }
+        }
+    }
+
+    fn release(&mut self, instance_id: u32, input: Input, event_queue: &mut EventQueue) {
+        if self.held_inputs.remove(&(instance_id, input)) {
+            for action in input.actions() {
+                if !self.is_held_elsewhere(*action, instance_id, input) {
+                    event_queue.add_event(Event::ActionUp(*action))
+                }
+            }
+        }
+    }
+
+    fn is_held_elsewhere(&self, action: Action, instance_id: u32, input: Input) -> bool {
+        self.held_inputs
+            .iter()
+            .any(|held| *held != (instance_id, input) && held.1.actions().contains(&action))
+    }
+}
@@ -1,49 +1,200 @@
-use runner_core::input::{Event, Keycode};
+use crate::gamepad_manager::GamepadManager;
+use runner_core::data::WorldData;
+use runner_core::input::{Event, Keycode, Pointer, PointerId};
+use runner_core::rect::Rect;
 use runner_core::resources::EventQueue;
-use sdl2::event::Event as SdlEvent;
+use sdl2::event::{Event as SdlEvent, WindowEvent};
 use sdl2::keyboard::Keycode as SdlKeycode;
+use sdl2::mouse::MouseButton;
 use sdl2::EventPump;
 
+// SDL also reports touches as mouse events from this (made up) mouse. These
+// are skipped since the touches themselves are already taken care of
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+
 pub struct InputManager {
+    world_data: WorldData,
     event_pump: EventPump,
+    gamepad_manager: GamepadManager,
 }
 
 impl InputManager {
-    pub fn new(event_pump: EventPump) -> InputManager {
-        InputManager { event_pump }
+    pub fn new(
+        world_data: WorldData,
+        event_pump: EventPump,
+        gamepad_manager: GamepadManager,
+    ) -> InputManager {
+        InputManager {
+            world_data,
+            event_pump,
+            gamepad_manager,
+        }
     }
 
+    /// Keys & pointers are passed on as is. It is up to the core to figure
+    /// out which actions they are meant for. Controllers skip all that &
+    /// directly deliver actions
     pub fn reset_and_populate(&mut self, event_queue: &mut EventQueue) {
         event_queue.reset();
+        let world_bounds = self.world_data.bounds();
         for event in self.event_pump.poll_iter() {
             match event {
                 SdlEvent::Quit { .. } => event_queue.add_event(Event::Quit),
+                SdlEvent::Window {
+                    win_event: WindowEvent::FocusLost,
+                    ..
+                } => event_queue.add_event(Event::FocusLost),
                 SdlEvent::KeyDown {
                     keycode: Some(keycode),
                     ..
-                } => match keycode {
-                    SdlKeycode::Up => event_queue.add_event(Event::KeyDown(Keycode::Up)),
-                    SdlKeycode::Down => event_queue.add_event(Event::KeyDown(Keycode::Down)),
-                    SdlKeycode::Left => event_queue.add_event(Event::KeyDown(Keycode::Left)),
-                    SdlKeycode::Right => event_queue.add_event(Event::KeyDown(Keycode::Right)),
-                    SdlKeycode::Escape => event_queue.add_event(Event::KeyDown(Keycode::Escape)),
-                    SdlKeycode::Space => event_queue.add_event(Event::KeyDown(Keycode::Space)),
-                    _ => {}
-                },
+                } => {
+                    if let Some(keycode) = to_keycode(keycode) {
+                        event_queue.add_event(Event::KeyDown(keycode))
+                    }
+                }
                 SdlEvent::KeyUp {
                     keycode: Some(keycode),
                     ..
-                } => match keycode {
-                    SdlKeycode::Up => event_queue.add_event(Event::KeyUp(Keycode::Up)),
-                    SdlKeycode::Down => event_queue.add_event(Event::KeyUp(Keycode::Down)),
-                    SdlKeycode::Left => event_queue.add_event(Event::KeyUp(Keycode::Left)),
-                    SdlKeycode::Right => event_queue.add_event(Event::KeyUp(Keycode::Right)),
-                    SdlKeycode::Escape => event_queue.add_event(Event::KeyUp(Keycode::Escape)),
-                    SdlKeycode::Space => event_queue.add_event(Event::KeyUp(Keycode::Space)),
-                    _ => {}
-                },
-                _ => {}
+                } => {
+                    if let Some(keycode) = to_keycode(keycode) {
+                        event_queue.add_event(Event::KeyUp(keycode))
+                    }
+                }
+                SdlEvent::MouseButtonDown {
+                    which,
+                    mouse_btn: MouseButton::Left,
+                    x,
+                    y,
+                    ..
+                } if which != TOUCH_MOUSE_ID => {
+                    event_queue.add_event(Event::PointerDown(mouse_pointer(&world_bounds, x, y)))
+                }
+                SdlEvent::MouseMotion {
+                    which,
+                    mousestate,
+                    x,
+                    y,
+                    ..
+                } if which != TOUCH_MOUSE_ID && mousestate.left() => {
+                    event_queue.add_event(Event::PointerMove(mouse_pointer(&world_bounds, x, y)))
+                }
+                SdlEvent::MouseButtonUp {
+                    which,
+                    mouse_btn: MouseButton::Left,
+                    x,
+                    y,
+                    ..
+                } if which != TOUCH_MOUSE_ID => {
+                    event_queue.add_event(Event::PointerUp(mouse_pointer(&world_bounds, x, y)))
+                }
+                SdlEvent::FingerDown {
+                    finger_id, x, y, ..
+                } => {
+                    let pointer = finger_pointer(&world_bounds, finger_id, x, y);
+                    event_queue.add_event(Event::PointerDown(pointer))
+                }
+                SdlEvent::FingerMotion {
+                    finger_id, x, y, ..
+                } => {
+                    let pointer = finger_pointer(&world_bounds, finger_id, x, y);
+                    event_queue.add_event(Event::PointerMove(pointer))
+                }
+                SdlEvent::FingerUp {
+                    finger_id, x, y, ..
+                } => {
+                    let pointer = finger_pointer(&world_bounds, finger_id, x, y);
+                    event_queue.add_event(Event::PointerUp(pointer))
+                }
+                event => self.gamepad_manager.handle_event(&event, event_queue),
             }
         }
     }
 }
+
+/// The window is exactly as big as the world. So, mouse positions (which
+/// are in pixels from the top left of the window) just need to be moved
+fn mouse_pointer(world_bounds: &Rect, x: i32, y: i32) -> Pointer {
+    Pointer {
+        id: PointerId::Mouse,
+        x: world_bounds.left() + x,
+        y: world_bounds.top() + y,
+    }
+}
+
+/// Finger positions are fractions (0 to 1) of the window
+fn finger_pointer(world_bounds: &Rect, finger_id: i64, x: f32, y: f32) -> Pointer {
+    Pointer {
+        id: PointerId::Touch(finger_id),
+        x: world_bounds.left() + (x * world_bounds.width() as f32) as i32,
+        y: world_bounds.top() + (y * world_bounds.height() as f32) as i32,
+    }
+}
+
+fn to_keycode(keycode: SdlKeycode) -> Option<Keycode> {
+    let keycode = match keycode {
+        SdlKeycode::Up => Keycode::Up,
+        SdlKeycode::Down => Keycode::Down,
+        SdlKeycode::Left => Keycode::Left,
+        SdlKeycode::Right => Keycode::Right,
+        SdlKeycode::Escape => Keycode::Escape,
+        SdlKeycode::Space => Keycode::Space,
+        SdlKeycode::Return => Keycode::Return,
+        SdlKeycode::Backspace => Keycode::Backspace,
+        SdlKeycode::Tab => Keycode::Tab,
+        SdlKeycode::LShift => Keycode::LShift,
+        SdlKeycode::RShift => Keycode::RShift,
+        SdlKeycode::LCtrl => Keycode::LCtrl,
+        SdlKeycode::RCtrl => Keycode::RCtrl,
+        SdlKeycode::A => Keycode::A,
+        SdlKeycode::B => Keycode::B,
+        SdlKeycode::C => Keycode::C,
+        SdlKeycode::D => Keycode::D,
+        SdlKeycode::E => Keycode::E,
+        SdlKeycode::F => Keycode::F,
+        SdlKeycode::G => Keycode::G,
+        SdlKeycode::H => Keycode::H,
+        SdlKeycode::I => Keycode::I,
+        SdlKeycode::J => Keycode::J,
+        SdlKeycode::K => Keycode::K,
+        SdlKeycode::L => Keycode::L,
+        SdlKeycode::M => Keycode::M,
+        SdlKeycode::N => Keycode::N,
+        SdlKeycode::O => Keycode::O,
+        SdlKeycode::P => Keycode::P,
+        SdlKeycode::Q => Keycode::Q,
+        SdlKeycode::R => Keycode::R,
+        SdlKeycode::S => Keycode::S,
+        SdlKeycode::T => Keycode::T,
+        SdlKeycode::U => Keycode::U,
+        SdlKeycode::V => Keycode::V,
+        SdlKeycode::W => Keycode::W,
+        SdlKeycode::X => Keycode::X,
+        SdlKeycode::Y => Keycode::Y,
+        SdlKeycode::Z => Keycode::Z,
+        SdlKeycode::Num0 => Keycode::Num0,
+        SdlKeycode::Num1 => Keycode::Num1,
+        SdlKeycode::Num2 => Keycode::Num2,
+        SdlKeycode::Num3 => Keycode::Num3,
+        SdlKeycode::Num4 => Keycode::Num4,
+        SdlKeycode::Num5 => Keycode::Num5,
+        SdlKeycode::Num6 => Keycode::Num6,
+        SdlKeycode::Num7 => Keycode::Num7,
+        SdlKeycode::Num8 => Keycode::Num8,
+        SdlKeycode::Num9 => Keycode::Num9,
+        SdlKeycode::F1 => Keycode::F1,
+        SdlKeycode::F2 => Keycode::F2,
+        SdlKeycode::F3 => Keycode::F3,
+        SdlKeycode::F4 => Keycode::F4,
+        SdlKeycode::F5 => Keycode::F5,
+        SdlKeycode::F6 => Keycode::F6,
+        SdlKeycode::F7 => Keycode::F7,
+        SdlKeycode::F8 => Keycode::F8,
+        SdlKeycode::F9 => Keycode::F9,
+        SdlKeycode::F10 => Keycode::F10,
+        SdlKeycode::F11 => Keycode::F11,
+        SdlKeycode::F12 => Keycode::F12,
+        _ => return None,
+    };
+
+    Some(keycode)
+}
@@ -1,21 +1,36 @@
 extern crate sdl2;
 
 use crate::frame_limiter::FrameLimiter;
+use crate::gamepad_manager::GamepadManager;
 use crate::input_manager::InputManager;
 use crate::renderer::Renderer;
 use crate::textures::Textures;
-use runner_core::data::WorldData;
+use log::{info, warn};
+use runner_core::data::{PlayerData, WorldData};
 use runner_core::game_loop::{GameLoop, GameLoopResult};
+use runner_core::render;
+use runner_frontend_utils::{DifficultyStore, Fps, HighScoreStore, KeyBindingsStore, LivesStore};
 use sdl2::render::WindowCanvas;
 use sdl2::Sdl;
 use simplelog::{Config, LevelFilter, SimpleLogger};
+use std::env;
+use std::fs;
 
 mod color;
 mod frame_limiter;
+mod gamepad_manager;
 mod input_manager;
 mod renderer;
 mod textures;
 
+const DEADZONE_ENV_VAR: &str = "RUNNER_GAMEPAD_DEADZONE";
+
+/// Usage: `runner_sdl2_frontend [replay-file]`. If a replay file is given,
+/// every game that comes to an end is saved to it (overwriting the last one).
+///
+/// The deadzone of controller sticks can be set (as a fraction of the full
+/// tilt) through the `RUNNER_GAMEPAD_DEADZONE` environment variable. Check
+/// out [LivesStore] to play with more than one life
 pub fn main() {
     // Initiate log before anything else
     SimpleLogger::init(LevelFilter::Debug, Config::default()).expect("log couldn't be initiated");
@@ -24,7 +39,7 @@ pub fn main() {
     let (sdl, mut canvas) = build_canvas(world_data);
 
     setup_splash_screen(world_data, &mut canvas);
-    run_game_loop(world_data, sdl, canvas);
+    run_game_loop(world_data, sdl, canvas, env::args().nth(1));
 }
 
 fn build_canvas(world_data: WorldData) -> (Sdl, WindowCanvas) {
@@ -52,15 +67,33 @@ fn setup_splash_screen(world_data: WorldData, canvas: &mut WindowCanvas) {
     canvas.present();
 }
 
-fn run_game_loop(world_data: WorldData, sdl: Sdl, canvas: WindowCanvas) {
+fn run_game_loop(
+    world_data: WorldData,
+    sdl: Sdl,
+    canvas: WindowCanvas,
+    replay_file_path: Option<String>,
+) {
     let mut frame_limiter = FrameLimiter::new(60);
+    let mut fps = Fps::new();
 
+    let tile_atlas = textures::load_tile_atlas();
     let texture_creator = canvas.texture_creator();
-    let textures = Textures::load_from_files(&texture_creator);
-
-    let mut game_loop = GameLoop::new(world_data);
-    let mut renderer = Renderer::new(world_data, canvas, textures);
-    let mut input_manager = InputManager::new(sdl.event_pump().unwrap());
+    let textures = Textures::load_from_files(&tile_atlas, &texture_creator);
+
+    let mut game_loop = GameLoop::new(
+        world_data,
+        tile_atlas,
+        HighScoreStore::load(),
+        KeyBindingsStore::load(),
+    )
+    .with_player_data(PlayerData::new().with_lives(LivesStore::load()))
+    .with_difficulties(DifficultyStore::load());
+    let mut renderer = Renderer::new(canvas, textures);
+    let mut input_manager = InputManager::new(
+        world_data,
+        sdl.event_pump().unwrap(),
+        GamepadManager::new(sdl.game_controller().unwrap(), gamepad_deadzone()),
+    );
 
     'running: loop {
         {
@@ -74,13 +107,45 @@ fn run_game_loop(world_data: WorldData, sdl: Sdl, canvas: WindowCanvas) {
         // Execute loop once
         match game_loop.execute() {
             GameLoopResult::Continue => {}
+            GameLoopResult::GameOver => {
+                HighScoreStore::save(game_loop.high_scores());
+                if let Some(path) = &replay_file_path {
+                    fs::write(path, game_loop.replay().to_ron())
+                        .unwrap_or_else(|_| panic!("Couldn't write replay: {}", path));
+                    info!("Replay saved to {}", path)
+                }
+            }
             GameLoopResult::Quit => break 'running,
         }
 
         // Display whatever we have
-        renderer.draw(game_loop.drawables_storage());
+        fps.track();
+        render::draw_frame(
+            &mut renderer,
+            &world_data,
+            game_loop.transforms_storage(),
+            game_loop.drawables_storage(),
+            game_loop.hidden_storage(),
+            &game_loop.debug_overlay(fps.fps()),
+        );
 
         // We don't want to drink up too much power
         frame_limiter.limit_as_required();
     }
 }
+
+fn gamepad_deadzone() -> f32 {
+    match env::var(DEADZONE_ENV_VAR) {
+        Err(_) => gamepad_manager::DEFAULT_DEADZONE,
+        Ok(value) => match value.parse::<f32>() {
+            Ok(deadzone) if (0.0..=1.0).contains(&deadzone) => deadzone,
+            _ => {
+                warn!(
+                    "Ignoring {}. Expected 0 to 1, found: {}",
+                    DEADZONE_ENV_VAR, value
+                );
+                gamepad_manager::DEFAULT_DEADZONE
+            }
+        },
+    }
+}
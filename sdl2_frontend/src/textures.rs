@@ -1,13 +1,26 @@
+use runner_core::graphics::data::TileSheet;
+use runner_core::graphics::tile_atlas::TileAtlas;
 use rust_embed::RustEmbed;
 use sdl2::image::ImageRWops;
 use sdl2::render::{Texture, TextureCreator};
 use sdl2::rwops::RWops;
 use sdl2::video::WindowContext;
+use std::str;
 
 #[derive(RustEmbed)]
 #[folder = "../assets_processed/"]
 struct Asset;
 
+const TILE_ATLAS_FILENAME: &str = "tile_atlas.ron";
+
+pub fn load_tile_atlas() -> TileAtlas {
+    let bytes = Asset::get(TILE_ATLAS_FILENAME).unwrap();
+    let text = str::from_utf8(&bytes)
+        .unwrap_or_else(|_| panic!("Couldn't read file: {}", TILE_ATLAS_FILENAME));
+
+    TileAtlas::from_ron(text).unwrap_or_else(|error| panic!("{}", error))
+}
+
 pub struct Textures<'a> {
     pub cloud_texture: Texture<'a>,
     pub character_texture: Texture<'a>,
@@ -19,18 +32,23 @@ pub struct Textures<'a> {
 }
 
 impl<'a> Textures<'a> {
-    pub fn load_from_files(texture_creator: &'a TextureCreator<WindowContext>) -> Textures<'a> {
+    pub fn load_from_files(
+        tile_atlas: &TileAtlas,
+        texture_creator: &'a TextureCreator<WindowContext>,
+    ) -> Textures<'a> {
         // TODO - Couldn't find a way to hold `creator` in the same struct too!
         // Compiler kept complaining & none of my workarounds did anything :(
-        let character_texture =
-            Textures::load_from_file("character_tile_sheet.png", texture_creator);
-
-        let cloud_texture = Textures::load_from_file("cloud_tile_sheet.png", texture_creator);
-        let enemy_texture = Textures::load_from_file("enemy_tile_sheet.png", texture_creator);
-        let icon_texture = Textures::load_from_file("icon_tile_sheet.png", texture_creator);
-        let letter_texture = Textures::load_from_file("letter_tile_sheet.png", texture_creator);
-        let platform_texture = Textures::load_from_file("platform_tile_sheet.png", texture_creator);
-        let number_texture = Textures::load_from_file("number_tile_sheet.png", texture_creator);
+        let load = |tile_sheet| {
+            Textures::load_from_file(tile_atlas.image_file(tile_sheet), texture_creator)
+        };
+
+        let character_texture = load(TileSheet::Character);
+        let cloud_texture = load(TileSheet::Cloud);
+        let enemy_texture = load(TileSheet::Enemy);
+        let icon_texture = load(TileSheet::Icon);
+        let letter_texture = load(TileSheet::Letter);
+        let platform_texture = load(TileSheet::Platform);
+        let number_texture = load(TileSheet::Number);
 
         Textures {
             cloud_texture,